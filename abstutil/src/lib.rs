@@ -103,6 +103,14 @@ pub fn path_all_ab_test_saves(map_name: &str, test_name: &str) -> String {
     format!("../data/player/ab_test_saves/{}/{}", map_name, test_name)
 }
 
+pub fn path_options() -> String {
+    format!("../data/player/options.json")
+}
+
+pub fn path_keybindings() -> String {
+    format!("../data/player/keybindings.json")
+}
+
 pub fn path_camera_state(map_name: &str) -> String {
     format!("../data/player/camera_state/{}.json", map_name)
 }
@@ -127,6 +135,28 @@ pub fn path_all_saves(map_name: &str, edits_name: &str, run_name: &str) -> Strin
     )
 }
 
+pub fn path_metric(map_name: &str, name: &str) -> String {
+    format!("../data/player/metrics/{}/{}.csv", map_name, name)
+}
+pub fn path_all_metrics(map_name: &str) -> String {
+    format!("../data/player/metrics/{}", map_name)
+}
+
+pub fn path_chart_export(name: &str) -> String {
+    format!("../data/player/charts/{}.csv", name)
+}
+
+pub fn path_map_image(map_name: &str, time: &str, extension: &str) -> String {
+    format!("../data/player/images/{}/{}.{}", map_name, time, extension)
+}
+
+pub fn path_departure_profile(name: &str) -> String {
+    format!("../data/player/departure_profiles/{}.txt", name)
+}
+pub fn path_all_departure_profiles() -> String {
+    format!("../data/player/departure_profiles")
+}
+
 pub fn path_shortcut(name: &str) -> String {
     format!("../data/player/shortcuts/{}.json", name)
 }
@@ -140,6 +170,10 @@ pub fn path_fixes(name: &str) -> String {
     format!("../data/input/fixes/{}.json", name)
 }
 
+pub fn path_map_validation(map_name: &str) -> String {
+    format!("../data/input/validation/{}.json", map_name)
+}
+
 pub fn path_neighborhood(map_name: &str, neighborhood: &str) -> String {
     format!(
         "../data/input/neighborhoods/{}/{}.json",
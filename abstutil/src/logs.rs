@@ -56,6 +56,10 @@ impl<T> Warn<T> {
         self.value
     }
 
+    pub fn get_warnings(&self) -> &Vec<String> {
+        &self.warnings
+    }
+
     pub fn map<O, F: Fn(T) -> O>(self, f: F) -> Warn<O> {
         Warn {
             value: f(self.value),
@@ -0,0 +1,33 @@
+use abstutil::{CmdArgs, Timer};
+use geom::Duration;
+use map_model::{AccessibilityScores, Map, MapEdits, Opportunity};
+
+// Compares how many jobs, shops, and amenities each neighborhood can reach before and after some
+// map edits.
+fn main() {
+    let mut args = CmdArgs::new();
+    let map_name = args.required("--map");
+    let edits_name = args.required("--edits");
+    let minutes = args
+        .optional_parse("--minutes", |s| s.parse::<usize>())
+        .unwrap_or(15);
+    args.done();
+
+    let mut timer = Timer::new(format!("accessibility of {} on {}", edits_name, map_name));
+    let mut map = Map::new(abstutil::path_map(&map_name), false, &mut timer);
+    let limit = Duration::minutes(minutes);
+
+    let before = AccessibilityScores::new(&map, Opportunity::defaults(), limit, &mut timer);
+
+    map.apply_edits(
+        MapEdits::load(map.get_name(), &edits_name, &mut timer),
+        &mut timer,
+    );
+    map.recalculate_pathfinding_after_edits(&mut timer);
+    let after = AccessibilityScores::new(&map, Opportunity::defaults(), limit, &mut timer);
+    timer.done();
+
+    for line in AccessibilityScores::compare(&before, &after, &map) {
+        println!("{}", line);
+    }
+}
@@ -94,7 +94,7 @@ pub fn extract_osm(
                 && !tags.contains_key(osm::PARKING_BOTH)
                 && tags.get(osm::HIGHWAY) != Some(&"motorway".to_string())
                 && tags.get(osm::HIGHWAY) != Some(&"motorway_link".to_string())
-                && tags.get("junction") != Some(&"roundabout".to_string())
+                && tags.get(osm::JUNCTION) != Some(&"roundabout".to_string())
             {
                 tags.insert(osm::PARKING_BOTH.to_string(), "no_parking".to_string());
                 tags.insert(osm::INFERRED_PARKING.to_string(), "true".to_string());
@@ -106,7 +106,7 @@ pub fn extract_osm(
                 tags.insert(osm::INFERRED_SIDEWALKS.to_string(), "true".to_string());
                 if tags.get(osm::HIGHWAY) == Some(&"motorway".to_string())
                     || tags.get(osm::HIGHWAY) == Some(&"motorway_link".to_string())
-                    || tags.get("junction") == Some(&"roundabout".to_string())
+                    || tags.get(osm::JUNCTION) == Some(&"roundabout".to_string())
                 {
                     tags.insert(osm::SIDEWALK.to_string(), "none".to_string());
                 } else if tags.get("oneway") == Some(&"yes".to_string()) {
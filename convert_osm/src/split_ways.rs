@@ -19,13 +19,45 @@ pub fn split_up_roads(
     timer.start("splitting up roads");
 
     let mut pt_to_intersection: HashMap<HashablePt2D, OriginalIntersection> = HashMap::new();
+    // Ways that share a node on different layers -- a bridge over a street, a tunnel under it --
+    // aren't really connected, so count separately per layer.
     let mut counts_per_pt = Counter::new();
+    let mut shared_on_layer: HashSet<(HashablePt2D, isize)> = HashSet::new();
+    // Ways on different layers only connect where one of them explicitly changes layers there: a
+    // ramp ending anywhere, or a bridge or tunnel starting from a way at ground level. A dead-end
+    // street that happens to end on a node of a bridge shouldn't connect to it.
+    let mut ramp_ends: HashSet<HashablePt2D> = HashSet::new();
+    let mut grade_separated_ends: HashSet<HashablePt2D> = HashSet::new();
+    // Every point along a roundabout's ring that winds up being an intersection is controlled as
+    // part of the roundabout.
+    let mut roundabout_pts: HashSet<HashablePt2D> = HashSet::new();
     for (_, r) in &roads {
+        let roundabout = r.osm_tags.get(osm::JUNCTION) == Some(&"roundabout".to_string());
+        let zorder = r.get_zorder();
+        let ramp = r
+            .osm_tags
+            .get(osm::HIGHWAY)
+            .map(|hwy| hwy.ends_with("_link"))
+            .unwrap_or(false);
         for (idx, raw_pt) in r.center_points.iter().enumerate() {
             let pt = raw_pt.to_hashable();
-            let count = counts_per_pt.inc(pt);
+            let count = counts_per_pt.inc((pt, zorder));
+            if roundabout {
+                roundabout_pts.insert(pt);
+            }
 
+            if count == 2 {
+                shared_on_layer.insert((pt, zorder));
+            }
             // All start and endpoints of ways are also intersections.
+            if idx == 0 || idx == r.center_points.len() - 1 {
+                if ramp {
+                    ramp_ends.insert(pt);
+                }
+                if zorder != 0 {
+                    grade_separated_ends.insert(pt);
+                }
+            }
             if count == 2 || idx == 0 || idx == r.center_points.len() - 1 {
                 if !pt_to_intersection.contains_key(&pt) {
                     let id = OriginalIntersection {
@@ -44,6 +76,8 @@ pub fn split_up_roads(
                 point: pt.to_pt2d(),
                 intersection_type: if traffic_signals.contains(pt) {
                     IntersectionType::TrafficSignal
+                } else if roundabout_pts.contains(pt) {
+                    IntersectionType::Roundabout
                 } else {
                     IntersectionType::StopSign
                 },
@@ -60,13 +94,22 @@ pub fn split_up_roads(
         let endpt1 = pt_to_intersection[&orig_road.center_points[0].to_hashable()];
         let endpt2 = pt_to_intersection[&orig_road.center_points.last().unwrap().to_hashable()];
         let mut i1 = endpt1;
+        let zorder = orig_road.get_zorder();
 
-        for pt in &orig_road.center_points {
+        for (idx, pt) in orig_road.center_points.iter().enumerate() {
             pts.push(*pt);
             if pts.len() == 1 {
                 continue;
             }
-            if let Some(i2) = pt_to_intersection.get(&pt.to_hashable()) {
+            let hash_pt = pt.to_hashable();
+            // Don't split a bridge just because some street underneath it has an intersection at
+            // a node they happen to share.
+            if let Some(i2) = pt_to_intersection.get(&hash_pt).filter(|_| {
+                idx == orig_road.center_points.len() - 1
+                    || shared_on_layer.contains(&(hash_pt, zorder))
+                    || ramp_ends.contains(&hash_pt)
+                    || (zorder == 0 && grade_separated_ends.contains(&hash_pt))
+            }) {
                 if i1 == endpt1 {
                     r.osm_tags
                         .insert(osm::ENDPT_BACK.to_string(), "true".to_string());
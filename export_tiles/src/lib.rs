@@ -0,0 +1,283 @@
+// Writes Mapbox Vector Tiles (https://github.com/mapbox/vector-tile-spec). The export_tiles tool
+// turns a map into features; this handles projecting and encoding them.
+
+use geom::LonLat;
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Clone, PartialEq)]
+pub enum Value {
+    String(String),
+    Number(f64),
+    Bool(bool),
+}
+
+pub enum Geometry {
+    Point(LonLat),
+    Line(Vec<LonLat>),
+    // Just the outer ring
+    Polygon(Vec<LonLat>),
+}
+
+pub struct Feature {
+    pub id: u64,
+    pub geometry: Geometry,
+    pub props: Vec<(&'static str, Value)>,
+}
+
+// Tiles are EXTENT units across. Geometry can poke out of a tile by BUFFER units, so shapes
+// spanning tiles line up. Nothing is clipped; renderers cope with coordinates outside the extent.
+pub const EXTENT: u32 = 4096;
+pub const BUFFER: f64 = 64.0;
+
+impl Feature {
+    pub fn new(id: u64, geometry: Geometry, props: Vec<(&'static str, Value)>) -> Feature {
+        Feature {
+            id,
+            geometry,
+            props,
+        }
+    }
+
+    fn points(&self) -> Vec<LonLat> {
+        match self.geometry {
+            Geometry::Point(pt) => vec![pt],
+            Geometry::Line(ref pts) | Geometry::Polygon(ref pts) => pts.clone(),
+        }
+    }
+
+    pub fn tiles(&self, zoom: u32) -> Vec<(u32, u32)> {
+        let pts: Vec<(f64, f64)> = self
+            .points()
+            .into_iter()
+            .map(|pt| project(pt, zoom))
+            .collect();
+        if pts.is_empty() {
+            return Vec::new();
+        }
+        let buffer = BUFFER / f64::from(EXTENT);
+        let max_tile = f64::from((1u32 << zoom) - 1);
+        let clamp = |v: f64| v.max(0.0).min(max_tile) as u32;
+        let min_x = clamp((pts.iter().map(|p| p.0).fold(std::f64::MAX, f64::min) - buffer).floor());
+        let max_x = clamp((pts.iter().map(|p| p.0).fold(std::f64::MIN, f64::max) + buffer).floor());
+        let min_y = clamp((pts.iter().map(|p| p.1).fold(std::f64::MAX, f64::min) - buffer).floor());
+        let max_y = clamp((pts.iter().map(|p| p.1).fold(std::f64::MIN, f64::max) + buffer).floor());
+        let mut tiles = Vec::new();
+        for x in min_x..=max_x {
+            for y in min_y..=max_y {
+                tiles.push((x, y));
+            }
+        }
+        tiles
+    }
+}
+
+// Web Mercator, in units of tiles at this zoom
+pub fn project(pt: LonLat, zoom: u32) -> (f64, f64) {
+    let n = f64::from(1u32 << zoom);
+    let lat = pt.latitude.to_radians();
+    let x = (pt.longitude + 180.0) / 360.0 * n;
+    let y = (1.0 - (lat.tan() + 1.0 / lat.cos()).ln() / std::f64::consts::PI) / 2.0 * n;
+    (x, y)
+}
+
+pub fn encode_tile(zoom: u32, x: u32, y: u32, features: Vec<(&str, &Feature)>) -> Vec<u8> {
+    let mut per_layer: BTreeMap<&str, Vec<&Feature>> = BTreeMap::new();
+    for (layer, f) in features {
+        per_layer.entry(layer).or_insert_with(Vec::new).push(f);
+    }
+
+    let mut tile = Protobuf::new();
+    for (name, features) in per_layer {
+        let mut layer = Protobuf::new();
+        layer.uint(15, 2);
+        layer.string(1, name);
+
+        let mut keys: Vec<&str> = Vec::new();
+        let mut key_idx: HashMap<&str, u32> = HashMap::new();
+        let mut values: Vec<Value> = Vec::new();
+        for f in features {
+            let geometry = encode_geometry(&f.geometry, zoom, x, y);
+            if geometry.is_empty() {
+                continue;
+            }
+            let mut tags = Vec::new();
+            for (k, v) in &f.props {
+                let k_idx = *key_idx.entry(*k).or_insert_with(|| {
+                    keys.push(*k);
+                    (keys.len() - 1) as u32
+                });
+                let v_idx = match values.iter().position(|existing| existing == v) {
+                    Some(idx) => idx as u32,
+                    None => {
+                        values.push(v.clone());
+                        (values.len() - 1) as u32
+                    }
+                };
+                tags.push(k_idx);
+                tags.push(v_idx);
+            }
+
+            let mut feature = Protobuf::new();
+            feature.uint(1, f.id);
+            feature.packed(2, &tags);
+            feature.uint(
+                3,
+                match f.geometry {
+                    Geometry::Point(_) => 1,
+                    Geometry::Line(_) => 2,
+                    Geometry::Polygon(_) => 3,
+                },
+            );
+            feature.packed(4, &geometry);
+            layer.message(2, &feature);
+        }
+
+        for k in keys {
+            layer.string(3, k);
+        }
+        for v in values {
+            let mut value = Protobuf::new();
+            match v {
+                Value::String(s) => value.string(1, &s),
+                Value::Number(n) => value.double(3, n),
+                Value::Bool(b) => value.uint(7, if b { 1 } else { 0 }),
+            }
+            layer.message(4, &value);
+        }
+        layer.uint(5, u64::from(EXTENT));
+        tile.message(3, &layer);
+    }
+    tile.0
+}
+
+// Returns the command stream, or nothing if the geometry collapses at this zoom.
+pub fn encode_geometry(geom: &Geometry, zoom: u32, tile_x: u32, tile_y: u32) -> Vec<u32> {
+    let to_tile = |pt: &LonLat| {
+        let (x, y) = project(*pt, zoom);
+        (
+            ((x - f64::from(tile_x)) * f64::from(EXTENT)).round() as i32,
+            ((y - f64::from(tile_y)) * f64::from(EXTENT)).round() as i32,
+        )
+    };
+    let mut pts: Vec<(i32, i32)> = match geom {
+        Geometry::Point(pt) => vec![to_tile(pt)],
+        Geometry::Line(pts) | Geometry::Polygon(pts) => pts.iter().map(to_tile).collect(),
+    };
+    pts.dedup();
+
+    let mut cmds = Vec::new();
+    let mut cursor = (0, 0);
+    let mut move_to = |cmds: &mut Vec<u32>, pt: (i32, i32)| {
+        cmds.push(zigzag(pt.0 - cursor.0));
+        cmds.push(zigzag(pt.1 - cursor.1));
+        cursor = pt;
+    };
+    match geom {
+        Geometry::Point(_) => {
+            cmds.push(command(1, 1));
+            move_to(&mut cmds, pts[0]);
+        }
+        Geometry::Line(_) => {
+            if pts.len() < 2 {
+                return Vec::new();
+            }
+            cmds.push(command(1, 1));
+            move_to(&mut cmds, pts[0]);
+            cmds.push(command(2, pts.len() - 1));
+            for pt in pts.iter().skip(1) {
+                move_to(&mut cmds, *pt);
+            }
+        }
+        Geometry::Polygon(_) => {
+            if pts.len() > 1 && pts[0] == pts[pts.len() - 1] {
+                pts.pop();
+            }
+            if pts.len() < 3 {
+                return Vec::new();
+            }
+            // The outer ring has to be clockwise in tile coordinates, where y points down.
+            let area: i64 = (0..pts.len())
+                .map(|i| {
+                    let (x1, y1) = pts[i];
+                    let (x2, y2) = pts[(i + 1) % pts.len()];
+                    i64::from(x1) * i64::from(y2) - i64::from(x2) * i64::from(y1)
+                })
+                .sum();
+            if area == 0 {
+                return Vec::new();
+            }
+            if area < 0 {
+                pts.reverse();
+            }
+            cmds.push(command(1, 1));
+            move_to(&mut cmds, pts[0]);
+            cmds.push(command(2, pts.len() - 1));
+            for pt in pts.iter().skip(1) {
+                move_to(&mut cmds, *pt);
+            }
+            cmds.push(command(7, 1));
+        }
+    }
+    cmds
+}
+
+pub fn command(id: u32, count: usize) -> u32 {
+    (id & 0x7) | ((count as u32) << 3)
+}
+
+pub fn zigzag(n: i32) -> u32 {
+    ((n << 1) ^ (n >> 31)) as u32
+}
+
+// Just enough of the protobuf wire format to write vector tiles
+pub struct Protobuf(pub Vec<u8>);
+
+impl Protobuf {
+    pub fn new() -> Protobuf {
+        Protobuf(Vec::new())
+    }
+
+    pub fn varint(&mut self, mut x: u64) {
+        while x >= 0x80 {
+            self.0.push((x as u8) | 0x80);
+            x >>= 7;
+        }
+        self.0.push(x as u8);
+    }
+
+    pub fn key(&mut self, field: u32, wire_type: u32) {
+        self.varint(u64::from((field << 3) | wire_type));
+    }
+
+    pub fn uint(&mut self, field: u32, x: u64) {
+        self.key(field, 0);
+        self.varint(x);
+    }
+
+    pub fn double(&mut self, field: u32, x: f64) {
+        self.key(field, 1);
+        self.0.extend_from_slice(&x.to_bits().to_le_bytes());
+    }
+
+    pub fn bytes(&mut self, field: u32, bytes: &[u8]) {
+        self.key(field, 2);
+        self.varint(bytes.len() as u64);
+        self.0.extend_from_slice(bytes);
+    }
+
+    pub fn string(&mut self, field: u32, s: &str) {
+        self.bytes(field, s.as_bytes());
+    }
+
+    pub fn message(&mut self, field: u32, msg: &Protobuf) {
+        self.bytes(field, &msg.0);
+    }
+
+    pub fn packed(&mut self, field: u32, list: &Vec<u32>) {
+        let mut inner = Protobuf::new();
+        for x in list {
+            inner.varint(u64::from(*x));
+        }
+        self.bytes(field, &inner.0);
+    }
+}
@@ -0,0 +1,332 @@
+use abstutil::{CmdArgs, Timer};
+use export_tiles::{encode_tile, Feature, Geometry, Value};
+use geom::{LonLat, Pt2D};
+use map_model::{Map, MapEdits};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Write;
+
+// Exports the map as Mapbox Vector Tiles (https://github.com/mapbox/vector-tile-spec), along with
+// a TileJSON file and a simple viewer, so the map and edits can be browsed without the game.
+fn main() {
+    let mut args = CmdArgs::new();
+    let map_name = args.required("--map");
+    let edits_name = args.optional("--edits");
+    let min_zoom = args
+        .optional_parse("--min_zoom", |s| s.parse::<u32>())
+        .unwrap_or(13);
+    let max_zoom = args
+        .optional_parse("--max_zoom", |s| s.parse::<u32>())
+        .unwrap_or(17);
+    let out_dir = args
+        .optional("--out")
+        .unwrap_or_else(|| format!("../data/player/tiles/{}", map_name));
+    args.done();
+
+    let mut timer = Timer::new(format!("export tiles for {}", map_name));
+    let mut map = Map::new(abstutil::path_map(&map_name), false, &mut timer);
+    if let Some(edits) = edits_name {
+        map.apply_edits(
+            MapEdits::load(map.get_name(), &edits, &mut timer),
+            &mut timer,
+        );
+    }
+
+    let layers = vec![
+        ("areas", areas(&map)),
+        ("lanes", lanes(&map)),
+        ("intersections", intersections(&map)),
+        ("buildings", buildings(&map)),
+        ("bus_stops", bus_stops(&map)),
+    ];
+
+    let mut num_tiles = 0;
+    for zoom in min_zoom..=max_zoom {
+        timer.start(format!("zoom {}", zoom));
+        // Which tiles does each feature touch?
+        let mut per_tile: BTreeMap<(u32, u32), Vec<(&str, &Feature)>> = BTreeMap::new();
+        for (name, features) in &layers {
+            for f in features {
+                for tile in f.tiles(zoom) {
+                    per_tile
+                        .entry(tile)
+                        .or_insert_with(Vec::new)
+                        .push((*name, f));
+                }
+            }
+        }
+        for ((x, y), features) in per_tile {
+            let path = format!("{}/{}/{}/{}.pbf", out_dir, zoom, x, y);
+            std::fs::create_dir_all(std::path::Path::new(&path).parent().unwrap()).unwrap();
+            let mut file = File::create(&path).unwrap();
+            file.write_all(&encode_tile(zoom, x, y, features)).unwrap();
+            num_tiles += 1;
+        }
+        timer.stop(format!("zoom {}", zoom));
+    }
+
+    // Map-space has y pointing down, so the corners flip
+    let gps = map.get_gps_bounds();
+    let top_left = Pt2D::new(map.get_bounds().min_x, map.get_bounds().min_y).forcibly_to_gps(gps);
+    let bottom_right =
+        Pt2D::new(map.get_bounds().max_x, map.get_bounds().max_y).forcibly_to_gps(gps);
+    abstutil::write_json(
+        format!("{}/tiles.json", out_dir),
+        &TileJSON {
+            tilejson: "2.2.0".to_string(),
+            name: map.get_name().to_string(),
+            tiles: vec!["{z}/{x}/{y}.pbf".to_string()],
+            minzoom: min_zoom,
+            maxzoom: max_zoom,
+            bounds: [
+                top_left.longitude,
+                bottom_right.latitude,
+                bottom_right.longitude,
+                top_left.latitude,
+            ],
+            edits: map.get_edits().edits_name.clone(),
+        },
+    );
+    let mut file = File::create(format!("{}/index.html", out_dir)).unwrap();
+    file.write_all(VIEWER.as_bytes()).unwrap();
+    timer.done();
+    println!("Wrote {} tiles to {}", num_tiles, out_dir);
+    println!("The viewer loads MapLibre from unpkg.com, so it needs network access");
+}
+
+// The attributes mostly match what the info panel in the game shows.
+fn lanes(map: &Map) -> Vec<Feature> {
+    let edits = map.get_edits();
+    map.all_lanes()
+        .iter()
+        .map(|l| {
+            let r = map.get_r(l.parent);
+            let mut props = vec![
+                ("id", Value::Number(l.id.0 as f64)),
+                ("lane_type", Value::String(format!("{:?}", l.lane_type))),
+                ("road", Value::String(r.get_name())),
+                ("road_id", Value::Number(r.id.0 as f64)),
+                ("length_m", Value::Number(l.length().inner_meters())),
+                (
+                    "edited",
+                    Value::Bool(
+                        edits.original_lts.contains_key(&l.id)
+                            || edits.reversed_lanes.contains(&l.id),
+                    ),
+                ),
+            ];
+            if l.is_parking() {
+                props.push((
+                    "parking_spots",
+                    Value::Number(l.number_parking_spots() as f64),
+                ));
+            } else {
+                props.push((
+                    "speed_limit",
+                    Value::String(r.get_speed_limit().to_string()),
+                ));
+            }
+            Feature::new(
+                l.id.0 as u64,
+                Geometry::Line(to_gps(map, l.lane_center_pts.points())),
+                props,
+            )
+        })
+        .collect()
+}
+
+fn intersections(map: &Map) -> Vec<Feature> {
+    let edits = map.get_edits();
+    map.all_intersections()
+        .iter()
+        .map(|i| {
+            let mut roads: Vec<String> = i.roads.iter().map(|r| map.get_r(*r).get_name()).collect();
+            roads.sort();
+            roads.dedup();
+            Feature::new(
+                i.id.0 as u64,
+                Geometry::Polygon(to_gps(map, i.polygon.points())),
+                vec![
+                    ("id", Value::Number(i.id.0 as f64)),
+                    (
+                        "intersection_type",
+                        Value::String(format!("{:?}", i.intersection_type)),
+                    ),
+                    ("roads", Value::String(roads.join(", "))),
+                    (
+                        "edited",
+                        Value::Bool(edits.changed_intersections.contains(&i.id)),
+                    ),
+                ],
+            )
+        })
+        .collect()
+}
+
+fn buildings(map: &Map) -> Vec<Feature> {
+    map.all_buildings()
+        .iter()
+        .map(|b| {
+            let mut props = vec![
+                ("id", Value::Number(b.id.0 as f64)),
+                ("name", Value::String(b.get_name(map))),
+            ];
+            if let Some(ref p) = b.parking {
+                props.push(("parking_spots", Value::Number(p.num_stalls as f64)));
+                props.push(("parking_name", Value::String(p.name.clone())));
+            }
+            add_osm_tags(&mut props, &b.osm_tags);
+            Feature::new(
+                b.id.0 as u64,
+                Geometry::Polygon(to_gps(map, b.polygon.points())),
+                props,
+            )
+        })
+        .collect()
+}
+
+fn areas(map: &Map) -> Vec<Feature> {
+    map.all_areas()
+        .iter()
+        .map(|a| {
+            let mut props = vec![
+                ("id", Value::Number(a.id.0 as f64)),
+                ("area_type", Value::String(format!("{:?}", a.area_type))),
+            ];
+            add_osm_tags(&mut props, &a.osm_tags);
+            Feature::new(
+                a.id.0 as u64,
+                Geometry::Polygon(to_gps(map, a.polygon.points())),
+                props,
+            )
+        })
+        .collect()
+}
+
+fn bus_stops(map: &Map) -> Vec<Feature> {
+    map.all_bus_stops()
+        .values()
+        .map(|bs| {
+            let routes: Vec<String> = map
+                .get_routes_serving_stop(bs.id)
+                .into_iter()
+                .map(|r| r.name.clone())
+                .collect();
+            // Stable across exports, unlike the position in the list. Both halves fit easily.
+            let id = ((bs.id.sidewalk.0 as u64) << 32) | (bs.id.idx as u64);
+            Feature::new(
+                id,
+                Geometry::Point(
+                    bs.sidewalk_pos
+                        .pt(map)
+                        .forcibly_to_gps(map.get_gps_bounds()),
+                ),
+                vec![
+                    ("id", Value::String(bs.id.to_string())),
+                    ("routes", Value::String(routes.join(", "))),
+                ],
+            )
+        })
+        .collect()
+}
+
+fn add_osm_tags(props: &mut Vec<(&'static str, Value)>, tags: &BTreeMap<String, String>) {
+    // Most viewers only style on a few well-known keys, so don't bother with everything.
+    for key in &[
+        "amenity",
+        "shop",
+        "building",
+        "leisure",
+        "natural",
+        "addr:street",
+    ] {
+        if let Some(value) = tags.get(*key) {
+            props.push((*key, Value::String(value.clone())));
+        }
+    }
+}
+
+fn to_gps(map: &Map, pts: &Vec<Pt2D>) -> Vec<LonLat> {
+    pts.iter()
+        .map(|pt| pt.forcibly_to_gps(map.get_gps_bounds()))
+        .collect()
+}
+
+#[derive(serde_derive::Serialize)]
+struct TileJSON {
+    tilejson: String,
+    name: String,
+    tiles: Vec<String>,
+    minzoom: u32,
+    maxzoom: u32,
+    bounds: [f64; 4],
+    // Not part of TileJSON, just shown by the viewer
+    edits: String,
+}
+
+// Colors match the defaults in the game.
+const VIEWER: &str = r##"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>A/B Street map</title>
+<script src="https://unpkg.com/maplibre-gl@1.13.0/dist/maplibre-gl.js"></script>
+<link href="https://unpkg.com/maplibre-gl@1.13.0/dist/maplibre-gl.css" rel="stylesheet">
+<style>body { margin: 0; } #map { position: absolute; width: 100%; height: 100%; }</style>
+</head>
+<body>
+<div id="map"></div>
+<script>
+if (typeof maplibregl == "undefined") {
+  document.getElementById("map").innerHTML =
+    "<p>Couldn't load MapLibre from unpkg.com. The viewer needs network access.</p>";
+}
+fetch("tiles.json").then((resp) => resp.json()).then((meta) => {
+  const source = {
+    type: "vector",
+    tiles: [new URL("{z}/{x}/{y}.pbf", window.location.href).href.replace(/%7B/g, "{").replace(/%7D/g, "}")],
+    minzoom: meta.minzoom,
+    maxzoom: meta.maxzoom,
+  };
+  const map = new maplibregl.Map({
+    container: "map",
+    bounds: [[meta.bounds[0], meta.bounds[1]], [meta.bounds[2], meta.bounds[3]]],
+    style: {
+      version: 8,
+      sources: { abst: source },
+      layers: [
+        { id: "background", type: "background", paint: { "background-color": "#DEDEDE" } },
+        { id: "areas", type: "fill", source: "abst", "source-layer": "areas", paint: {
+          "fill-color": ["match", ["get", "area_type"], "Park", "#94C84A", "Water", "#A4C8EA", "#DEDEDE"] } },
+        { id: "lanes", type: "line", source: "abst", "source-layer": "lanes", paint: {
+          "line-width": ["interpolate", ["exponential", 2], ["zoom"], 13, 0.5, 20, 25],
+          "line-color": ["match", ["get", "lane_type"],
+            "Driving", "#000000", "Bus", "#BE4A4C", "Parking", "#333333", "Sidewalk", "#CCCCCC",
+            "Biking", "#0F7D4B", "Construction", "#FF6D00", "#000000"] } },
+        { id: "edited lanes", type: "line", source: "abst", "source-layer": "lanes",
+          filter: ["==", ["get", "edited"], true], paint: {
+          "line-width": 2, "line-color": "#FF00FF" } },
+        { id: "intersections", type: "fill", source: "abst", "source-layer": "intersections", paint: {
+          "fill-color": ["match", ["get", "intersection_type"], "Border", "#32CD32", "#333333"] } },
+        { id: "buildings", type: "fill", source: "abst", "source-layer": "buildings", paint: {
+          "fill-color": "#C4C1BC" } },
+        { id: "bus stops", type: "circle", source: "abst", "source-layer": "bus_stops", paint: {
+          "circle-color": "#00FFFF", "circle-radius": 4 } },
+      ],
+    },
+  });
+  map.on("click", (e) => {
+    const features = map.queryRenderedFeatures(e.point);
+    if (features.length == 0) {
+      return;
+    }
+    const props = features[0].properties;
+    const html = Object.keys(props).map((k) => k + ": " + props[k]).join("<br>");
+    new maplibregl.Popup().setLngLat(e.lngLat).setHTML(html).addTo(map);
+  });
+  document.title = meta.name + (meta.edits ? " with " + meta.edits : "");
+});
+</script>
+</body>
+</html>
+"##;
@@ -1,4 +1,4 @@
-use crate::{ScreenDims, ScreenPt, Text};
+use crate::{Catalog, Keybindings, ScreenDims, ScreenPt, Text};
 use glium::Rect;
 use glium_glyph::glyph_brush::rusttype::{Font, Scale};
 use glium_glyph::glyph_brush::{FontId, GlyphCruncher};
@@ -14,10 +14,17 @@ pub struct Assets {
     line_height_per_font_size: RefCell<HashMap<(FontId, usize), f64>>,
     pub default_line_height: f64,
     pub font_size: usize,
+    pub catalog: RefCell<Catalog>,
+    pub keybindings: Keybindings,
 }
 
 impl Assets {
-    pub fn new(display: &glium::Display, font_size: usize) -> Assets {
+    pub fn new(
+        display: &glium::Display,
+        font_size: usize,
+        catalog: Catalog,
+        keybindings: Keybindings,
+    ) -> Assets {
         let dejavu: &[u8] = include_bytes!("assets/DejaVuSans.ttf");
         let roboto: &[u8] = include_bytes!("assets/Roboto-Regular.ttf");
         let roboto_bold: &[u8] = include_bytes!("assets/Roboto-Bold.ttf");
@@ -48,6 +55,8 @@ impl Assets {
             line_height_per_font_size: RefCell::new(HashMap::new()),
             default_line_height: 0.0,
             font_size,
+            catalog: RefCell::new(catalog),
+            keybindings,
         };
         a.default_line_height = a.line_height(FontId(0), a.font_size);
         a
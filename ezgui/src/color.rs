@@ -44,6 +44,12 @@ impl fmt::Display for Color {
     }
 }
 
+impl From<raster::Rgba> for Color {
+    fn from(c: raster::Rgba) -> Color {
+        Color::RGBA(c.r, c.g, c.b, c.a)
+    }
+}
+
 impl Color {
     // TODO Won't this confuse the shader? :P
     pub const INVISIBLE: Color = Color::rgba_f(1.0, 0.0, 0.0, 0.0);
@@ -363,6 +363,18 @@ impl GeomBatch {
         self.list
     }
 
+    // For the raster crate. Only plain colors work; textures and hatching need the shaders, so
+    // they're skipped.
+    pub fn for_export(self) -> Vec<(raster::Rgba, Polygon)> {
+        self.list
+            .into_iter()
+            .filter_map(|(color, poly)| match color {
+                Color::RGBA(r, g, b, a) => Some((raster::Rgba::new(r, g, b, a), poly)),
+                _ => None,
+            })
+            .collect()
+    }
+
     pub fn draw(self, g: &mut GfxCtx) {
         let refs = self.list.iter().map(|(color, p)| (*color, p)).collect();
         let obj = g.prerender.upload_temporary(refs);
@@ -1,7 +1,8 @@
 use crate::ScreenPt;
 use glium::glutin;
+use serde_derive::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum Event {
     // Used to initialize the application and also to recalculate menu state when some other event
     // is used.
@@ -85,7 +86,7 @@ impl Event {
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
 pub enum Key {
     // Case is unspecified.
     // TODO Would be cool to represent A and UpperA, but then release semantics get weird... hold
@@ -372,7 +373,7 @@ impl Key {
 }
 
 // TODO This is not an ideal representation at all.
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
 pub struct MultiKey {
     pub key: Key,
     pub lctrl: bool,
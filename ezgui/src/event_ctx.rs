@@ -1,7 +1,7 @@
 use crate::assets::Assets;
 use crate::{
-    Canvas, Color, Event, GfxCtx, HorizontalAlignment, Line, Prerender, ScreenDims, Text,
-    UserInput, VerticalAlignment,
+    Canvas, Catalog, Color, Event, GfxCtx, HorizontalAlignment, Line, MultiKey, Prerender,
+    ScreenDims, Text, UserInput, VerticalAlignment,
 };
 use abstutil::{elapsed_seconds, Timer, TimerSink};
 use geom::Angle;
@@ -89,6 +89,32 @@ impl<'a> EventCtx<'a> {
         self.canvas.drag_canvas_from.is_some() || self.canvas.drag_just_ended
     }
 
+    // Text is translated as it's drawn, but anything already laid out keeps its old size until it's
+    // rebuilt.
+    pub fn set_catalog(&mut self, catalog: Catalog) {
+        *self.assets.catalog.borrow_mut() = catalog;
+    }
+
+    // For text built from values, which has to be translated before it's laid out. See
+    // Catalog::format.
+    pub fn tr(&self, key: &str, args: &[(&str, String)]) -> String {
+        self.assets.catalog.borrow().format(key, args)
+    }
+
+    // The player's binding for an action, if they changed it
+    pub(crate) fn hotkey_for(&self, action: &str, default: Option<MultiKey>) -> Option<MultiKey> {
+        self.assets.keybindings.lookup(action, default)
+    }
+
+    // For hotkeys without a button or menu entry. The player can rebind these by the action name
+    // too, and the help screen lists them.
+    pub fn action_pressed(&mut self, default: Option<MultiKey>, action: &str) -> bool {
+        match self.hotkey_for(action, default) {
+            Some(key) => self.input.action_pressed(key, action),
+            None => false,
+        }
+    }
+
     pub fn set_textures(&mut self, textures: Vec<(&str, TextureType)>, timer: &mut Timer) {
         self.canvas.texture_arrays.clear();
         self.canvas.texture_lookups.clear();
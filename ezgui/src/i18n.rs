@@ -0,0 +1,69 @@
+use abstutil::Timer;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+// Translations of UI text. Messages are keyed by the English text itself, so every Line, button,
+// menu entry, and wizard prompt is translated when it's drawn, and anything missing falls back to
+// English. Code keeps matching on the English labels. Text built from values won't match anything
+// by the time it's drawn, so it goes through format with a key instead.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Catalog {
+    pub locale: String,
+    // In the language itself, for choosing between catalogs
+    pub name: String,
+    pub metric_units: bool,
+    pub messages: BTreeMap<String, String>,
+}
+
+impl Catalog {
+    pub fn english() -> Catalog {
+        Catalog {
+            locale: "en".to_string(),
+            name: "English".to_string(),
+            metric_units: false,
+            messages: BTreeMap::new(),
+        }
+    }
+
+    // Reads {dir}/{locale}.json. A regional locale like "es_MX" is layered on top of "es", so it
+    // only has to list what differs, and either file alone is enough.
+    pub fn load(dir: &str, locale: &str, timer: &mut Timer) -> Catalog {
+        let mut catalog = Catalog::english();
+        let mut names = Vec::new();
+        if let Some(idx) = locale.find(|c| c == '_' || c == '-') {
+            names.push(&locale[..idx]);
+        }
+        names.push(locale);
+
+        let mut found = false;
+        for name in names {
+            if let Ok(c) =
+                abstutil::maybe_read_json::<Catalog>(format!("{}/{}.json", dir, name), timer)
+            {
+                catalog.locale = c.locale;
+                catalog.name = c.name;
+                catalog.metric_units = c.metric_units;
+                catalog.messages.extend(c.messages);
+                found = true;
+            }
+        }
+        if !found {
+            timer.warn(format!("No translations for {} in {}", locale, dir));
+        }
+        catalog
+    }
+
+    pub fn translate<'a>(&'a self, text: &'a str) -> &'a str {
+        self.messages.get(text).map(|s| s.as_str()).unwrap_or(text)
+    }
+
+    // The key is the English template, with {name} wherever an argument goes, so translations can
+    // reorder them.
+    pub fn format(&self, key: &str, args: &[(&str, String)]) -> String {
+        let mut result = self.translate(key).to_string();
+        for (name, value) in args {
+            result = result.replace(&format!("{{{}}}", name), value);
+        }
+        result
+    }
+}
@@ -9,6 +9,8 @@ pub struct UserInput {
     important_actions: Vec<(Key, String)>,
     // If two different callers both expect the same key, there's likely an unintentional conflict.
     reserved_keys: HashMap<Key, String>,
+    // Every hotkey-bound action checked during this event, for the help screen
+    bindings: Vec<(MultiKey, String)>,
 
     lctrl_held: bool,
 }
@@ -20,6 +22,7 @@ impl UserInput {
             event_consumed: false,
             important_actions: Vec::new(),
             reserved_keys: HashMap::new(),
+            bindings: Vec::new(),
             lctrl_held: canvas.lctrl_held,
         }
     }
@@ -76,6 +79,21 @@ impl UserInput {
         false
     }
 
+    // For anything the player can rebind. The action is remembered even if another widget already
+    // consumed the event, so the help screen sees everything on screen.
+    pub(crate) fn action_pressed(&mut self, multikey: MultiKey, action: &str) -> bool {
+        self.bindings.push((multikey, action.to_string()));
+        self.new_was_pressed(multikey)
+    }
+
+    // Sorted by key, so conflicts wind up next to each other
+    pub fn active_bindings(&self) -> Vec<(MultiKey, String)> {
+        let mut bindings = self.bindings.clone();
+        bindings.sort();
+        bindings.dedup();
+        bindings
+    }
+
     pub fn key_released(&mut self, key: Key) -> bool {
         if self.event_consumed {
             return false;
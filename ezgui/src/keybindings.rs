@@ -0,0 +1,114 @@
+use crate::MultiKey;
+use abstutil::Timer;
+use serde_derive::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+
+// Lets players change hotkeys. Actions are named by the label of their button or menu entry, and
+// the defaults stay where they're declared in code. Only the differences live in the config file.
+// TODO Camera controls and the keys ezgui's own widgets read with new_was_pressed, like arrows in
+// popup menus, can't be rebound yet.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Keybindings {
+    // None unbinds the action.
+    pub overrides: BTreeMap<String, Option<MultiKey>>,
+
+    // The default of every action looked up so far, to notice overrides that take a key some other
+    // action still uses.
+    #[serde(skip)]
+    defaults: RefCell<BTreeMap<String, MultiKey>>,
+    #[serde(skip)]
+    reported: RefCell<BTreeSet<(String, String)>>,
+}
+
+impl Keybindings {
+    pub fn new() -> Keybindings {
+        Keybindings {
+            overrides: BTreeMap::new(),
+            defaults: RefCell::new(BTreeMap::new()),
+            reported: RefCell::new(BTreeSet::new()),
+        }
+    }
+
+    // A missing file just means nothing's been changed.
+    pub fn load(path: String) -> Keybindings {
+        let kb = if std::path::Path::new(&path).exists() {
+            match abstutil::maybe_read_json::<Keybindings>(path.clone(), &mut Timer::throwaway()) {
+                Ok(kb) => kb,
+                Err(err) => {
+                    println!("Ignoring keybindings in {}: {}", path, err);
+                    Keybindings::new()
+                }
+            }
+        } else {
+            Keybindings::new()
+        };
+        for problem in kb.conflicts() {
+            println!("Keybinding conflict in {}: {}", path, problem);
+        }
+        kb
+    }
+
+    pub fn lookup(&self, action: &str, default: Option<MultiKey>) -> Option<MultiKey> {
+        if let Some(key) = default {
+            if self
+                .defaults
+                .borrow_mut()
+                .insert(action.to_string(), key)
+                .is_none()
+            {
+                self.report_collisions();
+            }
+        }
+        self.overrides.get(action).cloned().unwrap_or(default)
+    }
+
+    // Only catches overrides clashing with each other. Clashes with defaults are reported as the
+    // actions show up, and the help screen shows which ones are on screen together.
+    pub fn conflicts(&self) -> Vec<String> {
+        let mut per_key: BTreeMap<MultiKey, Vec<&String>> = BTreeMap::new();
+        for (action, key) in &self.overrides {
+            if let Some(key) = key {
+                per_key.entry(*key).or_insert_with(Vec::new).push(action);
+            }
+        }
+        per_key
+            .into_iter()
+            .filter(|(_, actions)| actions.len() > 1)
+            .map(|(key, actions)| {
+                let names: Vec<&str> = actions.into_iter().map(|a| a.as_str()).collect();
+                format!("{} is bound to {}", key.describe(), names.join(", "))
+            })
+            .collect()
+    }
+
+    // Defaults are declared wherever each widget is made, so a collision between an override and a
+    // default can't be known when the file is loaded. Instead, print it the first time both
+    // actions have been seen. Usually that's while the first screens are set up.
+    fn report_collisions(&self) {
+        let defaults = self.defaults.borrow();
+        for (action, key) in &self.overrides {
+            let key = match key {
+                Some(key) => key,
+                None => continue,
+            };
+            for (other, default) in defaults.iter() {
+                if other != action
+                    && default == key
+                    && !self.overrides.contains_key(other)
+                    && self
+                        .reported
+                        .borrow_mut()
+                        .insert((action.clone(), other.clone()))
+                {
+                    println!(
+                        "Keybinding for {} ({}) collides with the default for {}",
+                        action,
+                        key.describe(),
+                        other
+                    );
+                }
+            }
+        }
+    }
+}
@@ -4,9 +4,12 @@ mod color;
 mod drawing;
 mod event;
 mod event_ctx;
+mod i18n;
 mod input;
+mod keybindings;
 pub mod layout;
 mod managed;
+mod recorder;
 mod runner;
 mod screen_geom;
 mod svg;
@@ -18,14 +21,18 @@ pub use crate::color::Color;
 pub use crate::drawing::{DrawBoth, Drawable, GeomBatch, GfxCtx, Prerender, RewriteColor};
 pub use crate::event::{hotkey, lctrl, Event, Key, MultiKey};
 pub use crate::event_ctx::{EventCtx, TextureType};
+pub use crate::i18n::Catalog;
 pub use crate::input::UserInput;
+pub use crate::keybindings::Keybindings;
 pub use crate::managed::{Composite, ManagedWidget, Outcome};
+pub use crate::recorder::Recording;
 pub use crate::runner::{run, EventLoopMode, Settings, GUI};
 pub use crate::screen_geom::{ScreenDims, ScreenPt, ScreenRectangle};
 pub use crate::text::{Line, Text, TextSpan, HOTKEY_COLOR};
 pub use crate::widgets::{
-    Autocomplete, Button, Choice, Filler, Histogram, ItemSlider, JustDraw, ModalMenu, Plot, Series,
-    Slider, SliderWithTextBox, Warper, WarpingItemSlider, Wizard, WrappedWizard,
+    closest_in_time, csv_contents, plot_csv_rows, Autocomplete, Button, Choice, Filler, Histogram,
+    ItemSlider, JustDraw, ModalMenu, Plot, PlotView, Series, Slider, SliderWithTextBox, Warper,
+    WarpingItemSlider, Wizard, WrappedWizard, PLOT_CSV_HEADER,
 };
 
 pub enum InputResult<T: Clone> {
@@ -2,12 +2,12 @@ use crate::layout::Widget;
 use crate::widgets::PopupMenu;
 use crate::{
     Button, Color, DrawBoth, EventCtx, Filler, GeomBatch, GfxCtx, Histogram, HorizontalAlignment,
-    JustDraw, Plot, RewriteColor, ScreenDims, ScreenPt, ScreenRectangle, Slider, Text,
+    JustDraw, Plot, PlotView, RewriteColor, ScreenDims, ScreenPt, ScreenRectangle, Slider, Text,
     VerticalAlignment,
 };
 use abstutil::Cloneable;
 use geom::{Distance, Duration, Polygon};
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 use stretch::geometry::{Rect, Size};
 use stretch::node::{Node, Stretch};
 use stretch::number::Number;
@@ -279,10 +279,10 @@ impl ManagedWidget {
             WidgetType::Menu(ref name) => {
                 menus.get_mut(name).unwrap().event(ctx);
             }
-            WidgetType::Filler(_)
-            | WidgetType::DurationPlot(_)
-            | WidgetType::UsizePlot(_)
-            | WidgetType::Histogram(_) => {}
+            WidgetType::Filler(_) => {}
+            WidgetType::DurationPlot(ref mut plot) => plot.event(ctx),
+            WidgetType::UsizePlot(ref mut plot) => plot.event(ctx),
+            WidgetType::Histogram(ref mut hgram) => hgram.event(ctx),
             WidgetType::Row(ref mut widgets) | WidgetType::Column(ref mut widgets) => {
                 for w in widgets {
                     if let Some(o) = w.event(ctx, sliders, menus) {
@@ -519,6 +519,40 @@ impl ManagedWidget {
         }
     }
 
+    fn get_plot_views(&self, views: &mut Vec<PlotView>) {
+        match self.widget {
+            WidgetType::DurationPlot(ref plot) => views.push(plot.view()),
+            WidgetType::UsizePlot(ref plot) => views.push(plot.view()),
+            WidgetType::Row(ref widgets) | WidgetType::Column(ref widgets) => {
+                for w in widgets {
+                    w.get_plot_views(views);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn restore_plot_views(&mut self, ctx: &EventCtx, views: &mut VecDeque<PlotView>) {
+        match self.widget {
+            WidgetType::DurationPlot(ref mut plot) => {
+                if let Some(view) = views.pop_front() {
+                    plot.restore_view(ctx, view);
+                }
+            }
+            WidgetType::UsizePlot(ref mut plot) => {
+                if let Some(view) = views.pop_front() {
+                    plot.restore_view(ctx, view);
+                }
+            }
+            WidgetType::Row(ref mut widgets) | WidgetType::Column(ref mut widgets) => {
+                for w in widgets {
+                    w.restore_plot_views(ctx, views);
+                }
+            }
+            _ => {}
+        }
+    }
+
     fn center_of(&self, name: &str) -> Option<ScreenPt> {
         let found = match self.widget {
             WidgetType::Draw(_) => false,
@@ -772,6 +806,18 @@ impl Composite {
         self.set_scroll_offset(ctx, offset);
     }
 
+    // Zooming and hidden series on plots, in order
+    pub fn preserve_plots(&self) -> Vec<PlotView> {
+        let mut views = Vec::new();
+        self.top_level.get_plot_views(&mut views);
+        views
+    }
+
+    pub fn restore_plots(&mut self, ctx: &EventCtx, views: Vec<PlotView>) {
+        self.top_level
+            .restore_plot_views(ctx, &mut views.into_iter().collect());
+    }
+
     pub fn slider(&self, name: &str) -> &Slider {
         &self.sliders[name]
     }
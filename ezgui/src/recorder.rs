@@ -0,0 +1,107 @@
+use crate::Event;
+use abstutil::Timer;
+use serde_derive::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+// Everything the GUI saw, grouped by the frame that delivered it. Replaying feeds the same events in
+// the same frames, so a GUI that only depends on its input behaves the same way.
+// TODO Some things still read the clock (warping the camera, time-limited sim steps at high
+// speeds), so those can diverge if the replaying machine is much slower or faster.
+#[derive(Serialize, Deserialize)]
+pub struct Recording {
+    pub window_width: f64,
+    pub window_height: f64,
+    pub hidpi_factor: f64,
+    // When each frame started, relative to the first one
+    pub frames: Vec<(Duration, Vec<Event>)>,
+}
+
+impl Recording {
+    pub fn load(path: &str) -> Recording {
+        abstutil::read_json(path.to_string(), &mut Timer::throwaway())
+    }
+}
+
+pub(crate) struct Recorder {
+    path: String,
+    // Set on the first frame, so loading the GUI doesn't count
+    started: Option<Instant>,
+    recording: Recording,
+}
+
+impl Recorder {
+    pub(crate) fn new(
+        path: String,
+        window_width: f64,
+        window_height: f64,
+        hidpi_factor: f64,
+    ) -> Recorder {
+        Recorder {
+            path,
+            started: None,
+            recording: Recording {
+                window_width,
+                window_height,
+                hidpi_factor,
+                frames: Vec::new(),
+            },
+        }
+    }
+
+    pub(crate) fn record_frame(&mut self, events: &Vec<Event>) {
+        let started = *self.started.get_or_insert_with(Instant::now);
+        self.recording
+            .frames
+            .push((Instant::now().duration_since(started), events.clone()));
+    }
+}
+
+// The only place the recording is saved, so it also happens when the GUI panics, since that's
+// usually the session worth replaying.
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        abstutil::write_json(self.path.clone(), &self.recording);
+        println!(
+            "Recorded {} frames of input to {}",
+            self.recording.frames.len(),
+            self.path
+        );
+    }
+}
+
+pub(crate) struct Replayer {
+    started: Option<Instant>,
+    recording: Recording,
+    next_frame: usize,
+    // Ignore the recorded timing and deliver one frame after another
+    pub(crate) as_fast_as_possible: bool,
+}
+
+impl Replayer {
+    pub(crate) fn new(recording: Recording, as_fast_as_possible: bool) -> Replayer {
+        Replayer {
+            started: None,
+            recording,
+            next_frame: 0,
+            as_fast_as_possible,
+        }
+    }
+
+    // None means the recording is over.
+    pub(crate) fn next_frame(&mut self) -> Option<Vec<Event>> {
+        let (at, events) = self.recording.frames.get(self.next_frame)?.clone();
+        self.next_frame += 1;
+        let started = *self.started.get_or_insert_with(Instant::now);
+        if !self.as_fast_as_possible {
+            let elapsed = Instant::now().duration_since(started);
+            if at > elapsed {
+                std::thread::sleep(at - elapsed);
+            }
+        }
+        Some(events)
+    }
+
+    pub(crate) fn num_frames(&self) -> usize {
+        self.recording.frames.len()
+    }
+}
@@ -1,5 +1,8 @@
 use crate::assets::Assets;
-use crate::{text, widgets, Canvas, Event, EventCtx, GfxCtx, Key, Prerender, UserInput};
+use crate::recorder::{Recorder, Recording, Replayer};
+use crate::{
+    text, widgets, Canvas, Catalog, Event, EventCtx, GfxCtx, Key, Keybindings, Prerender, UserInput,
+};
 use glium::glutin;
 use std::cell::Cell;
 use std::time::{Duration, Instant};
@@ -195,8 +198,13 @@ pub struct Settings {
     window_title: String,
     profiling_enabled: bool,
     default_font_size: usize,
+    catalog: Catalog,
+    keybindings: Keybindings,
     override_hidpi_factor: Option<f64>,
     dump_raw_events: bool,
+    record_events: Option<String>,
+    // The bool means to ignore the recorded timing
+    replay_events: Option<(String, bool)>,
 }
 
 impl Settings {
@@ -205,8 +213,12 @@ impl Settings {
             window_title: window_title.to_string(),
             profiling_enabled: false,
             default_font_size: 30,
+            catalog: Catalog::english(),
+            keybindings: Keybindings::new(),
             override_hidpi_factor: None,
             dump_raw_events: false,
+            record_events: None,
+            replay_events: None,
         }
     }
 
@@ -220,10 +232,30 @@ impl Settings {
         self.dump_raw_events = true;
     }
 
+    // Saves every event to a file, so the session can be replayed later.
+    pub fn record_events(&mut self, path: String) {
+        assert!(self.replay_events.is_none());
+        self.record_events = Some(path);
+    }
+
+    // Ignores real input and instead feeds in a recording, then quits.
+    pub fn replay_events(&mut self, path: String, as_fast_as_possible: bool) {
+        assert!(self.record_events.is_none());
+        self.replay_events = Some((path, as_fast_as_possible));
+    }
+
     pub fn default_font_size(&mut self, size: usize) {
         self.default_font_size = size;
     }
 
+    pub fn catalog(&mut self, catalog: Catalog) {
+        self.catalog = catalog;
+    }
+
+    pub fn keybindings(&mut self, keybindings: Keybindings) {
+        self.keybindings = keybindings;
+    }
+
     pub fn override_hidpi_factor(&mut self, override_hidpi_factor: f64) {
         self.override_hidpi_factor = Some(override_hidpi_factor);
     }
@@ -302,7 +334,35 @@ pub fn run<G: GUI, F: FnOnce(&mut EventCtx) -> G>(settings: Settings, make_gui:
     }
     let window_size = events_loop.get_primary_monitor().get_dimensions();
     let mut canvas = Canvas::new(window_size.width, window_size.height, hidpi_factor);
-    let assets = Assets::new(&display, settings.default_font_size);
+    // Start from the same window as the recording, or else clicks could land somewhere else.
+    let replayer = settings.replay_events.map(|(path, as_fast_as_possible)| {
+        let recording = Recording::load(&path);
+        canvas = Canvas::new(
+            recording.window_width,
+            recording.window_height,
+            recording.hidpi_factor,
+        );
+        println!(
+            "Replaying {} frames of input from {}",
+            recording.frames.len(),
+            path
+        );
+        Replayer::new(recording, as_fast_as_possible)
+    });
+    let recorder = settings.record_events.map(|path| {
+        Recorder::new(
+            path,
+            canvas.window_width,
+            canvas.window_height,
+            canvas.hidpi_factor,
+        )
+    });
+    let assets = Assets::new(
+        &display,
+        settings.default_font_size,
+        settings.catalog,
+        settings.keybindings,
+    );
     let prerender = Prerender {
         display: &display,
         num_uploads: Cell::new(0),
@@ -331,6 +391,8 @@ pub fn run<G: GUI, F: FnOnce(&mut EventCtx) -> G>(settings: Settings, make_gui:
         prerender,
         settings.profiling_enabled,
         settings.dump_raw_events,
+        recorder,
+        replayer,
     );
 }
 
@@ -341,6 +403,8 @@ fn loop_forever<G: GUI>(
     prerender: Prerender,
     profiling_enabled: bool,
     dump_raw_events: bool,
+    mut recorder: Option<Recorder>,
+    mut replayer: Option<Replayer>,
 ) {
     if profiling_enabled {
         #[cfg(feature = "profiler")]
@@ -369,8 +433,14 @@ fn loop_forever<G: GUI>(
                         }
                     }
                     state.gui.before_quit(&state.canvas);
+                    // Exiting skips destructors, so drop the recorder to save it first.
+                    drop(recorder.take());
                     process::exit(0);
                 }
+                // While replaying, only the recording matters.
+                if replayer.is_some() {
+                    return;
+                }
                 if let glutin::WindowEvent::HiDpiFactorChanged(hidpi_factor) = event {
                     println!(
                         "HiDPI factor changed from {} to {}",
@@ -389,6 +459,21 @@ fn loop_forever<G: GUI>(
         if !wait_for_events {
             new_events.push(Event::Update);
         }
+        if let Some(ref mut r) = replayer {
+            new_events = match r.next_frame() {
+                Some(events) => events,
+                None => {
+                    println!("Replayed all {} frames", r.num_frames());
+                    process::exit(0);
+                }
+            };
+        }
+        // Waiting for input produces lots of empty frames, which don't matter.
+        if let Some(ref mut r) = recorder {
+            if !new_events.is_empty() {
+                r.record_frame(&new_events);
+            }
+        }
 
         let mut any_input_used = false;
 
@@ -439,7 +524,11 @@ fn loop_forever<G: GUI>(
         // Primitive event loop.
         // TODO Read http://gameprogrammingpatterns.com/game-loop.html carefully.
         let this_frame = Instant::now().duration_since(start_frame);
-        if SLEEP_BETWEEN_FRAMES > this_frame {
+        let rushing = replayer
+            .as_ref()
+            .map(|r| r.as_fast_as_possible)
+            .unwrap_or(false);
+        if SLEEP_BETWEEN_FRAMES > this_frame && !rushing {
             thread::sleep(SLEEP_BETWEEN_FRAMES - this_frame);
         }
     }
@@ -188,12 +188,13 @@ impl Text {
     pub(crate) fn dims(&self, assets: &Assets) -> ScreenDims {
         let mut max_width = 0;
         let mut height = 0.0;
+        let catalog = assets.catalog.borrow();
 
         for (_, line) in &self.lines {
             let mut full_line = String::new();
             let mut max_size = 0;
             for span in line {
-                full_line.push_str(&span.text);
+                full_line.push_str(catalog.translate(&span.text));
                 max_size = max_size.max(span.size.unwrap_or(assets.font_size));
             }
             // Empty lines or whitespace-only lines effectively have 0 width.
@@ -243,6 +244,8 @@ pub fn draw_text_bubble(
 
     // TODO Is it expensive to constantly change uniforms and the shader program?
     g.fork_screenspace();
+    let assets = g.assets;
+    let catalog = assets.catalog.borrow();
 
     if let Some(c) = txt.bg_color {
         g.draw_polygon(
@@ -263,7 +266,7 @@ pub fn draw_text_bubble(
                 .map(|span| {
                     max_size = max_size.max(span.size.unwrap_or(g.assets.font_size));
                     SectionText {
-                        text: &span.text,
+                        text: catalog.translate(&span.text),
                         color: match span.fg_color {
                             Color::RGBA(r, g, b, a) => [r, g, b, a],
                             _ => unreachable!(),
@@ -312,6 +315,8 @@ pub fn draw_text_bubble_mapspace(
         );
     }
 
+    let assets = g.assets;
+    let catalog = assets.catalog.borrow();
     let mut y = top_left.y();
     for (line_color, line) in &txt.lines {
         let mut max_size = 0;
@@ -325,7 +330,7 @@ pub fn draw_text_bubble_mapspace(
                 .map(|span| {
                     max_size = max_size.max(span.size.unwrap_or(g.assets.font_size));
                     SectionText {
-                        text: &span.text,
+                        text: catalog.translate(&span.text),
                         color: match span.fg_color {
                             Color::RGBA(r, g, b, a) => [r, g, b, a],
                             _ => unreachable!(),
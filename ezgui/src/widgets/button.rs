@@ -33,7 +33,10 @@ impl Button {
         hotkey: Option<MultiKey>,
         tooltip: &str,
         hitbox: Polygon,
+        ctx: &EventCtx,
     ) -> Button {
+        // The player may have rebound this
+        let hotkey = ctx.hotkey_for(tooltip, hotkey);
         // dims are based on the hitbox, not the two drawables!
         let bounds = hitbox.get_bounds();
         let dims = ScreenDims::new(bounds.width(), bounds.height());
@@ -82,7 +85,7 @@ impl Button {
         }
 
         if let Some(hotkey) = self.hotkey {
-            if ctx.input.new_was_pressed(hotkey) {
+            if ctx.input.action_pressed(hotkey, &self.action) {
                 self.clicked = true;
             }
         }
@@ -160,7 +163,7 @@ impl Button {
             ]),
             vec![],
         );
-        Button::new(normal, hovered, key, label, bg)
+        Button::new(normal, hovered, key, label, bg, ctx)
     }
 
     pub fn rectangle_svg(
@@ -182,6 +185,7 @@ impl Button {
             key,
             tooltip,
             bounds.get_rectangle(),
+            ctx,
         )
     }
 
@@ -206,6 +210,7 @@ impl Button {
             key,
             tooltip,
             bounds.get_rectangle(),
+            ctx,
         )
     }
 
@@ -234,6 +239,7 @@ impl Button {
             key,
             tooltip,
             bounds.get_rectangle(),
+            ctx,
         )
     }
 
@@ -267,7 +273,7 @@ impl Button {
             draw_text,
         );
 
-        Button::new(normal, hovered, hotkey, tooltip, geom)
+        Button::new(normal, hovered, hotkey, tooltip, geom, ctx)
     }
 
     pub fn text_no_bg(
@@ -301,6 +307,6 @@ impl Button {
             vec![(selected_text, ScreenPt::new(horiz_padding, vert_padding))],
         );
 
-        Button::new(normal, hovered, hotkey, tooltip, geom)
+        Button::new(normal, hovered, hotkey, tooltip, geom, ctx)
     }
 }
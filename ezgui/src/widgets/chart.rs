@@ -0,0 +1,120 @@
+use crate::{Color, EventCtx, GfxCtx, Line, ScreenPt, ScreenRectangle, Text};
+use geom::{Polygon, Pt2D};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Shared by Plot and Histogram: drag across the chart to pick a range of the X axis.
+pub(crate) struct RangeSelection {
+    // Screen-space X where the drag started
+    from: Option<f64>,
+}
+
+pub(crate) enum Selected {
+    // Percentages of the chart's width, in order
+    Range(f64, f64),
+    // Let go without really dragging
+    Click,
+}
+
+impl RangeSelection {
+    pub(crate) fn new() -> RangeSelection {
+        RangeSelection { from: None }
+    }
+
+    // The rectangle is in screen-space.
+    pub(crate) fn event(&mut self, ctx: &mut EventCtx, rect: &ScreenRectangle) -> Option<Selected> {
+        let cursor = ctx.canvas.get_cursor_in_screen_space()?;
+        if self.from.is_none() {
+            if rect.contains(cursor) && ctx.input.left_mouse_button_pressed() {
+                self.from = Some(cursor.x);
+            }
+            return None;
+        }
+        if !ctx.normal_left_click() {
+            return None;
+        }
+
+        let from = self.from.take().unwrap();
+        let to = abstutil::clamp(cursor.x, rect.x1, rect.x2);
+        if (to - from).abs() < 5.0 {
+            return Some(Selected::Click);
+        }
+        let pct1 = (from.min(to) - rect.x1) / rect.width();
+        let pct2 = (from.max(to) - rect.x1) / rect.width();
+        Some(Selected::Range(pct1, pct2))
+    }
+
+    pub(crate) fn is_active(&self) -> bool {
+        self.from.is_some()
+    }
+
+    pub(crate) fn draw(&self, g: &mut GfxCtx, rect: &ScreenRectangle) {
+        if let (Some(from), Some(cursor)) = (self.from, g.canvas.get_cursor_in_screen_space()) {
+            let to = abstutil::clamp(cursor.x, rect.x1, rect.x2);
+            if let Some(poly) = Polygon::rectangle_two_corners(
+                Pt2D::new(from.min(to), rect.y1),
+                Pt2D::new(from.max(to), rect.y2),
+            ) {
+                g.fork_screenspace();
+                g.draw_polygon(Color::YELLOW.alpha(0.3), &poly);
+                g.unfork();
+            }
+        }
+    }
+}
+
+// The "export to CSV" link and a usage hint, or the result of the last export. Returns where the
+// link is.
+pub(crate) fn footer(
+    ctx: &EventCtx,
+    top_left: ScreenPt,
+    status: &Option<String>,
+    zoomed: bool,
+    txt: &mut Vec<(Text, ScreenPt)>,
+) -> ScreenRectangle {
+    let export = Text::from(Line("export to CSV").fg(Color::CYAN));
+    let export_dims = ctx.text_dims(&export);
+    txt.push((export, top_left));
+
+    let hint = if let Some(ref status) = status {
+        status.clone()
+    } else if zoomed {
+        "click to zoom out".to_string()
+    } else {
+        "drag across to zoom in".to_string()
+    };
+    txt.push((
+        Text::from(Line(hint).fg(Color::grey(0.7))),
+        ScreenPt::new(top_left.x + export_dims.width + 10.0, top_left.y),
+    ));
+
+    ScreenRectangle::top_left(top_left, export_dims)
+}
+
+// Returns a message for the player, whether or not it worked.
+pub(crate) fn export_csv(kind: &str, header: &str, rows: Vec<String>) -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let path = abstutil::path_chart_export(&format!("{}_{}", kind, secs));
+
+    let result = std::fs::create_dir_all(std::path::Path::new(&path).parent().unwrap())
+        .and_then(|_| std::fs::write(&path, csv_contents(header, rows)));
+    match result {
+        Ok(()) => {
+            println!("Wrote {}", path);
+            format!("Exported to {}", path)
+        }
+        Err(err) => format!("Couldn't export to {}: {}", path, err),
+    }
+}
+
+// Every line, including the last, ends with a newline.
+pub fn csv_contents(header: &str, rows: Vec<String>) -> String {
+    let mut contents = format!("{}\n", header);
+    for row in rows {
+        contents.push_str(&row);
+        contents.push('\n');
+    }
+    contents
+}
@@ -1,16 +1,34 @@
 use crate::layout::Widget;
+use crate::widgets::chart::{self, RangeSelection, Selected};
 use crate::{
-    Color, DrawBoth, EventCtx, GeomBatch, GfxCtx, Line, ManagedWidget, ScreenDims, ScreenPt, Text,
+    Color, DrawBoth, EventCtx, GeomBatch, GfxCtx, Line, ManagedWidget, ScreenDims, ScreenPt,
+    ScreenRectangle, Text,
 };
 use abstutil::prettyprint_usize;
 use geom::{Distance, Duration, Polygon, Pt2D};
 
-// The X axis is Durations, with positive meaning "faster" (considered good) and negative "slower"
+// The X axis is Durations, with positive meaning "faster" (considered good) and negative "slower".
+// Dragging across the bars zooms into that range (click to zoom back out), and the values in view
+// can be exported to CSV.
 pub struct Histogram {
-    draw: DrawBoth,
+    dts: Vec<Duration>,
+    // None means everything
+    zoom: Option<(Duration, Duration)>,
+    selection: RangeSelection,
+    // Replaces the usage hint after exporting
+    status: Option<String>,
 
+    draw: DrawBoth,
     // TODO Bit sad to pretty much duplicate the geometry from DrawBoth...
+    // Relative to top_left, like everything else render() recalculates
     rect_labels: Vec<(Polygon, Text)>,
+    export_btn: ScreenRectangle,
+    min_x: Duration,
+    max_x: Duration,
+
+    // Fixed once the histogram is created, so the layout never changes.
+    bars_rect: ScreenRectangle,
+    y_label_width: f64,
 
     top_left: ScreenPt,
     dims: ScreenDims,
@@ -18,15 +36,67 @@ pub struct Histogram {
 
 impl Histogram {
     pub fn new(unsorted_dts: Vec<Duration>, ctx: &EventCtx) -> ManagedWidget {
-        let mut batch = GeomBatch::new();
-        let mut rect_labels = Vec::new();
-
+        let line_height = ctx.default_line_height();
         let width = 0.25 * ctx.canvas.window_width;
         let height = 0.3 * ctx.canvas.window_height;
 
-        // TODO Generic "bucket into 10 groups, give (min, max, count)"
-        let min_x = unsorted_dts.iter().min().cloned().unwrap_or(Duration::ZERO);
-        let max_x = unsorted_dts.iter().max().cloned().unwrap_or(Duration::ZERO);
+        let mut histogram = Histogram {
+            dts: unsorted_dts,
+            zoom: None,
+            selection: RangeSelection::new(),
+            status: None,
+
+            draw: DrawBoth::new(ctx, GeomBatch::new(), Vec::new()),
+            rect_labels: Vec::new(),
+            export_btn: ScreenRectangle::placeholder(),
+            min_x: Duration::ZERO,
+            max_x: Duration::ZERO,
+
+            bars_rect: ScreenRectangle::placeholder(),
+            y_label_width: 0.0,
+
+            top_left: ScreenPt::new(0.0, 0.0),
+            dims: ScreenDims::new(0.0, 0.0),
+        };
+
+        // Zooming in only shrinks the counts, so size the Y labels for everything.
+        let max_y = histogram
+            .buckets()
+            .into_iter()
+            .map(|(_, _, cnt)| cnt)
+            .max()
+            .unwrap();
+        histogram.y_label_width = y_labels(max_y)
+            .into_iter()
+            .map(|(txt, _)| ctx.text_dims(&txt).width)
+            .fold(0.0, f64::max)
+            + 10.0;
+        histogram.bars_rect = ScreenRectangle::top_left(
+            ScreenPt::new(histogram.y_label_width, line_height / 2.0),
+            ScreenDims::new(width, height),
+        );
+        // Room for the X axis labels and the export link
+        histogram.dims = ScreenDims::new(
+            histogram.bars_rect.x2,
+            histogram.bars_rect.y2 + 5.0 + 2.0 * line_height,
+        );
+        histogram.render(ctx);
+
+        ManagedWidget::histogram(histogram)
+    }
+
+    // TODO Generic "bucket into 10 groups, give (min, max, count)"
+    // Also sets min_x and max_x.
+    fn buckets(&mut self) -> Vec<(Duration, Duration, usize)> {
+        let (min_x, max_x) = match self.zoom {
+            Some(range) => range,
+            None => (
+                self.dts.iter().min().cloned().unwrap_or(Duration::ZERO),
+                self.dts.iter().max().cloned().unwrap_or(Duration::ZERO),
+            ),
+        };
+        self.min_x = min_x;
+        self.max_x = max_x;
 
         let num_buckets = 10;
         let bucket_size = (max_x - min_x) / (num_buckets as f64);
@@ -37,18 +107,32 @@ impl Histogram {
                 (min_x + bucket_size * i, min_x + bucket_size * (i + 1.0), 0)
             })
             .collect();
-        for dt in unsorted_dts {
+        for dt in &self.dts {
+            let dt = *dt;
+            if dt < min_x || dt > max_x {
+                continue;
+            }
             // TODO Could sort them and do this more efficiently.
-            if dt == max_x {
+            if dt == max_x || bucket_size == Duration::ZERO {
                 // Most bars represent [low, high) except the last
                 bars[num_buckets - 1].2 += 1;
             } else {
                 let bin = ((dt - min_x) / bucket_size).floor() as usize;
-                bars[bin].2 += 1;
+                bars[bin.min(num_buckets - 1)].2 += 1;
             }
         }
+        bars
+    }
 
-        let min_y = 0;
+    fn render(&mut self, ctx: &EventCtx) {
+        let bars = self.buckets();
+        let num_buckets = bars.len();
+        let line_height = ctx.default_line_height();
+        let rect = self.bars_rect.clone();
+        let mut batch = GeomBatch::new();
+        let mut txt: Vec<(Text, ScreenPt)> = Vec::new();
+
+        self.rect_labels.clear();
         let max_y = bars.iter().map(|(_, _, cnt)| *cnt).max().unwrap();
         for (idx, (min, max, cnt)) in bars.into_iter().enumerate() {
             let color = if max < Duration::ZERO {
@@ -60,24 +144,27 @@ impl Histogram {
             };
             let percent_x_left = (idx as f64) / (num_buckets as f64);
             let percent_x_right = ((idx + 1) as f64) / (num_buckets as f64);
-            let percent_y_top = if max_y == min_y {
+            let percent_y_top = if max_y == 0 {
                 0.0
             } else {
-                (cnt as f64) / ((max_y - min_y) as f64)
+                (cnt as f64) / (max_y as f64)
             };
-            if let Some(rect) = Polygon::rectangle_two_corners(
+            if let Some(bar) = Polygon::rectangle_two_corners(
                 // Top-left
-                Pt2D::new(width * percent_x_left, height * (1.0 - percent_y_top)),
+                Pt2D::new(
+                    rect.x1 + rect.width() * percent_x_left,
+                    rect.y1 + rect.height() * (1.0 - percent_y_top),
+                ),
                 // Bottom-right
-                Pt2D::new(width * percent_x_right, height),
+                Pt2D::new(rect.x1 + rect.width() * percent_x_right, rect.y2),
             ) {
-                batch.push(color, rect.clone());
+                batch.push(color, bar.clone());
                 batch.push(
                     Color::BLACK.alpha(0.5),
-                    rect.to_outline(Distance::meters(0.5)),
+                    bar.to_outline(Distance::meters(0.5)),
                 );
-                rect_labels.push((
-                    rect,
+                self.rect_labels.push((
+                    bar,
                     Text::from(Line(format!(
                         "[{}, {}) has {} trips",
                         min,
@@ -89,53 +176,109 @@ impl Histogram {
             }
         }
 
-        let histogram = Histogram {
-            draw: DrawBoth::new(ctx, batch, Vec::new()),
-            rect_labels,
-
-            top_left: ScreenPt::new(0.0, 0.0),
-            dims: ScreenDims::new(width, height),
-        };
-
         // TODO These can still get really squished. Draw rotated?
         let num_x_labels = 3;
-        let mut row = Vec::new();
         for i in 0..num_x_labels {
             let percent_x = (i as f64) / ((num_x_labels - 1) as f64);
-            let dt = min_x + (max_x - min_x) * percent_x;
-            row.push(ManagedWidget::draw_text(
-                ctx,
-                Text::from(Line(dt.to_string())),
+            let dt = self.min_x + (self.max_x - self.min_x) * percent_x;
+            let label = Text::from(Line(dt.to_string()));
+            let dims = ctx.text_dims(&label);
+            // Keep the first and last labels inside the histogram
+            txt.push((
+                label,
+                ScreenPt::new(
+                    rect.x1 + percent_x * (rect.width() - dims.width),
+                    rect.y2 + 5.0,
+                ),
             ));
         }
-        let x_axis = ManagedWidget::row(row);
 
-        let num_y_labels = 5;
-        let mut col = Vec::new();
-        for i in 0..num_y_labels {
-            let percent_y = (i as f64) / ((num_y_labels - 1) as f64);
-            col.push(ManagedWidget::draw_text(
-                ctx,
-                Text::from(Line(prettyprint_usize(
-                    ((max_y as f64) * percent_y) as usize,
-                ))),
+        for (label, percent_y) in y_labels(max_y) {
+            let dims = ctx.text_dims(&label);
+            txt.push((
+                label,
+                ScreenPt::new(
+                    (self.y_label_width - 5.0 - dims.width).max(0.0),
+                    rect.y1 + (1.0 - percent_y) * rect.height() - dims.height / 2.0,
+                ),
             ));
         }
-        col.reverse();
-        let y_axis = ManagedWidget::col(col);
-
-        ManagedWidget::col(vec![
-            ManagedWidget::row(vec![
-                y_axis.evenly_spaced(),
-                ManagedWidget::histogram(histogram),
-            ]),
-            x_axis.evenly_spaced(),
-        ])
+
+        self.export_btn = chart::footer(
+            ctx,
+            ScreenPt::new(rect.x1, rect.y2 + 5.0 + line_height),
+            &self.status,
+            self.zoom.is_some(),
+            &mut txt,
+        );
+
+        self.draw = DrawBoth::new(ctx, batch, txt);
+    }
+
+    fn bars_on_screen(&self) -> ScreenRectangle {
+        ScreenRectangle {
+            x1: self.top_left.x + self.bars_rect.x1,
+            y1: self.top_left.y + self.bars_rect.y1,
+            x2: self.top_left.x + self.bars_rect.x2,
+            y2: self.top_left.y + self.bars_rect.y2,
+        }
+    }
+
+    pub(crate) fn event(&mut self, ctx: &mut EventCtx) {
+        let rect = self.bars_on_screen();
+        match self.selection.event(ctx, &rect) {
+            Some(Selected::Range(pct1, pct2)) => {
+                let range = self.max_x - self.min_x;
+                self.zoom = Some((self.min_x + range * pct1, self.min_x + range * pct2));
+                self.status = None;
+                self.render(ctx);
+                return;
+            }
+            Some(Selected::Click) => {
+                if self.zoom.is_some() {
+                    self.zoom = None;
+                    self.status = None;
+                    self.render(ctx);
+                }
+                return;
+            }
+            None => {}
+        }
+        if self.selection.is_active() {
+            return;
+        }
+
+        if let Some(pt) = ctx.canvas.get_cursor_in_screen_space() {
+            let pt = ScreenPt::new(pt.x - self.top_left.x, pt.y - self.top_left.y);
+            if self.export_btn.contains(pt) && ctx.normal_left_click() {
+                let mut values: Vec<Duration> = self
+                    .dts
+                    .iter()
+                    .filter(|dt| **dt >= self.min_x && **dt <= self.max_x)
+                    .cloned()
+                    .collect();
+                values.sort();
+                self.status = Some(chart::export_csv(
+                    "histogram",
+                    "duration_seconds",
+                    values
+                        .into_iter()
+                        .map(|dt| dt.inner_seconds().to_string())
+                        .collect(),
+                ));
+                self.render(ctx);
+            }
+        }
     }
 
     pub(crate) fn draw(&self, g: &mut GfxCtx) {
         self.draw.redraw(self.top_left, g);
 
+        if self.selection.is_active() {
+            self.selection.draw(g, &self.bars_on_screen());
+            return;
+        }
+
         if let Some(cursor) = g.canvas.get_cursor_in_screen_space() {
             let pt = Pt2D::new(cursor.x - self.top_left.x, cursor.y - self.top_left.y);
             for (rect, lbl) in &self.rect_labels {
@@ -148,6 +291,21 @@ impl Histogram {
     }
 }
 
+fn y_labels(max_y: usize) -> Vec<(Text, f64)> {
+    let num_y_labels = 5;
+    (0..num_y_labels)
+        .map(|i| {
+            let percent_y = (i as f64) / ((num_y_labels - 1) as f64);
+            (
+                Text::from(Line(prettyprint_usize(
+                    ((max_y as f64) * percent_y) as usize,
+                ))),
+                percent_y,
+            )
+        })
+        .collect()
+}
+
 impl Widget for Histogram {
     fn get_dims(&self) -> ScreenDims {
         self.dims
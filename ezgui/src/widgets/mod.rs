@@ -1,5 +1,6 @@
 mod autocomplete;
 mod button;
+mod chart;
 mod filler;
 mod histogram;
 mod modal_menu;
@@ -14,11 +15,12 @@ mod wizard;
 
 pub use self::autocomplete::Autocomplete;
 pub use self::button::Button;
+pub use self::chart::csv_contents;
 pub use self::filler::Filler;
 pub use self::histogram::Histogram;
 pub use self::modal_menu::ModalMenu;
 pub use self::no_op::JustDraw;
-pub use self::plot::{Plot, Series};
+pub use self::plot::{closest_in_time, plot_csv_rows, Plot, PlotView, Series, PLOT_CSV_HEADER};
 pub(crate) use self::popup_menu::PopupMenu;
 pub(crate) use self::screenshot::{screenshot_current, screenshot_everything};
 pub use self::slider::{ItemSlider, Slider, SliderWithTextBox, WarpingItemSlider};
@@ -34,10 +34,13 @@ impl ModalMenu {
             chosen_action: None,
             choices: raw_choices
                 .into_iter()
-                .map(|(hotkey, label)| Choice {
-                    hotkey,
-                    label: label.into(),
-                    active: false,
+                .map(|(hotkey, label)| {
+                    let label: String = label.into();
+                    Choice {
+                        hotkey: ctx.hotkey_for(&label, hotkey),
+                        label,
+                        active: false,
+                    }
                 })
                 .collect(),
             hovering_idx: None,
@@ -114,7 +117,7 @@ impl ModalMenu {
                 continue;
             }
             if let Some(hotkey) = choice.hotkey {
-                if ctx.input.new_was_pressed(hotkey) {
+                if ctx.input.action_pressed(hotkey, &choice.label) {
                     self.chosen_action = Some(choice.label.clone());
                     break;
                 }
@@ -129,7 +132,7 @@ impl ModalMenu {
 
     pub fn push_action(&mut self, hotkey: Option<MultiKey>, label: &str, ctx: &EventCtx) {
         self.choices.push(Choice {
-            hotkey,
+            hotkey: ctx.hotkey_for(label, hotkey),
             label: label.to_string(),
             active: false,
         });
@@ -1,247 +1,474 @@
 use crate::layout::Widget;
+use crate::widgets::chart::{self, RangeSelection, Selected};
 use crate::{
     Color, DrawBoth, EventCtx, GeomBatch, GfxCtx, Line, ManagedWidget, ScreenDims, ScreenPt,
     ScreenRectangle, Text,
 };
 use abstutil::prettyprint_usize;
-use geom::{Bounds, Circle, Distance, Duration, FindClosest, PolyLine, Pt2D, Time};
+use geom::{Circle, Distance, Duration, PolyLine, Pt2D, Time};
+use std::collections::BTreeSet;
 
-// The X is always time
+// The X is always time. Hovering shows the exact values nearest the cursor, dragging across the
+// plot zooms into that time range (click to zoom back out), clicking the legend hides a series, and
+// whatever's visible can be exported to CSV.
 pub struct Plot<T> {
-    draw: DrawBoth,
+    series: Vec<Series<T>>,
+    y_zero: T,
+    view: PlotView,
+    selection: RangeSelection,
+    // Replaces the usage hint after exporting
+    status: Option<String>,
 
-    // The geometry here is in screen-space.
+    draw: DrawBoth,
+    // The geometry here is in screen-space, relative to top_left. render() recalculates it.
+    legend: Vec<(String, ScreenRectangle)>,
+    export_btn: ScreenRectangle,
+    min_x: Time,
     max_x: Time,
-    max_y: Box<dyn Yvalue<T>>,
-    closest: FindClosest<String>,
+    max_y: T,
+
+    // Fixed once the plot is created, so the layout never changes.
+    plot_rect: ScreenRectangle,
+    y_label_width: f64,
 
     top_left: ScreenPt,
     dims: ScreenDims,
 }
 
-impl<T: 'static + Ord + PartialEq + Copy + core::fmt::Debug + Yvalue<T>> Plot<T> {
-    // TODO I want to store y_zero in the trait, but then we can't Box max_y.
-    // Returns (plot, legend, X axis labels, Y axis labels)
-    fn new(
-        series: Vec<Series<T>>,
-        y_zero: T,
-        ctx: &EventCtx,
-    ) -> (Plot<T>, ManagedWidget, ManagedWidget, ManagedWidget) {
-        let mut batch = GeomBatch::new();
+// What the player has done to a plot. Hang onto this when rebuilding a plot with fresh data.
+#[derive(Clone)]
+pub struct PlotView {
+    // Only restore onto a plot with the same series
+    labels: Vec<String>,
+    hidden: BTreeSet<String>,
+    // None means everything so far
+    zoom: Option<(Time, Time)>,
+}
 
+impl<T: 'static + Ord + PartialEq + Copy + core::fmt::Debug + Yvalue<T>> Plot<T> {
+    // TODO I want to store y_zero in the trait.
+    fn new(series: Vec<Series<T>>, y_zero: T, ctx: &EventCtx) -> Plot<T> {
+        let line_height = ctx.default_line_height();
         let width = 0.3 * ctx.canvas.window_width;
         let height = 0.2 * ctx.canvas.window_height;
+        let legend_height = (series.len() as f64) * line_height;
 
-        let radius = 15.0;
-        let legend = ManagedWidget::col(
-            series
-                .iter()
-                .map(|s| {
-                    ManagedWidget::row(vec![
-                        ManagedWidget::draw_batch(
-                            ctx,
-                            GeomBatch::from(vec![(
-                                s.color,
-                                Circle::new(Pt2D::new(radius, radius), Distance::meters(radius))
-                                    .to_polygon(),
-                            )]),
-                        ),
-                        ManagedWidget::draw_text(ctx, Text::from(Line(&s.label))),
-                    ])
-                })
-                .collect(),
+        let mut plot = Plot {
+            view: PlotView {
+                labels: series.iter().map(|s| s.label.clone()).collect(),
+                hidden: BTreeSet::new(),
+                zoom: None,
+            },
+            series,
+            y_zero,
+            selection: RangeSelection::new(),
+            status: None,
+
+            draw: DrawBoth::new(ctx, GeomBatch::new(), Vec::new()),
+            legend: Vec::new(),
+            export_btn: ScreenRectangle::placeholder(),
+            min_x: Time::START_OF_DAY,
+            max_x: Time::START_OF_DAY,
+            max_y: y_zero,
+
+            plot_rect: ScreenRectangle::placeholder(),
+            y_label_width: 0.0,
+
+            top_left: ScreenPt::new(0.0, 0.0),
+            dims: ScreenDims::new(0.0, 0.0),
+        };
+
+        // Zooming or hiding series only shrinks the Y labels, so size for everything.
+        plot.update_bounds();
+        plot.y_label_width = plot
+            .y_labels()
+            .into_iter()
+            .map(|(txt, _)| ctx.text_dims(&txt).width)
+            .fold(0.0, f64::max)
+            + 10.0;
+        plot.plot_rect = ScreenRectangle::top_left(
+            ScreenPt::new(plot.y_label_width, legend_height + line_height / 2.0),
+            ScreenDims::new(width, height),
         );
+        // Room for the X axis labels and the export link
+        plot.dims = ScreenDims::new(
+            plot.plot_rect.x2,
+            plot.plot_rect.y2 + 5.0 + 2.0 * line_height,
+        );
+        plot.render(ctx);
+        plot
+    }
 
-        // Assume min_x is Time::START_OF_DAY and min_y is y_zero
-        let max_x = series
+    fn visible_series<'a>(&'a self) -> impl Iterator<Item = &'a Series<T>> + 'a {
+        self.series
             .iter()
-            .map(|s| {
-                s.pts
-                    .iter()
-                    .map(|(t, _)| *t)
-                    .max()
-                    .unwrap_or(Time::START_OF_DAY)
-            })
-            .max()
-            .unwrap_or(Time::START_OF_DAY);
-        let max_y = series
-            .iter()
-            .map(|s| {
-                s.pts
-                    .iter()
-                    .map(|(_, value)| *value)
-                    .max()
-                    .unwrap_or(y_zero)
+            .filter(move |s| !self.view.hidden.contains(&s.label))
+    }
+
+    fn update_bounds(&mut self) {
+        let (min_x, max_x) = self.view.zoom.unwrap_or_else(|| {
+            // Assume min_x is Time::START_OF_DAY
+            let max_x = self
+                .series
+                .iter()
+                .flat_map(|s| s.pts.iter().map(|(t, _)| *t))
+                .max()
+                .unwrap_or(Time::START_OF_DAY);
+            (Time::START_OF_DAY, max_x)
+        });
+        self.min_x = min_x;
+        self.max_x = max_x;
+
+        // Assume min_y is y_zero
+        let mut max_y = self.y_zero;
+        for s in self.visible_series() {
+            for (t, y) in &s.pts {
+                if *t >= min_x && *t <= max_x && *y > max_y {
+                    max_y = *y;
+                }
+            }
+        }
+        self.max_y = max_y;
+    }
+
+    fn pct_x(&self, t: Time) -> f64 {
+        if self.max_x == self.min_x {
+            0.0
+        } else {
+            (t - self.min_x) / (self.max_x - self.min_x)
+        }
+    }
+
+    fn to_screen(&self, t: Time, y: T) -> Pt2D {
+        Pt2D::new(
+            self.plot_rect.x1 + self.pct_x(t) * self.plot_rect.width(),
+            // Y inversion! :D
+            self.plot_rect.y1 + (1.0 - y.to_percent(self.max_y)) * self.plot_rect.height(),
+        )
+    }
+
+    fn y_labels(&self) -> Vec<(Text, f64)> {
+        let num_y_labels = 4;
+        (0..num_y_labels)
+            .map(|i| {
+                let percent_y = (i as f64) / ((num_y_labels - 1) as f64);
+                (
+                    Text::from(Line(self.max_y.from_percent(percent_y).prettyprint())),
+                    percent_y,
+                )
             })
-            .max()
-            .unwrap_or(y_zero);
+            .collect()
+    }
+
+    fn render(&mut self, ctx: &EventCtx) {
+        self.update_bounds();
+
+        let line_height = ctx.default_line_height();
+        let rect = self.plot_rect.clone();
+        let mut batch = GeomBatch::new();
+        let mut txt: Vec<(Text, ScreenPt)> = Vec::new();
+
+        self.legend.clear();
+        for (idx, s) in self.series.iter().enumerate() {
+            let y = (idx as f64) * line_height;
+            let shown = !self.view.hidden.contains(&s.label);
+            batch.push(
+                if shown { s.color } else { s.color.alpha(0.2) },
+                Circle::new(
+                    Pt2D::new(line_height / 2.0, y + line_height / 2.0),
+                    Distance::meters(line_height / 3.0),
+                )
+                .to_polygon(),
+            );
+            let label = if shown {
+                Text::from(Line(&s.label))
+            } else {
+                Text::from(Line(&s.label).fg(Color::grey(0.5)))
+            };
+            let dims = ctx.text_dims(&label);
+            txt.push((label, ScreenPt::new(line_height, y)));
+            self.legend.push((
+                s.label.clone(),
+                ScreenRectangle::top_left(
+                    ScreenPt::new(0.0, y),
+                    ScreenDims::new(line_height + dims.width, line_height),
+                ),
+            ));
+        }
 
         // Grid lines for the Y scale. Draw up to 10 lines max to cover the order of magnitude of
         // the range.
         // TODO This caps correctly, but if the max is 105, then suddenly we just have 2 grid
         // lines.
         {
-            let order_of_mag = 10.0_f64.powf(max_y.to_f64().log10().ceil());
+            let order_of_mag = 10.0_f64.powf(self.max_y.to_f64().log10().ceil());
             for i in 0..10 {
-                let y = max_y.from_f64(order_of_mag / 10.0 * (i as f64));
-                let pct = y.to_percent(max_y);
+                let y = self.max_y.from_f64(order_of_mag / 10.0 * (i as f64));
+                let pct = y.to_percent(self.max_y);
                 if pct > 1.0 {
                     break;
                 }
                 batch.push(
                     Color::BLACK,
                     PolyLine::new(vec![
-                        Pt2D::new(0.0, (1.0 - pct) * height),
-                        Pt2D::new(width, (1.0 - pct) * height),
+                        Pt2D::new(rect.x1, rect.y1 + (1.0 - pct) * rect.height()),
+                        Pt2D::new(rect.x2, rect.y1 + (1.0 - pct) * rect.height()),
                     ])
                     .make_polygons(Distance::meters(5.0)),
                 );
             }
         }
         // X axis grid
-        if max_x != Time::START_OF_DAY {
-            let order_of_mag = 10.0_f64.powf(max_x.inner_seconds().log10().ceil());
+        if self.max_x != self.min_x {
+            let order_of_mag =
+                10.0_f64.powf((self.max_x - self.min_x).inner_seconds().log10().ceil());
             for i in 0..10 {
-                let x = Time::START_OF_DAY + Duration::seconds(order_of_mag / 10.0 * (i as f64));
-                let pct = x.to_percent(max_x);
+                let x = self.min_x + Duration::seconds(order_of_mag / 10.0 * (i as f64));
+                let pct = self.pct_x(x);
                 if pct > 1.0 {
                     break;
                 }
                 batch.push(
                     Color::BLACK,
                     PolyLine::new(vec![
-                        Pt2D::new(pct * width, 0.0),
-                        Pt2D::new(pct * width, height),
+                        Pt2D::new(rect.x1 + pct * rect.width(), rect.y1),
+                        Pt2D::new(rect.x1 + pct * rect.width(), rect.y2),
                     ])
                     .make_polygons(Distance::meters(5.0)),
                 );
             }
-        }
 
-        let mut closest = FindClosest::new(&Bounds::from(&vec![
-            Pt2D::new(0.0, 0.0),
-            Pt2D::new(width, height),
-        ]));
-        for s in series {
-            if max_x == Time::START_OF_DAY {
-                continue;
-            }
-            let mut pts = Vec::new();
-            for (t, y) in s.pts {
-                let percent_x = t.to_percent(max_x);
-                let percent_y = y.to_percent(max_y);
-                pts.push(Pt2D::new(
-                    percent_x * width,
-                    // Y inversion! :D
-                    (1.0 - percent_y) * height,
-                ));
-            }
-            pts.dedup();
-            if pts.len() >= 2 {
-                closest.add(s.label.clone(), &pts);
-                batch.push(
-                    s.color,
-                    PolyLine::new(pts)
-                        .make_polygons_with_miter_threshold(Distance::meters(5.0), 10.0),
-                );
+            for s in self.visible_series() {
+                let mut pts = Vec::new();
+                for (t, y) in &s.pts {
+                    if *t >= self.min_x && *t <= self.max_x {
+                        pts.push(self.to_screen(*t, *y));
+                    }
+                }
+                pts.dedup();
+                if pts.len() >= 2 {
+                    batch.push(
+                        s.color,
+                        PolyLine::new(pts)
+                            .make_polygons_with_miter_threshold(Distance::meters(5.0), 10.0),
+                    );
+                }
             }
         }
 
-        let plot = Plot {
-            draw: DrawBoth::new(ctx, batch, Vec::new()),
-            closest,
-            max_x,
-            max_y: Box::new(max_y),
-
-            top_left: ScreenPt::new(0.0, 0.0),
-            dims: ScreenDims::new(width, height),
-        };
+        for (label, percent_y) in self.y_labels() {
+            let dims = ctx.text_dims(&label);
+            txt.push((
+                label,
+                ScreenPt::new(
+                    (self.y_label_width - 5.0 - dims.width).max(0.0),
+                    rect.y1 + (1.0 - percent_y) * rect.height() - dims.height / 2.0,
+                ),
+            ));
+        }
 
         let num_x_labels = 4;
-        let mut row = Vec::new();
         for i in 0..num_x_labels {
             let percent_x = (i as f64) / ((num_x_labels - 1) as f64);
-            let t = max_x.percent_of(percent_x);
-            row.push(ManagedWidget::draw_text(
-                ctx,
-                Text::from(Line(t.to_string())),
+            let t = self.min_x + (self.max_x - self.min_x) * percent_x;
+            let label = Text::from(Line(t.to_string()));
+            let dims = ctx.text_dims(&label);
+            // Keep the first and last labels inside the plot
+            txt.push((
+                label,
+                ScreenPt::new(
+                    rect.x1 + percent_x * (rect.width() - dims.width),
+                    rect.y2 + 5.0,
+                ),
             ));
         }
-        let x_axis = ManagedWidget::row(row);
 
-        let num_y_labels = 4;
-        let mut col = Vec::new();
-        for i in 0..num_y_labels {
-            let percent_y = (i as f64) / ((num_y_labels - 1) as f64);
-            col.push(ManagedWidget::draw_text(
-                ctx,
-                Text::from(Line(max_y.from_percent(percent_y).prettyprint())),
-            ));
+        self.export_btn = chart::footer(
+            ctx,
+            ScreenPt::new(rect.x1, rect.y2 + 5.0 + line_height),
+            &self.status,
+            self.view.zoom.is_some(),
+            &mut txt,
+        );
+
+        self.draw = DrawBoth::new(ctx, batch, txt);
+    }
+
+    fn to_absolute(&self, rect: &ScreenRectangle) -> ScreenRectangle {
+        ScreenRectangle {
+            x1: self.top_left.x + rect.x1,
+            y1: self.top_left.y + rect.y1,
+            x2: self.top_left.x + rect.x2,
+            y2: self.top_left.y + rect.y2,
+        }
+    }
+
+    pub(crate) fn event(&mut self, ctx: &mut EventCtx) {
+        let rect = self.to_absolute(&self.plot_rect);
+        match self.selection.event(ctx, &rect) {
+            Some(Selected::Range(pct1, pct2)) => {
+                let range = self.max_x - self.min_x;
+                self.view.zoom = Some((self.min_x + range * pct1, self.min_x + range * pct2));
+                self.status = None;
+                self.render(ctx);
+                return;
+            }
+            Some(Selected::Click) => {
+                if self.view.zoom.is_some() {
+                    self.view.zoom = None;
+                    self.status = None;
+                    self.render(ctx);
+                }
+                return;
+            }
+            None => {}
+        }
+        if self.selection.is_active() {
+            return;
         }
-        col.reverse();
-        let y_axis = ManagedWidget::col(col);
 
-        (plot, legend, x_axis, y_axis)
+        let pt = match ctx.canvas.get_cursor_in_screen_space() {
+            Some(pt) => ScreenPt::new(pt.x - self.top_left.x, pt.y - self.top_left.y),
+            None => {
+                return;
+            }
+        };
+        if self.export_btn.contains(pt) && ctx.normal_left_click() {
+            self.status = Some(self.export());
+            self.render(ctx);
+            return;
+        }
+        let toggle = self
+            .legend
+            .iter()
+            .find(|(_, rect)| rect.contains(pt))
+            .map(|(label, _)| label.clone());
+        if let Some(label) = toggle {
+            if ctx.normal_left_click() {
+                if !self.view.hidden.remove(&label) {
+                    self.view.hidden.insert(label);
+                }
+                self.render(ctx);
+            }
+        }
     }
 
     pub(crate) fn draw(&self, g: &mut GfxCtx) {
         self.draw.redraw(self.top_left, g);
 
+        let rect = self.to_absolute(&self.plot_rect);
+        if self.selection.is_active() {
+            self.selection.draw(g, &rect);
+            return;
+        }
+        if self.max_x == self.min_x {
+            return;
+        }
         if let Some(cursor) = g.canvas.get_cursor_in_screen_space() {
-            if ScreenRectangle::top_left(self.top_left, self.dims).contains(cursor) {
-                let radius = Distance::meters(5.0);
+            if rect.contains(cursor) {
+                let t =
+                    self.min_x + (self.max_x - self.min_x) * ((cursor.x - rect.x1) / rect.width());
                 let mut txt = Text::new().bg(Color::grey(0.6));
-                for (label, pt, _) in self.closest.all_close_pts(
-                    Pt2D::new(cursor.x - self.top_left.x, cursor.y - self.top_left.y),
-                    radius,
-                ) {
-                    let t = self.max_x.percent_of(pt.x() / self.dims.width);
-                    let y_percent = 1.0 - (pt.y() / self.dims.height);
-
-                    // TODO Draw this info in the ColorLegend
-                    txt.add(Line(format!(
-                        "{}: at {}, {}",
-                        label,
-                        t,
-                        self.max_y.from_percent(y_percent).prettyprint()
-                    )));
+                txt.add(Line(t.to_string()));
+                let mut circles = Vec::new();
+                for s in self.visible_series() {
+                    if let Some((pt_t, y)) = closest_in_time(&s.pts, t, self.min_x, self.max_x) {
+                        txt.add_appended(vec![
+                            Line(&s.label).fg(s.color),
+                            Line(format!(": {} at {}", y.prettyprint(), pt_t)),
+                        ]);
+                        let pt = self.to_screen(pt_t, y);
+                        circles.push(Circle::new(
+                            Pt2D::new(self.top_left.x + pt.x(), self.top_left.y + pt.y()),
+                            Distance::meters(5.0),
+                        ));
+                    }
                 }
-                if txt.num_lines() > 0 {
-                    g.fork_screenspace();
-                    g.draw_circle(Color::RED, &Circle::new(cursor.to_pt(), radius));
-                    g.draw_mouse_tooltip(&txt);
-                    g.unfork();
+
+                g.fork_screenspace();
+                g.draw_polygon(
+                    Color::WHITE.alpha(0.5),
+                    &PolyLine::new(vec![
+                        Pt2D::new(cursor.x, rect.y1),
+                        Pt2D::new(cursor.x, rect.y2),
+                    ])
+                    .make_polygons(Distance::meters(2.0)),
+                );
+                for circle in circles {
+                    g.draw_circle(Color::RED, &circle);
                 }
+                g.draw_mouse_tooltip(&txt);
+                g.unfork();
             }
         }
     }
+
+    fn export(&self) -> String {
+        chart::export_csv(
+            "plot",
+            PLOT_CSV_HEADER,
+            plot_csv_rows(self.visible_series(), self.min_x, self.max_x),
+        )
+    }
+
+    pub(crate) fn view(&self) -> PlotView {
+        self.view.clone()
+    }
+
+    pub(crate) fn restore_view(&mut self, ctx: &EventCtx, view: PlotView) {
+        if view.labels == self.view.labels {
+            self.view = view;
+            self.render(ctx);
+        }
+    }
+}
+
+pub const PLOT_CSV_HEADER: &str = "series,time_seconds,value";
+
+// One row per point within the visible range, matching PLOT_CSV_HEADER
+pub fn plot_csv_rows<'a, T: 'a + Copy + Yvalue<T>, I: Iterator<Item = &'a Series<T>>>(
+    series: I,
+    min_x: Time,
+    max_x: Time,
+) -> Vec<String> {
+    let mut rows = Vec::new();
+    for s in series {
+        for (t, y) in &s.pts {
+            if *t >= min_x && *t <= max_x {
+                rows.push(format!(
+                    "\"{}\",{},{}",
+                    s.label.replace('"', "\"\""),
+                    t.inner_seconds(),
+                    y.to_csv()
+                ));
+            }
+        }
+    }
+    rows
+}
+
+// The point nearest in time, but still within the visible range. Ties go to the earlier point.
+pub fn closest_in_time<T: Copy>(
+    pts: &[(Time, T)],
+    t: Time,
+    min_x: Time,
+    max_x: Time,
+) -> Option<(Time, T)> {
+    pts.iter()
+        .filter(|(pt_t, _)| *pt_t >= min_x && *pt_t <= max_x)
+        .min_by_key(|(pt_t, _)| if *pt_t > t { *pt_t - t } else { t - *pt_t })
+        .cloned()
 }
 
 impl Plot<usize> {
     pub fn new_usize(series: Vec<Series<usize>>, ctx: &EventCtx) -> ManagedWidget {
-        let (plot, legend, x_axis, y_axis) = Plot::new(series, 0, ctx);
-        ManagedWidget::col(vec![
-            legend,
-            ManagedWidget::row(vec![
-                y_axis.evenly_spaced(),
-                ManagedWidget::usize_plot(plot),
-            ]),
-            x_axis.evenly_spaced(),
-        ])
+        ManagedWidget::usize_plot(Plot::new(series, 0, ctx))
     }
 }
 
 impl Plot<Duration> {
     pub fn new_duration(series: Vec<Series<Duration>>, ctx: &EventCtx) -> ManagedWidget {
-        let (plot, legend, x_axis, y_axis) = Plot::new(series, Duration::ZERO, ctx);
-        ManagedWidget::col(vec![
-            legend,
-            ManagedWidget::row(vec![
-                y_axis.evenly_spaced(),
-                ManagedWidget::duration_plot(plot),
-            ]),
-            x_axis.evenly_spaced(),
-        ])
+        ManagedWidget::duration_plot(Plot::new(series, Duration::ZERO, ctx))
     }
 }
 
@@ -263,6 +490,8 @@ pub trait Yvalue<T> {
     // For order of magnitude calculations
     fn to_f64(self) -> f64;
     fn from_f64(&self, x: f64) -> T;
+    // In base units (seconds, meters), no matter how things are displayed
+    fn to_csv(self) -> String;
 }
 
 impl Yvalue<usize> for usize {
@@ -285,6 +514,9 @@ impl Yvalue<usize> for usize {
     fn from_f64(&self, x: f64) -> usize {
         x as usize
     }
+    fn to_csv(self) -> String {
+        self.to_string()
+    }
 }
 impl Yvalue<Duration> for Duration {
     fn from_percent(&self, percent: f64) -> Duration {
@@ -306,6 +538,9 @@ impl Yvalue<Duration> for Duration {
     fn from_f64(&self, x: f64) -> Duration {
         Duration::seconds(x as f64)
     }
+    fn to_csv(self) -> String {
+        self.inner_seconds().to_string()
+    }
 }
 
 pub struct Series<T> {
@@ -18,7 +18,10 @@ pub struct PopupMenu<T: Clone> {
 }
 
 impl<T: Clone> PopupMenu<T> {
-    pub fn new(choices: Vec<Choice<T>>, ctx: &EventCtx) -> PopupMenu<T> {
+    pub fn new(mut choices: Vec<Choice<T>>, ctx: &EventCtx) -> PopupMenu<T> {
+        for choice in choices.iter_mut() {
+            choice.hotkey = ctx.hotkey_for(&choice.label, choice.hotkey);
+        }
         let mut m = PopupMenu {
             choices,
             current_idx: 0,
@@ -84,7 +87,7 @@ impl<T: Clone> PopupMenu<T> {
                 continue;
             }
             if let Some(hotkey) = choice.hotkey {
-                if ctx.input.new_was_pressed(hotkey) {
+                if ctx.input.action_pressed(hotkey, &choice.label) {
                     self.state = InputResult::Done(choice.label.clone(), choice.data.clone());
                     return;
                 }
@@ -178,10 +178,12 @@ pub fn challenges_picker(ctx: &mut EventCtx) -> Box<dyn State> {
                             summary.add(Line(l));
                         }
                         summary.add(Line(""));
-                        summary.add(Line(format!("{} proposals:", edits.len())));
+                        summary.add(Line(
+                            ctx.tr("{num} proposals:", &[("num", edits.len().to_string())]),
+                        ));
                         summary.add(Line(""));
                         for e in edits {
-                            summary.add(Line(format!("- {} (untested)", e)));
+                            summary.add(Line(ctx.tr("- {name} (untested)", &[("name", e)])));
                         }
 
                         Some(Transition::Push(Box::new(ChallengeSplash {
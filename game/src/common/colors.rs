@@ -11,7 +11,9 @@ use std::collections::HashMap;
 
 pub struct ColorerBuilder {
     header: Text,
-    prioritized_colors: Vec<(&'static str, Color)>,
+    prioritized_colors: Vec<(String, Color)>,
+    // Below the prioritized colors, like a continuous scale
+    extra_legend: Vec<ManagedWidget>,
     lanes: HashMap<LaneID, Color>,
     roads: HashMap<RoadID, Color>,
     intersections: HashMap<IntersectionID, Color>,
@@ -28,10 +30,17 @@ pub struct Colorer {
 impl Colorer {
     // Colors listed earlier override those listed later. This is used in unzoomed mode, when one
     // road has lanes of different colors.
-    pub fn new(header: Text, prioritized_colors: Vec<(&'static str, Color)>) -> ColorerBuilder {
+    pub fn new<S: Into<String>>(
+        header: Text,
+        prioritized_colors: Vec<(S, Color)>,
+    ) -> ColorerBuilder {
         ColorerBuilder {
             header,
-            prioritized_colors,
+            prioritized_colors: prioritized_colors
+                .into_iter()
+                .map(|(label, color)| (label.into(), color))
+                .collect(),
+            extra_legend: Vec::new(),
             lanes: HashMap::new(),
             roads: HashMap::new(),
             intersections: HashMap::new(),
@@ -100,6 +109,10 @@ impl ColorerBuilder {
         self.bus_stops.insert(bs, color);
     }
 
+    pub fn add_legend_row(&mut self, row: ManagedWidget) {
+        self.extra_legend.push(row);
+    }
+
     pub fn build(self, ctx: &mut EventCtx, ui: &UI) -> Colorer {
         let mut zoomed = GeomBatch::new();
         let mut unzoomed = GeomBatch::new();
@@ -141,6 +154,7 @@ impl ColorerBuilder {
         for (label, color) in self.prioritized_colors {
             col.push(ColorLegend::row(ctx, color, label));
         }
+        col.extend(self.extra_legend);
         let legend = Composite::new(ManagedWidget::col(col).bg(Color::grey(0.4)))
             .aligned(HorizontalAlignment::Right, VerticalAlignment::Center)
             .build(ctx);
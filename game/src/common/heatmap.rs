@@ -0,0 +1,354 @@
+use crate::common::{Colorer, Overlays};
+use crate::game::{msg, State, Transition, WizardState};
+use crate::ui::UI;
+use abstutil::Cloneable;
+use ezgui::{Choice, Color, EventCtx, GeomBatch, Line, ManagedWidget, Text, Wizard};
+use geom::{Duration, Polygon};
+use map_model::LaneID;
+use sim::{Binning, Metric, ParkingSpot};
+use std::collections::BTreeMap;
+
+#[derive(Clone, PartialEq)]
+pub enum MetricSource {
+    Throughput,
+    IntersectionDelay,
+    NearConflicts,
+    ParkingAvailability,
+    Csv(String),
+}
+
+impl Cloneable for MetricSource {}
+
+impl MetricSource {
+    fn builtin() -> Vec<MetricSource> {
+        vec![
+            MetricSource::Throughput,
+            MetricSource::IntersectionDelay,
+            MetricSource::NearConflicts,
+            MetricSource::ParkingAvailability,
+        ]
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            MetricSource::Throughput => "throughput so far".to_string(),
+            MetricSource::IntersectionDelay => {
+                "intersection delay in the last 2 hours (90%ile, seconds)".to_string()
+            }
+            MetricSource::NearConflicts => "near conflicts so far".to_string(),
+            MetricSource::ParkingAvailability => "percent of parking spots available".to_string(),
+            MetricSource::Csv(ref path) => path.clone(),
+        }
+    }
+
+    // Everything from the sim has to be recalculated as time passes.
+    pub fn is_live(&self) -> bool {
+        match self {
+            MetricSource::Csv(_) => false,
+            _ => true,
+        }
+    }
+
+    fn compute(&self, ui: &UI) -> Result<Metric, String> {
+        let map = &ui.primary.map;
+        let sim = &ui.primary.sim;
+        let now = sim.time();
+        let mut metric = Metric::new(self.describe());
+
+        match self {
+            MetricSource::Throughput => {
+                let stats = &sim.get_analytics().thruput_stats;
+                for r in map.all_roads() {
+                    let cnt = stats.count_per_road.get(r.id);
+                    if cnt > 0 {
+                        metric.roads.insert(r.id, cnt as f64);
+                    }
+                }
+                for i in map.all_intersections() {
+                    let cnt = stats.count_per_intersection.get(i.id);
+                    if cnt > 0 {
+                        metric.intersections.insert(i.id, cnt as f64);
+                    }
+                }
+            }
+            MetricSource::IntersectionDelay => {
+                for i in map.all_intersections() {
+                    let delays = sim.get_analytics().intersection_delays(
+                        i.id,
+                        now.clamped_sub(Duration::hours(2)),
+                        now,
+                    );
+                    if let Some(d) = delays.percentile(90.0) {
+                        metric.intersections.insert(i.id, d.inner_seconds());
+                    }
+                }
+            }
+            MetricSource::NearConflicts => {
+                for (i, counts) in sim.get_analytics().near_conflicts(now) {
+                    metric
+                        .intersections
+                        .insert(i, counts.values().sum::<usize>() as f64);
+                }
+            }
+            MetricSource::ParkingAvailability => {
+                let lane = |spot| match spot {
+                    ParkingSpot::Onstreet(l, _) => l,
+                    ParkingSpot::Offstreet(b, _) => {
+                        map.get_b(b).parking.as_ref().unwrap().driving_pos.lane()
+                    }
+                };
+                // (filled, available)
+                let mut per_lane: BTreeMap<LaneID, (usize, usize)> = BTreeMap::new();
+                let (filled, avail) = sim.get_all_parking_spots();
+                for spot in filled {
+                    per_lane.entry(lane(spot)).or_insert((0, 0)).0 += 1;
+                }
+                for spot in avail {
+                    per_lane.entry(lane(spot)).or_insert((0, 0)).1 += 1;
+                }
+                for (l, (filled, avail)) in per_lane {
+                    metric
+                        .lanes
+                        .insert(l, 100.0 * (avail as f64) / ((filled + avail) as f64));
+                }
+            }
+            MetricSource::Csv(ref path) => {
+                return Metric::load_csv(path, map);
+            }
+        }
+        Ok(metric)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ColorRamp {
+    Heat,
+    Cool,
+    Diverging,
+    ColorBlindSafe,
+}
+
+impl Cloneable for ColorRamp {}
+
+impl ColorRamp {
+    fn all() -> Vec<ColorRamp> {
+        vec![
+            ColorRamp::Heat,
+            ColorRamp::Cool,
+            ColorRamp::Diverging,
+            ColorRamp::ColorBlindSafe,
+        ]
+    }
+
+    fn describe(self) -> &'static str {
+        match self {
+            ColorRamp::Heat => "yellow to red",
+            ColorRamp::Cool => "light to dark blue",
+            ColorRamp::Diverging => "blue to white to red",
+            ColorRamp::ColorBlindSafe => "purple to yellow (color-blind safe)",
+        }
+    }
+
+    // Evenly spaced, from the lowest value to the highest
+    fn stops(self) -> Vec<Color> {
+        let hex = match self {
+            ColorRamp::Heat => vec!["#FFFFB2", "#FECC5C", "#FD8D3C", "#F03B20", "#BD0026"],
+            ColorRamp::Cool => vec!["#F1EEF6", "#BDC9E1", "#74A9CF", "#2B8CBE", "#045A8D"],
+            ColorRamp::Diverging => vec!["#2166AC", "#67A9CF", "#F7F7F7", "#EF8A62", "#B2182B"],
+            ColorRamp::ColorBlindSafe => {
+                vec!["#440154", "#3B528B", "#21908C", "#5DC963", "#FDE725"]
+            }
+        };
+        hex.into_iter().map(Color::hex).collect()
+    }
+
+    // pct is [0.0, 1.0]
+    fn eval(self, pct: f64) -> Color {
+        let stops = self.stops();
+        let pct = abstutil::clamp(pct, 0.0, 1.0) * ((stops.len() - 1) as f64);
+        let idx = (pct.floor() as usize).min(stops.len() - 2);
+        let t = (pct - (idx as f64)) as f32;
+        match (stops[idx], stops[idx + 1]) {
+            (Color::RGBA(r1, g1, b1, a1), Color::RGBA(r2, g2, b2, a2)) => Color::RGBA(
+                r1 + (r2 - r1) * t,
+                g1 + (g2 - g1) * t,
+                b1 + (b2 - b1) * t,
+                a1 + (a2 - a1) * t,
+            ),
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct HeatmapOptions {
+    pub source: MetricSource,
+    pub ramp: ColorRamp,
+    pub binning: Binning,
+}
+
+pub struct Heatmap {
+    pub opts: HeatmapOptions,
+    pub colorer: Colorer,
+}
+
+impl Heatmap {
+    pub fn new(opts: HeatmapOptions, ctx: &mut EventCtx, ui: &UI) -> Result<Heatmap, String> {
+        let metric = opts.source.compute(ui)?;
+        let values = metric.sorted_values();
+        let min = values.first().cloned().unwrap_or(0.0);
+        let max = values.last().cloned().unwrap_or(0.0);
+        let pct = |x: f64| {
+            if max == min {
+                0.0
+            } else {
+                (x - min) / (max - min)
+            }
+        };
+
+        let mut txt = Text::from(Line(&metric.name));
+        txt.add(Line(format!(
+            "{} roads, {} lanes, {} intersections, {} buildings",
+            metric.roads.len(),
+            metric.lanes.len(),
+            metric.intersections.len(),
+            metric.buildings.len()
+        )));
+
+        let thresholds = opts.binning.thresholds(&values);
+        let num_bins = thresholds.len() + 1;
+        let bin_color = |bin: usize| {
+            if num_bins == 1 {
+                opts.ramp.eval(1.0)
+            } else {
+                opts.ramp.eval((bin as f64) / ((num_bins - 1) as f64))
+            }
+        };
+        let color_for = |x: f64| {
+            if thresholds.is_empty() {
+                opts.ramp.eval(pct(x))
+            } else {
+                bin_color(thresholds.iter().filter(|t| x >= **t).count())
+            }
+        };
+
+        // Higher bins are listed first, so they win when lanes of one road differ.
+        let mut legend: Vec<(String, Color)> = Vec::new();
+        if !thresholds.is_empty() {
+            for bin in (0..num_bins).rev() {
+                let low = if bin == 0 { min } else { thresholds[bin - 1] };
+                let high = if bin == num_bins - 1 {
+                    max
+                } else {
+                    thresholds[bin]
+                };
+                legend.push((
+                    format!("{} to {}", prettyprint(low), prettyprint(high)),
+                    bin_color(bin),
+                ));
+            }
+        }
+        let mut colorer = Colorer::new(txt, legend);
+        if thresholds.is_empty() {
+            colorer.add_legend_row(gradient(ctx, opts.ramp, min, max));
+        }
+
+        // With a continuous scale, the first lane added decides the road's color, so go from the
+        // highest value down. Roads and intersections are colored directly.
+        let mut lanes: Vec<(LaneID, f64)> = metric.lanes.iter().map(|(l, x)| (*l, *x)).collect();
+        lanes.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap());
+        for (l, x) in lanes {
+            colorer.add_l(l, color_for(x), &ui.primary.map);
+        }
+        for (r, x) in &metric.roads {
+            colorer.add_r(*r, color_for(*x), &ui.primary.map);
+        }
+        for (i, x) in &metric.intersections {
+            colorer.add_i(*i, color_for(*x));
+        }
+        for (b, x) in &metric.buildings {
+            colorer.add_b(*b, color_for(*x));
+        }
+
+        Ok(Heatmap {
+            colorer: colorer.build(ctx, ui),
+            opts,
+        })
+    }
+}
+
+fn gradient(ctx: &mut EventCtx, ramp: ColorRamp, min: f64, max: f64) -> ManagedWidget {
+    let steps = 20;
+    let (width, height) = (10.0, 20.0);
+    let mut batch = GeomBatch::new();
+    for i in 0..steps {
+        batch.push(
+            ramp.eval((i as f64) / ((steps - 1) as f64)),
+            Polygon::rectangle(width, height).translate((i as f64) * width, 0.0),
+        );
+    }
+    ManagedWidget::row(vec![
+        ManagedWidget::draw_text(ctx, Text::from(Line(prettyprint(min)))).margin(5),
+        ManagedWidget::draw_batch(ctx, batch).margin(5),
+        ManagedWidget::draw_text(ctx, Text::from(Line(prettyprint(max)))).margin(5),
+    ])
+}
+
+fn prettyprint(x: f64) -> String {
+    if x.abs() >= 100.0 {
+        format!("{:.0}", x)
+    } else if x.abs() >= 1.0 {
+        format!("{:.1}", x)
+    } else {
+        format!("{:.2}", x)
+    }
+}
+
+pub fn choose_heatmap() -> Box<dyn State> {
+    WizardState::new(Box::new(choose))
+}
+
+fn choose(wiz: &mut Wizard, ctx: &mut EventCtx, ui: &mut UI) -> Option<Transition> {
+    let map_name = ui.primary.map.get_name().clone();
+    let mut wizard = wiz.wrap(ctx);
+    let (_, source) = wizard.choose("Show what?", || {
+        let mut choices: Vec<Choice<MetricSource>> = MetricSource::builtin()
+            .into_iter()
+            .map(|s| Choice::new(s.describe(), s))
+            .collect();
+        for name in abstutil::list_all_objects(abstutil::path_all_metrics(&map_name)) {
+            choices.push(Choice::new(
+                format!("{}.csv", name),
+                MetricSource::Csv(abstutil::path_metric(&map_name, &name)),
+            ));
+        }
+        choices
+    })?;
+    let (_, ramp) = wizard.choose("Use what colors?", || {
+        ColorRamp::all()
+            .into_iter()
+            .map(|r| Choice::new(r.describe(), r))
+            .collect()
+    })?;
+    let (_, binning) = wizard.choose("Group the values how?", || {
+        vec![
+            Choice::new("continuous scale", Binning::Continuous),
+            Choice::new("5 equal intervals", Binning::EqualIntervals(5)),
+            Choice::new("5 quantiles", Binning::Quantiles(5)),
+            Choice::new("10 quantiles", Binning::Quantiles(10)),
+        ]
+    })?;
+
+    let opts = HeatmapOptions {
+        source,
+        ramp,
+        binning,
+    };
+    match Heatmap::new(opts, ctx, ui) {
+        Ok(heatmap) => {
+            ui.overlay = Overlays::Heatmap(ui.primary.sim.time(), heatmap);
+            Some(Transition::Pop)
+        }
+        Err(err) => Some(Transition::Replace(msg("Couldn't show heatmap", vec![err]))),
+    }
+}
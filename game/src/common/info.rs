@@ -1,6 +1,6 @@
 use crate::common::{ColorLegend, CommonState, Warping};
 use crate::game::{msg, Transition};
-use crate::helpers::{rotating_color, rotating_color_map, ID};
+use crate::helpers::{color_for_mode, rotating_color, rotating_color_map, ID};
 use crate::managed::WrappedComposite;
 use crate::render::{dashed_lines, Renderable, MIN_ZOOM_FOR_DETAIL};
 use crate::sandbox::SpeedControls;
@@ -12,7 +12,7 @@ use ezgui::{
 };
 use geom::{Circle, Distance, Duration, Statistic, Time};
 use map_model::{IntersectionID, RoadID};
-use sim::{CarID, TripEnd, TripID, TripMode, TripStart};
+use sim::{CarID, TripEnd, TripID, TripStart};
 use std::collections::BTreeMap;
 
 pub struct InfoPanel {
@@ -189,8 +189,10 @@ impl InfoPanel {
             // TODO Detect crowds changing here maybe
 
             let preserve_scroll = self.composite.preserve_scroll();
+            let preserve_plots = self.composite.preserve_plots();
             *self = InfoPanel::new(self.id.clone(), ctx, ui, self.actions.clone(), maybe_speed);
             self.composite.restore_scroll(ctx, preserve_scroll);
+            self.composite.restore_plots(ctx, preserve_plots);
             return (false, None);
         }
 
@@ -263,7 +265,7 @@ fn info_for(id: ID, ctx: &EventCtx, ui: &UI) -> Vec<ManagedWidget> {
                 txt.add(Line(""));
 
                 let mut tr = false;
-                if let Some(types) = l.get_turn_restrictions(r) {
+                if let Some(ref types) = l.allowed_turns {
                     txt.add(Line(format!("Turn restriction for this lane: {:?}", types)));
                     tr = true;
                 }
@@ -321,6 +323,12 @@ fn info_for(id: ID, ctx: &EventCtx, ui: &UI) -> Vec<ManagedWidget> {
                 let road = map.get_r(*r);
                 txt.add_appended(vec![Line("- "), Line(road.get_name()).fg(name_color)]);
             }
+            if let Some(roundabout) = map.maybe_get_roundabout(id) {
+                txt.add(Line(format!(
+                    "Part of a roundabout with {} intersections",
+                    roundabout.members.len()
+                )));
+            }
 
             let cnt = sim.count_trips_involving_border(id);
             if cnt.nonzero() {
@@ -562,15 +570,6 @@ fn intersection_delay(
     )
 }
 
-fn color_for_mode(m: TripMode, ui: &UI) -> Color {
-    match m {
-        TripMode::Walk => ui.cs.get("unzoomed pedestrian"),
-        TripMode::Bike => ui.cs.get("unzoomed bike"),
-        TripMode::Transit => ui.cs.get("unzoomed bus"),
-        TripMode::Drive => ui.cs.get("unzoomed car"),
-    }
-}
-
 // (extra rows to display, unzoomed view, zoomed view)
 fn trip_details(trip: TripID, ctx: &mut EventCtx, ui: &UI) -> (ManagedWidget, Drawable, Drawable) {
     let map = &ui.primary.map;
@@ -0,0 +1,58 @@
+use crate::game::{State, Transition};
+use crate::managed::{ManagedGUIState, WrappedComposite};
+use ezgui::{
+    hotkey, Color, Composite, EventCtx, Key, Line, ManagedWidget, MultiKey, Text, HOTKEY_COLOR,
+};
+use std::collections::BTreeMap;
+
+// Everything the current state checked for on the last event. Keys claimed by more than one action
+// are probably a mistake in the player's keybindings file (or in the code).
+pub fn keybindings_help(ctx: &mut EventCtx, bindings: Vec<(MultiKey, String)>) -> Box<dyn State> {
+    let mut per_key: BTreeMap<MultiKey, usize> = BTreeMap::new();
+    for (key, _) in &bindings {
+        *per_key.entry(*key).or_insert(0) += 1;
+    }
+
+    let mut txt = Text::new();
+    if bindings.is_empty() {
+        txt.add(Line("Nothing here has a hotkey"));
+    }
+    txt.add(Line("(Camera controls and keys inside menus aren't listed)").fg(Color::grey(0.7)));
+    for (key, action) in bindings {
+        txt.add_appended(vec![
+            Line(key.describe()).fg(HOTKEY_COLOR),
+            Line(format!(" - {}", action)),
+        ]);
+        if per_key[&key] > 1 {
+            txt.append(Line(" (conflict)").fg(Color::RED));
+        }
+    }
+
+    ManagedGUIState::over_map(
+        WrappedComposite::new(
+            Composite::new(
+                ManagedWidget::col(vec![
+                    ManagedWidget::row(vec![
+                        ManagedWidget::draw_text(
+                            ctx,
+                            Text::from(Line("Keybindings").roboto_bold()),
+                        ),
+                        WrappedComposite::svg_button(
+                            ctx,
+                            "assets/pregame/back.svg",
+                            "back",
+                            hotkey(Key::Escape),
+                        )
+                        .align_right(),
+                    ]),
+                    ManagedWidget::draw_text(ctx, txt),
+                ])
+                .padding(10)
+                .bg(Color::hex("#5B5B5B")),
+            )
+            .max_size_percent(40, 70)
+            .build(ctx),
+        )
+        .cb("back", Box::new(|_, _| Some(Transition::Pop))),
+    )
+}
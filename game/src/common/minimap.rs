@@ -302,6 +302,7 @@ fn make_minimap_panel(ctx: &mut EventCtx, acs: &AgentColorScheme, zoom_lvl: usiz
             None,
             &format!("zoom to level {}", i + 1),
             rect,
+            ctx,
         )));
     }
     zoom_col.push(ManagedWidget::btn(Button::rectangle_svg(
@@ -1,6 +1,8 @@
 mod bus_explorer;
 mod colors;
+mod heatmap;
 mod info;
+mod keybindings;
 mod minimap;
 mod navigate;
 mod overlays;
@@ -11,6 +13,8 @@ mod warp;
 
 pub use self::bus_explorer::ShowBusRoute;
 pub use self::colors::{ColorLegend, Colorer};
+pub use self::heatmap::Heatmap;
+pub use self::keybindings::keybindings_help;
 pub use self::minimap::Minimap;
 pub use self::overlays::Overlays;
 pub use self::panels::tool_panel;
@@ -46,10 +50,10 @@ impl CommonState {
         ui: &mut UI,
         maybe_speed: Option<&mut SpeedControls>,
     ) -> Option<Transition> {
-        if ctx.input.new_was_pressed(lctrl(Key::S).unwrap()) {
+        if ctx.action_pressed(lctrl(Key::S), "toggle developer mode") {
             ui.opts.dev = !ui.opts.dev;
         }
-        if ui.opts.dev && ctx.input.new_was_pressed(lctrl(Key::J).unwrap()) {
+        if ui.opts.dev && ctx.action_pressed(lctrl(Key::J), "warp to an object") {
             return Some(Transition::Push(warp::EnteringWarp::new()));
         }
 
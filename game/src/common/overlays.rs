@@ -1,4 +1,5 @@
-use crate::common::{ColorLegend, Colorer, ShowBusRoute, Warping};
+use crate::common::heatmap::choose_heatmap;
+use crate::common::{ColorLegend, Colorer, Heatmap, ShowBusRoute, Warping};
 use crate::game::Transition;
 use crate::helpers::rotating_color_total;
 use crate::helpers::ID;
@@ -11,18 +12,21 @@ use ezgui::{
     Text, VerticalAlignment,
 };
 use geom::{Circle, Distance, Duration, PolyLine, Polygon, Pt2D, Statistic, Time};
-use map_model::{BusRouteID, IntersectionID};
-use sim::ParkingSpot;
-use std::collections::HashSet;
+use map_model::{AccessMode, BuildingID, BusRouteID, IntersectionID, Isochrone, PathConstraints};
+use sim::{ConflictKind, ParkingSpot};
+use std::collections::{BTreeMap, HashSet};
 
 pub enum Overlays {
     Inactive,
     ParkingAvailability(Time, Colorer),
     IntersectionDelay(Time, Colorer),
+    NearConflicts(Time, Colorer),
     CumulativeThroughput(Time, Colorer),
     BikeNetwork(Colorer),
     BusNetwork(Colorer),
     Edits(Colorer),
+    Isochrone(BuildingID, PathConstraints, Colorer, Drawable),
+    Heatmap(Time, Heatmap),
 
     FinishedTripsHistogram(Time, Composite),
     IntersectionDemand(Time, IntersectionID, Drawable, Composite),
@@ -46,6 +50,11 @@ impl Overlays {
                     ui.overlay = Overlays::intersection_delay(ctx, ui);
                 }
             }
+            Overlays::NearConflicts(t, _) => {
+                if now != t {
+                    ui.overlay = Overlays::near_conflicts(ctx, ui);
+                }
+            }
             Overlays::CumulativeThroughput(t, _) => {
                 if now != t {
                     ui.overlay = Overlays::cumulative_throughput(ctx, ui);
@@ -76,11 +85,21 @@ impl Overlays {
                     ui.overlay = Overlays::bus_passengers(id, ctx, ui);
                 }
             }
+            Overlays::Heatmap(t, ref heatmap) => {
+                if now != t && heatmap.opts.source.is_live() {
+                    let opts = heatmap.opts.clone();
+                    // Live metrics come from the sim and can't fail
+                    if let Ok(h) = Heatmap::new(opts, ctx, ui) {
+                        ui.overlay = Overlays::Heatmap(now, h);
+                    }
+                }
+            }
             // No updates needed
             Overlays::Inactive
             | Overlays::BikeNetwork(_)
             | Overlays::BusNetwork(_)
-            | Overlays::Edits(_) => {}
+            | Overlays::Edits(_)
+            | Overlays::Isochrone(_, _, _, _) => {}
         };
 
         // Because BusPassengers has the callbacks that need UI, but UI also stores Overlays, we
@@ -92,8 +111,17 @@ impl Overlays {
             | Overlays::BikeNetwork(ref mut heatmap)
             | Overlays::BusNetwork(ref mut heatmap)
             | Overlays::IntersectionDelay(_, ref mut heatmap)
+            | Overlays::NearConflicts(_, ref mut heatmap)
             | Overlays::CumulativeThroughput(_, ref mut heatmap)
-            | Overlays::Edits(ref mut heatmap) => {
+            | Overlays::Edits(ref mut heatmap)
+            | Overlays::Isochrone(_, _, ref mut heatmap, _)
+            | Overlays::Heatmap(
+                _,
+                Heatmap {
+                    colorer: ref mut heatmap,
+                    ..
+                },
+            ) => {
                 if heatmap.event(ctx) {
                     ui.overlay = Overlays::Inactive;
                 } else {
@@ -162,6 +190,34 @@ impl Overlays {
         None
     }
 
+    // Overlays bake in their colors, so rebuild the current one after the color scheme changes.
+    pub fn recolor(ctx: &mut EventCtx, ui: &mut UI) {
+        ui.overlay = match ui.overlay {
+            Overlays::Inactive => Overlays::Inactive,
+            Overlays::ParkingAvailability(_, _) => Overlays::parking_availability(ctx, ui),
+            Overlays::IntersectionDelay(_, _) => Overlays::intersection_delay(ctx, ui),
+            Overlays::NearConflicts(_, _) => Overlays::near_conflicts(ctx, ui),
+            Overlays::CumulativeThroughput(_, _) => Overlays::cumulative_throughput(ctx, ui),
+            Overlays::BikeNetwork(_) => Overlays::bike_network(ctx, ui),
+            Overlays::BusNetwork(_) => Overlays::bus_network(ctx, ui),
+            Overlays::Edits(_) => Overlays::map_edits(ctx, ui),
+            Overlays::Isochrone(b, constraints, _, _) => {
+                Overlays::isochrone(b, constraints, ctx, ui)
+            }
+            Overlays::Heatmap(t, ref heatmap) => {
+                match Heatmap::new(heatmap.opts.clone(), ctx, ui) {
+                    Ok(h) => Overlays::Heatmap(t, h),
+                    Err(_) => Overlays::Inactive,
+                }
+            }
+            Overlays::FinishedTripsHistogram(_, _) => Overlays::finished_trips_histogram(ctx, ui),
+            Overlays::IntersectionDemand(_, i, _, _) => Overlays::intersection_demand(i, ctx, ui),
+            Overlays::BusRoute(_, id, _) => Overlays::show_bus_route(id, ctx, ui),
+            Overlays::BusDelaysOverTime(_, id, _) => Overlays::delays_over_time(id, ctx, ui),
+            Overlays::BusPassengers(_, id, _) => Overlays::bus_passengers(id, ctx, ui),
+        };
+    }
+
     pub fn draw(&self, g: &mut GfxCtx) {
         match self {
             Overlays::Inactive => {}
@@ -169,8 +225,20 @@ impl Overlays {
             | Overlays::BikeNetwork(ref heatmap)
             | Overlays::BusNetwork(ref heatmap)
             | Overlays::IntersectionDelay(_, ref heatmap)
+            | Overlays::NearConflicts(_, ref heatmap)
             | Overlays::CumulativeThroughput(_, ref heatmap)
-            | Overlays::Edits(ref heatmap) => {
+            | Overlays::Edits(ref heatmap)
+            | Overlays::Heatmap(
+                _,
+                Heatmap {
+                    colorer: ref heatmap,
+                    ..
+                },
+            ) => {
+                heatmap.draw(g);
+            }
+            Overlays::Isochrone(_, _, ref heatmap, ref outlines) => {
+                g.redraw(outlines);
                 heatmap.draw(g);
             }
             Overlays::FinishedTripsHistogram(_, ref composite)
@@ -196,8 +264,11 @@ impl Overlays {
             | Overlays::BikeNetwork(ref heatmap)
             | Overlays::BusNetwork(ref heatmap)
             | Overlays::IntersectionDelay(_, ref heatmap)
+            | Overlays::NearConflicts(_, ref heatmap)
             | Overlays::CumulativeThroughput(_, ref heatmap)
-            | Overlays::Edits(ref heatmap) => Some(heatmap),
+            | Overlays::Edits(ref heatmap)
+            | Overlays::Isochrone(_, _, ref heatmap, _) => Some(heatmap),
+            Overlays::Heatmap(_, ref h) => Some(&h.colorer),
             Overlays::BusRoute(_, _, ref s) => Some(&s.colorer),
             _ => None,
         }
@@ -221,6 +292,8 @@ impl Overlays {
                             hotkey(Key::F),
                         ),
                         WrappedComposite::text_button(ctx, "map edits", hotkey(Key::E)),
+                        WrappedComposite::text_button(ctx, "near conflicts", hotkey(Key::C)),
+                        WrappedComposite::text_button(ctx, "custom heatmap", hotkey(Key::H)),
                         ManagedWidget::btn(Button::rectangle_svg(
                             "assets/layers/parking_avail.svg",
                             "parking availability",
@@ -314,6 +387,17 @@ impl Overlays {
                 Some(Transition::Pop)
             }),
         )
+        .cb(
+            "near conflicts",
+            Box::new(|ctx, ui| {
+                ui.overlay = Overlays::near_conflicts(ctx, ui);
+                Some(Transition::Pop)
+            }),
+        )
+        .cb(
+            "custom heatmap",
+            Box::new(|_, _| Some(Transition::Replace(choose_heatmap()))),
+        )
         .cb(
             "map edits",
             Box::new(|ctx, ui| {
@@ -338,10 +422,18 @@ impl Overlays {
             prettyprint_usize(avail_spots.len())
         )));
 
-        let awful = Color::hex("#801F1C");
-        let bad = Color::hex("#EB5757");
-        let meh = Color::hex("#F2C94C");
-        let good = Color::hex("#7FFA4D");
+        let awful = ui
+            .cs
+            .get_def("parking availability awful", Color::hex("#801F1C"));
+        let bad = ui
+            .cs
+            .get_def("parking availability bad", Color::hex("#EB5757"));
+        let meh = ui
+            .cs
+            .get_def("parking availability meh", Color::hex("#F2C94C"));
+        let good = ui
+            .cs
+            .get_def("parking availability good", Color::hex("#7FFA4D"));
         let mut colorer = Colorer::new(
             txt,
             vec![
@@ -399,9 +491,7 @@ impl Overlays {
     }
 
     pub fn intersection_delay(ctx: &mut EventCtx, ui: &UI) -> Overlays {
-        let fast = Color::hex("#7FFA4D");
-        let meh = Color::hex("#F4DA22");
-        let slow = Color::hex("#EB5757");
+        let (fast, meh, slow) = low_medium_high(ui);
         let mut colorer = Colorer::new(
             Text::from(Line(
                 "intersection delay for traffic signals in the last 2 hours (90%ile)",
@@ -430,10 +520,57 @@ impl Overlays {
         Overlays::IntersectionDelay(ui.primary.sim.time(), colorer.build(ctx, ui))
     }
 
+    fn near_conflicts(ctx: &mut EventCtx, ui: &UI) -> Overlays {
+        let (few, some, many) = low_medium_high(ui);
+
+        let now = ui.primary.sim.time();
+        let per_intersection = ui.primary.sim.get_analytics().near_conflicts(now);
+        let mut totals: BTreeMap<ConflictKind, usize> = BTreeMap::new();
+        for counts in per_intersection.values() {
+            for (kind, cnt) in counts {
+                *totals.entry(*kind).or_insert(0) += cnt;
+            }
+        }
+        let mut txt = Text::from(Line("near conflicts so far"));
+        for kind in ConflictKind::all() {
+            txt.add(Line(format!(
+                "{}: {}",
+                kind,
+                prettyprint_usize(totals.get(&kind).cloned().unwrap_or(0))
+            )));
+        }
+        if ui.has_prebaked() {
+            let baseline: usize = ui
+                .prebaked()
+                .near_conflicts(now)
+                .values()
+                .map(|counts| counts.values().sum::<usize>())
+                .sum();
+            txt.add(Line(format!(
+                "{} total, compared to {} in the baseline",
+                prettyprint_usize(totals.values().sum()),
+                prettyprint_usize(baseline)
+            )));
+        }
+        let mut colorer = Colorer::new(txt, vec![("< 10", few), ("< 50", some), (">= 50", many)]);
+
+        for (i, counts) in per_intersection {
+            let cnt: usize = counts.values().sum();
+            let color = if cnt < 10 {
+                few
+            } else if cnt < 50 {
+                some
+            } else {
+                many
+            };
+            colorer.add_i(i, color);
+        }
+
+        Overlays::NearConflicts(now, colorer.build(ctx, ui))
+    }
+
     fn cumulative_throughput(ctx: &mut EventCtx, ui: &UI) -> Overlays {
-        let light = Color::hex("#7FFA4D");
-        let medium = Color::hex("#F4DA22");
-        let heavy = Color::hex("#EB5757");
+        let (light, medium, heavy) = low_medium_high(ui);
         let mut colorer = Colorer::new(
             Text::from(Line("Throughput")),
             vec![
@@ -485,7 +622,7 @@ impl Overlays {
     }
 
     fn bike_network(ctx: &mut EventCtx, ui: &UI) -> Overlays {
-        let color = Color::hex("#7FFA4D");
+        let color = ui.cs.get_def("bike network", Color::hex("#7FFA4D"));
         let mut colorer = Colorer::new(
             Text::from(Line("bike networks")),
             vec![("bike lanes", color)],
@@ -499,8 +636,8 @@ impl Overlays {
     }
 
     fn bus_network(ctx: &mut EventCtx, ui: &UI) -> Overlays {
-        let lane = Color::hex("#4CA7E9");
-        let stop = Color::hex("#4CA7E9");
+        let lane = ui.cs.get_def("bus network lane", Color::hex("#4CA7E9"));
+        let stop = ui.cs.get_def("bus network stop", Color::hex("#4CA7E9"));
         let mut colorer = Colorer::new(
             Text::from(Line("bus networks")),
             vec![("bus lanes", lane), ("bus stops", stop)],
@@ -550,6 +687,7 @@ impl Overlays {
     }
 
     pub fn intersection_demand(i: IntersectionID, ctx: &mut EventCtx, ui: &UI) -> Overlays {
+        let color = ui.cs.get_def("intersection demand", Color::RED);
         let mut batch = GeomBatch::new();
 
         let mut total_demand = 0;
@@ -573,7 +711,7 @@ impl Overlays {
         for (pl, demand) in demand_per_group {
             let percent = (demand as f64) / (total_demand as f64);
             batch.push(
-                Color::RED,
+                color,
                 pl.make_arrow(percent * Distance::meters(5.0)).unwrap(),
             );
         }
@@ -589,7 +727,7 @@ impl Overlays {
             )),
             WrappedComposite::text_button(ctx, "X", None).align_right(),
         ])];
-        col.push(ColorLegend::row(ctx, Color::RED, "current demand"));
+        col.push(ColorLegend::row(ctx, color, "current demand"));
 
         Overlays::IntersectionDemand(
             ui.primary.sim.time(),
@@ -646,7 +784,11 @@ impl Overlays {
 
         let y_len = ctx.default_line_height() * (route.stops.len() as f64);
         let mut batch = GeomBatch::new();
-        batch.push(Color::CYAN, Polygon::rounded_rectangle(15.0, y_len, 4.0));
+        batch.push(
+            ui.cs.get_def("bus passenger timeline", Color::CYAN),
+            Polygon::rounded_rectangle(15.0, y_len, 4.0),
+        );
+        let bus_color = ui.cs.get_def("bus on passenger timeline", Color::BLUE);
         for (_, stop_idx, percent_next_stop) in ui.primary.sim.status_of_buses(route.id) {
             // TODO Line it up right in the middle of the line of text. This is probably a bit
             // wrong.
@@ -656,7 +798,7 @@ impl Overlays {
                 (stop_idx as f64) / ((route.stops.len() - 1) as f64)
             };
             batch.push(
-                Color::BLUE,
+                bus_color,
                 Circle::new(
                     Pt2D::new(
                         7.5,
@@ -760,7 +902,7 @@ impl Overlays {
             edits.changed_intersections.len()
         )));
 
-        let changed = Color::RED;
+        let changed = ui.cs.get_def("map edits", Color::RED);
         let mut colorer = Colorer::new(txt, vec![("modified lane/intersection", changed)]);
 
         for l in edits.original_lts.keys().chain(&edits.reversed_lanes) {
@@ -772,4 +914,91 @@ impl Overlays {
 
         Overlays::Edits(colorer.build(ctx, ui))
     }
+
+    pub fn isochrone(
+        b: BuildingID,
+        constraints: PathConstraints,
+        ctx: &mut EventCtx,
+        ui: &UI,
+    ) -> Overlays {
+        let map = &ui.primary.map;
+        let bands = vec![
+            (
+                5,
+                "within 5 minutes",
+                ui.cs.get_def("isochrone within 5 minutes", Color::GREEN),
+            ),
+            (
+                10,
+                "within 10 minutes",
+                ui.cs.get_def("isochrone within 10 minutes", Color::YELLOW),
+            ),
+            (
+                15,
+                "within 15 minutes",
+                ui.cs.get_def("isochrone within 15 minutes", Color::ORANGE),
+            ),
+            (
+                20,
+                "within 20 minutes",
+                ui.cs.get_def("isochrone within 20 minutes", Color::RED),
+            ),
+        ];
+        let isochrone = Isochrone::from_building(
+            map,
+            b,
+            constraints,
+            bands
+                .iter()
+                .map(|(mins, _, _)| Duration::minutes(*mins))
+                .collect(),
+        );
+
+        let start = ui.cs.get_def("isochrone start", Color::BLUE);
+        let mut legend: Vec<(&'static str, Color)> = bands
+            .iter()
+            .map(|(_, label, color)| (*label, *color))
+            .collect();
+        legend.push(("start", start));
+        let mut colorer = Colorer::new(
+            Text::from(Line(format!(
+                "reachable by {} from {}",
+                AccessMode::from_constraints(constraints),
+                b
+            ))),
+            legend,
+        );
+        let mut outlines = GeomBatch::new();
+        // Each band includes the smaller ones, so only color things the first time they show up.
+        let mut seen_lanes = HashSet::new();
+        let mut seen_bldgs = HashSet::new();
+        seen_bldgs.insert(b);
+        colorer.add_b(b, start);
+        for (band, (_, _, color)) in isochrone.bands.iter().zip(bands.iter()) {
+            for l in &band.lanes {
+                if seen_lanes.insert(*l) {
+                    colorer.add_l(*l, *color, map);
+                }
+            }
+            for b in &band.buildings {
+                if seen_bldgs.insert(*b) {
+                    colorer.add_b(*b, *color);
+                }
+            }
+            if let Some(ref polygon) = band.polygon {
+                outlines.push(*color, polygon.to_outline(Distance::meters(5.0)));
+            }
+        }
+
+        Overlays::Isochrone(b, constraints, colorer.build(ctx, ui), outlines.upload(ctx))
+    }
+}
+
+// Shared by the overlays that bucket things into a few levels
+fn low_medium_high(ui: &UI) -> (Color, Color, Color) {
+    (
+        ui.cs.get_def("overlay low", Color::hex("#7FFA4D")),
+        ui.cs.get_def("overlay medium", Color::hex("#F4DA22")),
+        ui.cs.get_def("overlay high", Color::hex("#EB5757")),
+    )
 }
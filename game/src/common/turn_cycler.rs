@@ -327,6 +327,7 @@ fn make_diagram(i: IntersectionID, selected: usize, ui: &UI, ctx: &mut EventCtx)
                 None,
                 &format!("phase {}", idx + 1),
                 bbox.clone(),
+                ctx,
             ))
             .margin(5),
         );
@@ -7,13 +7,16 @@ use crate::common::{tool_panel, CommonState};
 use crate::game::{msg, State, Transition, WizardState};
 use crate::helpers::ID;
 use crate::managed::{WrappedComposite, WrappedOutcome};
+use crate::render::DrawMap;
 use crate::ui::{ShowLayers, ShowObject, UI};
+use abstutil::Timer;
 use ezgui::{
     hotkey, Color, Drawable, EventCtx, EventLoopMode, GeomBatch, GfxCtx, Key, Line, ModalMenu,
     Text, Wizard,
 };
 use geom::Duration;
-use sim::Sim;
+use raster::Rgba;
+use sim::{GetDrawAgents, Sim};
 use std::collections::HashSet;
 
 pub struct DebugMode {
@@ -41,6 +44,8 @@ impl DebugMode {
                     (hotkey(Key::Num6), "show labels"),
                     (hotkey(Key::R), "show route for all agents"),
                     (None, "screenshot everything"),
+                    (None, "export map to SVG"),
+                    (None, "export map to PNG"),
                     (hotkey(Key::Slash), "search OSM metadata"),
                     (hotkey(Key::O), "save sim state"),
                     (hotkey(Key::Y), "load previous sim state"),
@@ -232,6 +237,13 @@ impl State for DebugMode {
             });
         }
 
+        if self.menu.action("export map to SVG") {
+            export_map(ui, true);
+        }
+        if self.menu.action("export map to PNG") {
+            export_map(ui, false);
+        }
+
         if self.search_results.is_some() {
             if self
                 .menu
@@ -377,3 +389,34 @@ fn load_savestate(wiz: &mut Wizard, ctx: &mut EventCtx, ui: &mut UI) -> Option<T
     });
     Some(Transition::Pop)
 }
+
+fn export_map(ui: &UI, svg: bool) {
+    let map = &ui.primary.map;
+    let mut timer = Timer::new("export map");
+    let batch = DrawMap::zoomed_out_batch(
+        map,
+        &ui.cs,
+        Some((&ui.primary.sim as &dyn GetDrawAgents, &ui.agent_cs)),
+        &mut timer,
+    );
+    let path = abstutil::path_map_image(
+        map.get_name(),
+        &ui.primary.sim.time().as_filename(),
+        if svg { "svg" } else { "png" },
+    );
+    let result = if svg {
+        raster::write_svg(&path, batch.for_export(), &map.get_bounds(), Rgba::WHITE)
+    } else {
+        raster::write_png(
+            &path,
+            batch.for_export(),
+            &map.get_bounds(),
+            Rgba::WHITE,
+            4000,
+        )
+    };
+    match result {
+        Ok(()) => println!("Wrote {}", path),
+        Err(err) => println!("Couldn't write {}: {}", path, err),
+    }
+}
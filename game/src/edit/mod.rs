@@ -101,7 +101,7 @@ impl State for EditMode {
             }
         }
 
-        if ui.opts.dev && ctx.input.new_was_pressed(lctrl(Key::D).unwrap()) {
+        if ui.opts.dev && ctx.action_pressed(lctrl(Key::D), "debug mode") {
             return Transition::Push(Box::new(DebugMode::new(ctx)));
         }
 
@@ -70,12 +70,12 @@ impl State for TrafficSignalEditor {
         ctx.canvas_movement();
 
         // TODO Buttons for these...
-        if self.current_phase != 0 && ctx.input.new_was_pressed(hotkey(Key::UpArrow).unwrap()) {
+        if self.current_phase != 0 && ctx.action_pressed(hotkey(Key::UpArrow), "previous phase") {
             self.change_phase(self.current_phase - 1, ui, ctx);
         }
 
         if self.current_phase != ui.primary.map.get_traffic_signal(self.i).phases.len() - 1
-            && ctx.input.new_was_pressed(hotkey(Key::DownArrow).unwrap())
+            && ctx.action_pressed(hotkey(Key::DownArrow), "next phase")
         {
             self.change_phase(self.current_phase + 1, ui, ctx);
         }
@@ -526,6 +526,7 @@ fn make_diagram(i: IntersectionID, selected: usize, ui: &UI, ctx: &mut EventCtx)
                     None,
                     &format!("phase {}", idx + 1),
                     bbox.clone(),
+                    ctx,
                 ))
                 .margin(5),
                 ManagedWidget::col(move_phase),
@@ -1,12 +1,12 @@
-use crate::common::CommonState;
+use crate::common::{keybindings_help, CommonState};
 use crate::options::Options;
 use crate::pregame::TitleScreen;
 use crate::render::DrawOptions;
 use crate::sandbox::{GameplayMode, SandboxMode};
 use crate::ui::{Flags, ShowEverything, UI};
 use ezgui::{
-    Canvas, Color, Drawable, EventCtx, EventLoopMode, GfxCtx, HorizontalAlignment, Line, Text,
-    VerticalAlignment, Wizard, GUI,
+    hotkey, Canvas, Color, Drawable, EventCtx, EventLoopMode, GfxCtx, HorizontalAlignment, Key,
+    Line, Text, VerticalAlignment, Wizard, GUI,
 };
 use geom::Polygon;
 
@@ -38,7 +38,16 @@ impl GUI for Game {
     fn event(&mut self, ctx: &mut EventCtx) -> EventLoopMode {
         self.ui.per_obj.reset();
 
-        let transition = self.states.last_mut().unwrap().event(ctx, &mut self.ui);
+        let mut transition = self.states.last_mut().unwrap().event(ctx, &mut self.ui);
+        // If nothing else wanted F1, show what keys the current state responds to.
+        let staying = match transition {
+            Transition::Keep | Transition::KeepWithMode(_) => true,
+            _ => false,
+        };
+        if staying && ctx.action_pressed(hotkey(Key::F1), "show keybindings") {
+            let bindings = ctx.input.active_bindings();
+            transition = Transition::Push(keybindings_help(ctx, bindings));
+        }
         // If we fall through, there's a new state that we need to wakeup.
         match transition {
             Transition::Keep => {
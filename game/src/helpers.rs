@@ -1,11 +1,11 @@
 use crate::render::ExtraShapeID;
-use crate::ui::PerMapUI;
+use crate::ui::{PerMapUI, UI};
 use abstutil::Timer;
 use ezgui::{Color, Line, Text, TextSpan};
 use geom::Pt2D;
 use map_model::{AreaID, BuildingID, BusStopID, IntersectionID, LaneID, RoadID, TurnID};
 use serde_derive::{Deserialize, Serialize};
-use sim::{AgentID, CarID, PedestrianID, TripID};
+use sim::{AgentID, CarID, PedestrianID, TripID, TripMode};
 use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fmt::Write;
 
@@ -130,6 +130,16 @@ impl ColorScheme {
 
 include!(concat!(env!("OUT_DIR"), "/init_colors.rs"));
 
+// Matches the agent colors, so themes change both at once
+pub fn color_for_mode(m: TripMode, ui: &UI) -> Color {
+    match m {
+        TripMode::Walk => ui.cs.get("unzoomed pedestrian"),
+        TripMode::Bike => ui.cs.get("unzoomed bike"),
+        TripMode::Transit => ui.cs.get("unzoomed bus"),
+        TripMode::Drive => ui.cs.get("unzoomed car"),
+    }
+}
+
 pub fn rotating_color(idx: usize) -> Color {
     rotating_color_total(idx, 9)
 }
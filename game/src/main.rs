@@ -16,7 +16,8 @@ mod tutorial;
 mod ui;
 
 use crate::ui::Flags;
-use abstutil::CmdArgs;
+use abstutil::{CmdArgs, Timer};
+use geom::UnitSystem;
 use sim::SimFlags;
 
 fn main() {
@@ -34,7 +35,7 @@ fn main() {
         draw_lane_markings: !args.enabled("--dont_draw_lane_markings"),
         num_agents: args.optional_parse("--num_agents", |s| s.parse()),
     };
-    let mut opts = options::Options::default();
+    let mut opts = options::Options::load_or_default();
     if args.enabled("--dev") {
         opts.dev = true;
         flags.sim_flags.rng_seed = Some(42);
@@ -43,12 +44,48 @@ fn main() {
         opts.color_scheme = Some(format!("../data/system/{}", x));
     }
     let mut settings = ezgui::Settings::new("A/B Street");
+    if let Some(x) = args.optional("--locale") {
+        let catalog = ezgui::Catalog::load(
+            options::LOCALES_DIR,
+            &x,
+            &mut Timer::new("load translations"),
+        );
+        // Use the units people expect with that language, unless they change it later
+        opts.units = if catalog.metric_units {
+            UnitSystem::Metric
+        } else {
+            UnitSystem::Imperial
+        };
+        opts.locale = x;
+        settings.catalog(catalog);
+    } else if opts.locale != "en" {
+        settings.catalog(ezgui::Catalog::load(
+            options::LOCALES_DIR,
+            &opts.locale,
+            &mut Timer::new("load translations"),
+        ));
+    }
+    settings.keybindings(ezgui::Keybindings::load(abstutil::path_keybindings()));
+    if let Some(x) = args.optional("--units") {
+        opts.units = match x.as_ref() {
+            "metric" => UnitSystem::Metric,
+            "imperial" => UnitSystem::Imperial,
+            _ => panic!("--units={} should be metric or imperial", x),
+        };
+    }
+    opts.units.set();
     if args.enabled("--enable_profiler") {
         settings.enable_profiling();
     }
     if args.enabled("--dump_raw_events") {
         settings.dump_raw_events();
     }
+    if let Some(path) = args.optional("--record_events") {
+        settings.record_events(path);
+    }
+    if let Some(path) = args.optional("--replay_events") {
+        settings.replay_events(path, args.enabled("--replay_fast"));
+    }
     if let Some(n) = args.optional_parse("--font_size", |s| s.parse::<usize>()) {
         settings.default_font_size(n);
     }
@@ -13,8 +13,8 @@ use ezgui::{
 use geom::{Distance, Duration, PolyLine, Time};
 use map_model::{BuildingID, IntersectionID, Map, Neighborhood};
 use sim::{
-    BorderSpawnOverTime, DrivingGoal, OriginDestination, Scenario, SeedParkedCars, SidewalkPOI,
-    SidewalkSpot, SpawnOverTime, SpawnTrip,
+    BorderSpawnOverTime, DepartureDistribution, DrivingGoal, OriginDestination, Scenario,
+    SeedParkedCars, SidewalkPOI, SidewalkSpot, SpawnOverTime, SpawnTrip,
 };
 use std::collections::BTreeSet;
 
@@ -348,6 +348,7 @@ fn edit_scenario(map: &Map, scenario: &mut Scenario, mut wizard: WrappedWizard)
                 num_agents: wizard.input_usize("Spawn how many agents?")?,
                 start_time,
                 stop_time,
+                departure: choose_departure(&mut wizard, start_time, stop_time)?,
                 start_from_neighborhood: choose_neighborhood(
                     map,
                     &mut wizard,
@@ -370,6 +371,7 @@ fn edit_scenario(map: &Map, scenario: &mut Scenario, mut wizard: WrappedWizard)
                 num_bikes: wizard.input_usize("Spawn how many bikes?")?,
                 start_time,
                 stop_time,
+                departure: choose_departure(&mut wizard, start_time, stop_time)?,
                 // TODO validate it's a border!
                 start_from_border: choose_intersection(
                     &mut wizard,
@@ -390,6 +392,7 @@ fn edit_scenario(map: &Map, scenario: &mut Scenario, mut wizard: WrappedWizard)
                         num_agents: 100,
                         start_time: Time::START_OF_DAY,
                         stop_time: Time::START_OF_DAY + Duration::minutes(10),
+                        departure: DepartureDistribution::Uniform,
                         start_from_neighborhood: src.to_string(),
                         goal: OriginDestination::Neighborhood(dst.to_string()),
                         percent_biking: 0.1,
@@ -444,6 +447,49 @@ fn choose_origin_destination(
     }
 }
 
+fn choose_departure(
+    wizard: &mut WrappedWizard,
+    start: Time,
+    stop: Time,
+) -> Option<DepartureDistribution> {
+    let uniform = "Evenly";
+    let normal = "Normal distribution";
+    let triangular = "Peaking at some time";
+    let profile = "Using a departure profile";
+    match wizard
+        .choose_string("How should departures be spread out?", || {
+            vec![uniform, normal, triangular, profile]
+        })?
+        .as_str()
+    {
+        x if x == uniform => Some(DepartureDistribution::Uniform),
+        x if x == normal => Some(DepartureDistribution::Normal {
+            mean: wizard.input_time_slider("Most departures around when?", start, stop)?,
+            std_dev: Duration::minutes(wizard.input_usize("Standard deviation in minutes?")?),
+        }),
+        x if x == triangular => Some(DepartureDistribution::Triangular {
+            peak: wizard.input_time_slider("Peak when?", start, stop)?,
+        }),
+        x if x == profile => wizard
+            .choose("Use which profile?", || {
+                abstutil::list_all_objects(abstutil::path_all_departure_profiles())
+                    .into_iter()
+                    .filter_map(|name| match DepartureDistribution::load_profile(&name) {
+                        // Otherwise every departure would fall back to uniform
+                        Ok(d) if !d.has_weight(start, stop) => None,
+                        Ok(d) => Some(Choice::new(name, d)),
+                        Err(err) => {
+                            println!("Skipping departure profile {}: {}", name, err);
+                            None
+                        }
+                    })
+                    .collect()
+            })
+            .map(|(_, d)| d),
+        _ => unreachable!(),
+    }
+}
+
 // TODO Yet another one of these... something needs to change.
 #[derive(PartialEq, Debug, Clone, Copy)]
 enum OD {
@@ -28,9 +28,10 @@ impl PerObjectActions {
 
         // Funny special case: don't recursively show the info panel option
         if !(key == Key::I && lbl == "show info") {
-            self.actions.borrow_mut().push((key, lbl));
+            self.actions.borrow_mut().push((key, lbl.clone()));
         }
-        !self.info_panel_open && ctx.input.new_was_pressed(hotkey(key).unwrap())
+        // The info panel's buttons use the same label, so rebinding one rebinds both.
+        !self.info_panel_open && ctx.action_pressed(hotkey(key), &lbl)
     }
 
     pub fn consume(&mut self) -> Vec<(Key, String)> {
@@ -1,11 +1,18 @@
 use crate::game::{State, Transition, WizardState};
-use ezgui::Choice;
+use abstutil::Timer;
+use ezgui::{Catalog, Choice};
+use geom::UnitSystem;
+use serde_derive::{Deserialize, Serialize};
+
+pub const LOCALES_DIR: &str = "../data/system/locales";
 
 // TODO SimOptions stuff too
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Options {
     pub traffic_signal_style: TrafficSignalStyle,
     pub color_scheme: Option<String>,
+    pub locale: String,
+    pub units: UnitSystem,
     pub dev: bool,
 }
 
@@ -14,12 +21,26 @@ impl Options {
         Options {
             traffic_signal_style: TrafficSignalStyle::GroupArrows,
             color_scheme: None,
+            locale: "en".to_string(),
+            units: UnitSystem::current(),
             dev: false,
         }
     }
+
+    // Whatever the player last chose in the options panel
+    pub fn load_or_default() -> Options {
+        match abstutil::maybe_read_json(abstutil::path_options(), &mut Timer::throwaway()) {
+            Ok(opts) => opts,
+            Err(_) => Options::default(),
+        }
+    }
+
+    pub fn save(&self) {
+        abstutil::write_json(abstutil::path_options(), self);
+    }
 }
 
-#[derive(Clone, PartialEq)]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub enum TrafficSignalStyle {
     GroupArrows,
     Sidewalks,
@@ -65,22 +86,35 @@ pub fn open_panel() -> Box<dyn State> {
                     "night mode",
                     Some("../data/system/night_colors.json".to_string()),
                 ),
+                Choice::new(
+                    "high contrast",
+                    Some("../data/system/high_contrast_colors.json".to_string()),
+                ),
+                Choice::new(
+                    "color-blind safe",
+                    Some("../data/system/colorblind_colors.json".to_string()),
+                ),
+            ]
+        })?;
+        let (_, locale) = wizard.choose("What language?", || {
+            let mut choices = vec![Choice::new("English", "en".to_string())];
+            let mut timer = Timer::new("load translations");
+            for locale in abstutil::list_all_objects(LOCALES_DIR.to_string()) {
+                let catalog = Catalog::load(LOCALES_DIR, &locale, &mut timer);
+                choices.push(Choice::new(catalog.name, locale));
+            }
+            choices
+        })?;
+        let (_, units) = wizard.choose("Show distances and speeds in what units?", || {
+            vec![
+                Choice::new("metric (meters, km/h)", UnitSystem::Metric),
+                Choice::new("imperial (feet, mph)", UnitSystem::Imperial),
             ]
         })?;
         let (_, dev) = wizard.choose("Enable developer mode?", || {
             vec![Choice::new("yes", true), Choice::new("no", false)]
         })?;
 
-        if ui.opts.color_scheme != color_scheme {
-            wizard.acknowledge("Changing color scheme", || {
-                vec![
-                    "Changing color scheme will reset the simulation",
-                    "Also, some colors don't completely change immediately",
-                    "Please file a bug if you notice anything weird",
-                ]
-            })?;
-        }
-
         ui.opts.dev = dev;
 
         if ui.opts.traffic_signal_style != traffic_signal_style {
@@ -92,10 +126,24 @@ pub fn open_panel() -> Box<dyn State> {
         }
 
         if ui.opts.color_scheme != color_scheme {
-            ui.opts.color_scheme = color_scheme.clone();
-            ui.switch_map(ctx, ui.primary.current_flags.sim_flags.load.clone());
+            ui.change_color_scheme(ctx, color_scheme);
+        }
+
+        if ui.opts.locale != locale {
+            ctx.set_catalog(Catalog::load(
+                LOCALES_DIR,
+                &locale,
+                &mut Timer::new("load translations"),
+            ));
+            ui.opts.locale = locale;
         }
 
+        // Panels already showing keep their old text until they're rebuilt.
+        ui.opts.units = units;
+        units.set();
+
+        ui.opts.save();
+
         Some(Transition::Pop)
     }))
 }
@@ -1,8 +1,9 @@
 use crate::helpers::{ColorScheme, ID};
+use crate::render::map::unzoomed_color;
 use crate::render::{DrawCtx, DrawOptions, Renderable};
-use ezgui::{Color, GeomBatch, GfxCtx};
+use ezgui::{GeomBatch, GfxCtx};
 use geom::Polygon;
-use map_model::{Area, AreaID, AreaType, Map};
+use map_model::{Area, AreaID, Map, UnzoomedShape};
 
 pub struct DrawArea {
     pub id: AreaID,
@@ -10,12 +11,10 @@ pub struct DrawArea {
 
 impl DrawArea {
     pub fn new(area: &Area, cs: &ColorScheme, all_areas: &mut GeomBatch) -> DrawArea {
-        let color = match area.area_type {
-            AreaType::Park => cs.get_def("grass", Color::hex("#94C84A")),
-            AreaType::Water => cs.get_def("water", Color::rgb(164, 200, 234)),
-            AreaType::PedestrianIsland => Color::grey(0.3),
-        };
-        all_areas.push(color, area.polygon.clone());
+        all_areas.push(
+            unzoomed_color(cs, UnzoomedShape::Area(area.area_type)),
+            area.polygon.clone(),
+        );
         DrawArea { id: area.id }
     }
 }
@@ -1,8 +1,9 @@
 use crate::helpers::{ColorScheme, ID};
+use crate::render::map::unzoomed_color;
 use crate::render::{DrawCtx, DrawOptions, Renderable, OUTLINE_THICKNESS};
 use ezgui::{Color, GeomBatch, GfxCtx, Line, Text};
 use geom::{Angle, Distance, Line, Polygon, Pt2D};
-use map_model::{Building, BuildingID, Map, LANE_THICKNESS};
+use map_model::{Building, BuildingID, Map, UnzoomedShape, LANE_THICKNESS};
 
 pub struct DrawBuilding {
     pub id: BuildingID,
@@ -26,7 +27,7 @@ impl DrawBuilding {
         let front_path = front_path_line.make_polygons(Distance::meters(1.0));
 
         batch.push(
-            cs.get_def("building", Color::rgb(196, 193, 188)),
+            unzoomed_color(cs, UnzoomedShape::Building),
             bldg.polygon.clone(),
         );
         batch.push(cs.get("sidewalk"), front_path);
@@ -74,7 +74,9 @@ impl DrawIntersection {
             IntersectionType::Construction => {
                 default_geom.push(cs.get("construction hatching"), i.polygon.clone());
             }
-            IntersectionType::TrafficSignal => {}
+            IntersectionType::TrafficSignal
+            | IntersectionType::Roundabout
+            | IntersectionType::Merge => {}
         }
 
         DrawIntersection {
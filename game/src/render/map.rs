@@ -13,8 +13,9 @@ use abstutil::{Cloneable, Timer};
 use ezgui::{Color, Drawable, EventCtx, GeomBatch, GfxCtx, Prerender};
 use geom::{Bounds, Circle, Distance, Duration, FindClosest, Time};
 use map_model::{
-    AreaID, BuildingID, BusStopID, DirectedRoadID, Intersection, IntersectionID, LaneID, Map, Road,
-    RoadID, Traversable, LANE_THICKNESS,
+    unzoomed_intersections, unzoomed_map, unzoomed_roads, AreaID, AreaType, BuildingID, BusStopID,
+    DirectedRoadID, IntersectionID, LaneID, Map, RoadID, Traversable, UnzoomedShape,
+    LANE_THICKNESS,
 };
 use sim::{GetDrawAgents, UnzoomedAgent, VehicleType};
 use std::borrow::Borrow;
@@ -58,22 +59,8 @@ impl DrawMap {
         }
 
         timer.start("generate thick roads");
-        let mut road_refs: Vec<&Road> = map.all_roads().iter().collect();
-        road_refs.sort_by_key(|r| r.get_zorder());
-        let mut all_roads = GeomBatch::new();
-        for r in road_refs {
-            all_roads.push(
-                osm_rank_to_color(cs, r.get_rank()),
-                r.get_thick_polygon().get(timer),
-            );
-            if false {
-                all_roads.push(
-                    cs.get_def("unzoomed outline", Color::BLACK),
-                    roads[r.id.0].get_outline(map),
-                );
-            }
-        }
-        let draw_all_thick_roads = all_roads.upload(ctx);
+        let draw_all_thick_roads =
+            GeomBatch::from(unzoomed_roads(map, &|s| unzoomed_color(cs, s), timer)).upload(ctx);
         timer.stop("generate thick roads");
 
         let almost_lanes =
@@ -103,28 +90,8 @@ impl DrawMap {
         }
 
         timer.start("generate unzoomed intersections");
-        let mut intersection_refs: Vec<&Intersection> = map.all_intersections().iter().collect();
-        intersection_refs.sort_by_key(|i| i.get_zorder(map));
-        let mut all_intersections = GeomBatch::new();
-        for i in intersection_refs {
-            // TODO Would be neat to show closed intersections here, but then edits need to
-            // regenerate this
-            if i.is_stop_sign() {
-                all_intersections.push(osm_rank_to_color(cs, i.get_rank(map)), i.polygon.clone());
-                if false {
-                    all_intersections.push(
-                        cs.get("unzoomed outline"),
-                        intersections[i.id.0].get_outline(map),
-                    );
-                }
-            } else {
-                all_intersections.push(
-                    cs.get_def("unzoomed interesting intersection", Color::BLACK),
-                    i.polygon.clone(),
-                );
-            }
-        }
-        let draw_all_unzoomed_intersections = all_intersections.upload(ctx);
+        let draw_all_unzoomed_intersections =
+            GeomBatch::from(unzoomed_intersections(map, &|s| unzoomed_color(cs, s))).upload(ctx);
         timer.stop("generate unzoomed intersections");
 
         let mut buildings: Vec<DrawBuilding> = Vec::new();
@@ -195,7 +162,7 @@ impl DrawMap {
         timer.stop("upload all areas");
 
         let boundary_polygon = ctx.prerender.upload_borrowed(vec![(
-            cs.get_def("map background", Color::grey(0.87)),
+            unzoomed_color(cs, UnzoomedShape::Background),
             map.get_boundary_polygon(),
         )]);
 
@@ -335,6 +302,29 @@ impl DrawMap {
         }
         results
     }
+
+    // The zoomed-out view of the map, with agents as dots. Nothing here touches the GPU, so the
+    // result can be exported with raster::write_svg or write_png.
+    pub fn zoomed_out_batch(
+        map: &Map,
+        cs: &ColorScheme,
+        agents: Option<(&dyn GetDrawAgents, &AgentColorScheme)>,
+        timer: &mut Timer,
+    ) -> GeomBatch {
+        let mut batch = GeomBatch::from(unzoomed_map(map, &|s| unzoomed_color(cs, s), timer));
+        if let Some((source, acs)) = agents {
+            for agent in source.get_unzoomed_agents(map) {
+                if let Some(color) = acs.color(&agent) {
+                    batch.push(
+                        color,
+                        Circle::new(agent.pos, Distance::meters(5.0)).to_polygon(),
+                    );
+                }
+            }
+        }
+
+        batch
+    }
 }
 
 pub struct AgentCache {
@@ -426,13 +416,33 @@ impl AgentCache {
     }
 }
 
-fn osm_rank_to_color(cs: &ColorScheme, rank: usize) -> Color {
-    if rank >= 16 {
-        cs.get_def("unzoomed highway road", Color::rgb(232, 146, 162))
-    } else if rank >= 6 {
-        cs.get_def("unzoomed arterial road", Color::rgb(255, 199, 62))
-    } else {
-        cs.get_def("unzoomed residential road", Color::WHITE)
+// Everything drawing the zoomed-out map goes through here, so exported images match the screen. The
+// defaults are raster's palette, which headless tools use directly.
+pub fn unzoomed_color(cs: &ColorScheme, shape: UnzoomedShape) -> Color {
+    match shape {
+        UnzoomedShape::Background => {
+            cs.get_def("map background", Color::from(raster::MAP_BACKGROUND))
+        }
+        UnzoomedShape::Area(AreaType::Park) => cs.get_def("grass", Color::from(raster::GRASS)),
+        UnzoomedShape::Area(AreaType::Water) => cs.get_def("water", Color::from(raster::WATER)),
+        UnzoomedShape::Area(AreaType::PedestrianIsland) => Color::from(raster::PEDESTRIAN_ISLAND),
+        UnzoomedShape::HighwayRoad => cs.get_def(
+            "unzoomed highway road",
+            Color::from(raster::UNZOOMED_HIGHWAY_ROAD),
+        ),
+        UnzoomedShape::ArterialRoad => cs.get_def(
+            "unzoomed arterial road",
+            Color::from(raster::UNZOOMED_ARTERIAL_ROAD),
+        ),
+        UnzoomedShape::ResidentialRoad => cs.get_def(
+            "unzoomed residential road",
+            Color::from(raster::UNZOOMED_RESIDENTIAL_ROAD),
+        ),
+        UnzoomedShape::InterestingIntersection => cs.get_def(
+            "unzoomed interesting intersection",
+            Color::from(raster::UNZOOMED_INTERESTING_INTERSECTION),
+        ),
+        UnzoomedShape::Building => cs.get_def("building", Color::from(raster::BUILDING)),
     }
 }
 
@@ -451,29 +461,50 @@ impl InnerAgentColorScheme {
             InnerAgentColorScheme::VehicleTypes => (
                 "vehicle types",
                 vec![
-                    ("car", cs.get_def("unzoomed car", Color::RED.alpha(0.5))),
-                    ("bike", cs.get_def("unzoomed bike", Color::GREEN.alpha(0.5))),
-                    ("bus", cs.get_def("unzoomed bus", Color::BLUE.alpha(0.5))),
+                    (
+                        "car",
+                        cs.get_def("unzoomed car", Color::from(raster::UNZOOMED_CAR)),
+                    ),
+                    (
+                        "bike",
+                        cs.get_def("unzoomed bike", Color::from(raster::UNZOOMED_BIKE)),
+                    ),
+                    (
+                        "bus",
+                        cs.get_def("unzoomed bus", Color::from(raster::UNZOOMED_BUS)),
+                    ),
                     (
                         "pedestrian",
-                        cs.get_def("unzoomed pedestrian", Color::ORANGE.alpha(0.5)),
+                        cs.get_def(
+                            "unzoomed pedestrian",
+                            Color::from(raster::UNZOOMED_PEDESTRIAN),
+                        ),
                     ),
                 ],
             ),
             InnerAgentColorScheme::Delay => (
                 "time spent delayed/blocked",
                 vec![
-                    ("<= 1 minute", Color::BLUE.alpha(0.3)),
-                    ("<= 5 minutes", Color::ORANGE.alpha(0.5)),
-                    ("> 5 minutes", Color::RED.alpha(0.8)),
+                    (
+                        "<= 1 minute",
+                        cs.get_def("agent delay low", Color::BLUE.alpha(0.3)),
+                    ),
+                    (
+                        "<= 5 minutes",
+                        cs.get_def("agent delay medium", Color::ORANGE.alpha(0.5)),
+                    ),
+                    (
+                        "> 5 minutes",
+                        cs.get_def("agent delay high", Color::RED.alpha(0.8)),
+                    ),
                 ],
             ),
             InnerAgentColorScheme::TripTimeSoFar => (
                 "trip time so far",
                 vec![
-                    ("<= 1 minute", Color::BLUE.alpha(0.3)),
-                    ("<= 5 minutes", Color::ORANGE.alpha(0.5)),
-                    ("> 5 minutes", Color::RED.alpha(0.8)),
+                    ("<= 1 minute", cs.get("agent delay low")),
+                    ("<= 5 minutes", cs.get("agent delay medium")),
+                    ("> 5 minutes", cs.get("agent delay high")),
                 ],
             ),
             InnerAgentColorScheme::DistanceCrossedSoFar => (
@@ -1,6 +1,6 @@
 use crate::common::ShowBusRoute;
 use crate::game::{State, Transition};
-use crate::helpers::ID;
+use crate::helpers::{color_for_mode, ID};
 use crate::managed::{Callback, ManagedGUIState, WrappedComposite};
 use crate::sandbox::gameplay::{cmp_count_fewer, cmp_count_more, cmp_duration_shorter};
 use crate::sandbox::SandboxMode;
@@ -9,10 +9,11 @@ use abstutil::prettyprint_usize;
 use abstutil::Counter;
 use ezgui::{
     hotkey, Color, Composite, EventCtx, Histogram, Key, Line, ManagedWidget, Plot, Series, Text,
+    TextSpan,
 };
 use geom::{Duration, Statistic, Time};
 use map_model::BusRouteID;
-use sim::{TripID, TripMode};
+use sim::{TripComparison, TripGrouping, TripID, TripMode};
 use std::collections::BTreeMap;
 
 #[derive(PartialEq, Clone, Copy)]
@@ -21,6 +22,8 @@ pub enum Tab {
     IndividualFinishedTrips(Option<TripMode>),
     ParkingOverhead,
     ExploreBusRoute,
+    TripComparison(TripGrouping),
+    Emissions,
 }
 
 // Oh the dashboards melted, but we still had the radio
@@ -33,6 +36,11 @@ pub fn make(ctx: &mut EventCtx, ui: &UI, tab: Tab) -> Box<dyn State> {
         ),
         (Tab::ParkingOverhead, "Parking overhead analysis"),
         (Tab::ExploreBusRoute, "Explore a bus route"),
+        (
+            Tab::TripComparison(TripGrouping::Mode),
+            "Winners and losers",
+        ),
+        (Tab::Emissions, "Emissions"),
     ];
 
     let mut tabs = tab_data
@@ -53,6 +61,8 @@ pub fn make(ctx: &mut EventCtx, ui: &UI, tab: Tab) -> Box<dyn State> {
         Tab::IndividualFinishedTrips(Some(m)) => pick_finished_trips(m, ctx, ui),
         Tab::ParkingOverhead => (parking_overhead(ctx, ui), Vec::new()),
         Tab::ExploreBusRoute => pick_bus_route(ctx, ui),
+        Tab::TripComparison(grouping) => trip_comparison(grouping, ctx, ui),
+        Tab::Emissions => (emissions(ctx, ui), Vec::new()),
     };
 
     let mut c = WrappedComposite::new(
@@ -314,19 +324,7 @@ fn pick_finished_trips(
     for (_, id, _, dt) in filtered {
         let label = format!("{} taking {}", id, dt);
         buttons.push(WrappedComposite::text_button(ctx, &label, None));
-        let trip = *id;
-        cbs.push((
-            label,
-            Box::new(move |_, _| {
-                Some(Transition::PopWithData(Box::new(move |state, ui, ctx| {
-                    state
-                        .downcast_mut::<SandboxMode>()
-                        .unwrap()
-                        .common
-                        .launch_info_panel(ID::Trip(trip), ctx, ui);
-                })))
-            }),
-        ));
+        cbs.push((label, inspect_trip(*id)));
     }
 
     // TODO Indicate the current mode
@@ -342,6 +340,118 @@ fn pick_finished_trips(
     )
 }
 
+fn trip_comparison(
+    grouping: TripGrouping,
+    ctx: &EventCtx,
+    ui: &UI,
+) -> (ManagedWidget, Vec<(String, Callback)>) {
+    if !ui.has_prebaked() {
+        return (
+            ManagedWidget::draw_text(ctx, Text::from(Line("No baseline to compare against"))),
+            Vec::new(),
+        );
+    }
+
+    let mut col = Vec::new();
+    let mut cbs: Vec<(String, Callback)> = Vec::new();
+
+    let mut grouping_buttons = Vec::new();
+    for g in TripGrouping::all() {
+        let label = format!("By {}", g.describe());
+        if g == grouping {
+            grouping_buttons.push(ManagedWidget::draw_text(ctx, Text::from(Line(label))).margin(5));
+        } else {
+            grouping_buttons.push(WrappedComposite::text_button(ctx, &label, None).margin(5));
+            cbs.push((
+                label,
+                Box::new(move |ctx, ui| {
+                    Some(Transition::Replace(make(ctx, ui, Tab::TripComparison(g))))
+                }),
+            ));
+        }
+    }
+    col.push(ManagedWidget::row(grouping_buttons));
+
+    let cmp = TripComparison::new(
+        ui.primary.sim.time(),
+        &ui.primary.sim,
+        ui.prebaked(),
+        &ui.primary.map,
+    );
+    if cmp.deltas.is_empty() {
+        col.push(ManagedWidget::draw_text(
+            ctx,
+            Text::from(Line("No trips have finished in both runs yet")),
+        ));
+        return (ManagedWidget::col(col), cbs);
+    }
+
+    for group in cmp.group_by(grouping) {
+        let mut txt = Text::new();
+        txt.add_appended(vec![
+            Line(format!(
+                "{}: {} trips ({} faster, {} slower), on average ",
+                group.key,
+                prettyprint_usize(group.trips.len()),
+                prettyprint_usize(group.num_faster),
+                prettyprint_usize(group.num_slower)
+            )),
+            savings(group.mean_savings()),
+        ]);
+        let mut row = vec![ManagedWidget::draw_text(ctx, txt).margin(5)];
+        if let Some((id, _)) = group.biggest_winner {
+            let label = format!("biggest winner in {}", group.key);
+            row.push(WrappedComposite::text_button(ctx, &label, None).margin(5));
+            cbs.push((label, inspect_trip(id)));
+        }
+        if let Some((id, _)) = group.biggest_loser {
+            let label = format!("biggest loser in {}", group.key);
+            row.push(WrappedComposite::text_button(ctx, &label, None).margin(5));
+            cbs.push((label, inspect_trip(id)));
+        }
+        col.push(ManagedWidget::row(row));
+    }
+
+    for (title, list) in vec![
+        ("Biggest winners", cmp.winners(10)),
+        ("Biggest losers", cmp.losers(10)),
+    ] {
+        col.push(ManagedWidget::draw_text(ctx, Text::from(Line(title))));
+        let mut buttons = Vec::new();
+        for d in list {
+            let label = format!("{}: {} instead of {}", d.id, d.now, d.baseline);
+            buttons.push(WrappedComposite::text_button(ctx, &label, None));
+            cbs.push((label, inspect_trip(d.id)));
+        }
+        col.push(ManagedWidget::row(buttons).flex_wrap(ctx, 80));
+    }
+
+    (ManagedWidget::col(col), cbs)
+}
+
+// Positive means faster
+fn savings(dt: Duration) -> TextSpan {
+    if dt > Duration::ZERO {
+        Line(format!("{} faster", dt)).fg(Color::GREEN)
+    } else if dt < Duration::ZERO {
+        Line(format!("{} slower", Duration::ZERO - dt)).fg(Color::RED)
+    } else {
+        Line("no change")
+    }
+}
+
+fn inspect_trip(trip: TripID) -> Callback {
+    Box::new(move |_, _| {
+        Some(Transition::PopWithData(Box::new(move |state, ui, ctx| {
+            state
+                .downcast_mut::<SandboxMode>()
+                .unwrap()
+                .common
+                .launch_info_panel(ID::Trip(trip), ctx, ui);
+        })))
+    })
+}
+
 fn parking_overhead(ctx: &EventCtx, ui: &UI) -> ManagedWidget {
     let mut txt = Text::new();
     for line in ui.primary.sim.get_analytics().analyze_parking_phases() {
@@ -350,6 +460,23 @@ fn parking_overhead(ctx: &EventCtx, ui: &UI) -> ManagedWidget {
     ManagedWidget::draw_text(ctx, txt)
 }
 
+fn emissions(ctx: &EventCtx, ui: &UI) -> ManagedWidget {
+    let baseline = if ui.has_prebaked() {
+        Some(&ui.prebaked().emissions)
+    } else {
+        None
+    };
+    let mut txt = Text::new();
+    for line in ui.primary.sim.get_analytics().emissions.describe(
+        ui.primary.sim.time(),
+        baseline,
+        &ui.primary.map,
+    ) {
+        txt.add(Line(line));
+    }
+    ManagedWidget::draw_text(ctx, txt)
+}
+
 fn pick_bus_route(ctx: &EventCtx, ui: &UI) -> (ManagedWidget, Vec<(String, Callback)>) {
     let mut buttons = Vec::new();
     let mut cbs: Vec<(String, Callback)> = Vec::new();
@@ -379,13 +506,3 @@ fn pick_bus_route(ctx: &EventCtx, ui: &UI) -> (ManagedWidget, Vec<(String, Callb
 
     (ManagedWidget::row(buttons).flex_wrap(ctx, 80), cbs)
 }
-
-// TODO Refactor
-fn color_for_mode(m: TripMode, ui: &UI) -> Color {
-    match m {
-        TripMode::Walk => ui.cs.get("unzoomed pedestrian"),
-        TripMode::Bike => ui.cs.get("unzoomed bike"),
-        TripMode::Transit => ui.cs.get("unzoomed bus"),
-        TripMode::Drive => ui.cs.get("unzoomed car"),
-    }
-}
@@ -9,7 +9,7 @@ use crate::ui::UI;
 use ezgui::{hotkey, layout, EventCtx, GfxCtx, Key, ModalMenu};
 use geom::{Duration, Statistic, Time};
 use map_model::{IntersectionID, Map};
-use sim::{BorderSpawnOverTime, OriginDestination, Scenario, TripMode};
+use sim::{BorderSpawnOverTime, DepartureDistribution, OriginDestination, Scenario, TripMode};
 
 pub struct FixTrafficSignals {
     time: Time,
@@ -262,6 +262,7 @@ fn spawn(
         percent_use_transit: 0.0,
         start_time: Time::START_OF_DAY,
         stop_time: Time::START_OF_DAY + Duration::minutes(5),
+        departure: DepartureDistribution::Uniform,
         start_from_border: map.get_i(from).some_outgoing_road(map),
         goal: OriginDestination::EndOfRoad(map.get_i(to).some_incoming_road(map)),
     });
@@ -14,7 +14,8 @@ use rand::seq::SliceRandom;
 use rand::Rng;
 use rand_xorshift::XorShiftRng;
 use sim::{
-    BorderSpawnOverTime, DrivingGoal, OriginDestination, Scenario, SidewalkSpot, Sim, TripSpec,
+    BorderSpawnOverTime, DepartureDistribution, DrivingGoal, OriginDestination, Scenario,
+    SidewalkSpot, Sim, TripSpec,
 };
 
 const SMALL_DT: Duration = Duration::const_seconds(0.1);
@@ -639,6 +640,7 @@ fn create_swarm(ui: &mut UI, from: LaneID, to: LaneID, count: usize, duration: D
         num_bikes: 0,
         start_time: ui.primary.sim.time() + SMALL_DT,
         stop_time: ui.primary.sim.time() + SMALL_DT + duration,
+        departure: DepartureDistribution::Uniform,
         start_from_border: ui
             .primary
             .map
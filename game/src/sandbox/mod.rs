@@ -18,7 +18,7 @@ use ezgui::{
 pub use gameplay::spawner::spawn_agents_around;
 pub use gameplay::GameplayMode;
 use geom::Time;
-use map_model::MapEdits;
+use map_model::{MapEdits, PathConstraints};
 use sim::TripMode;
 pub use speed::{SpeedControls, TimePanel};
 
@@ -66,7 +66,7 @@ impl State for SandboxMode {
             }
         }
 
-        if ui.opts.dev && ctx.input.new_was_pressed(lctrl(Key::D).unwrap()) {
+        if ui.opts.dev && ctx.action_pressed(lctrl(Key::D), "debug mode") {
             return Transition::Push(Box::new(DebugMode::new(ctx)));
         }
 
@@ -94,6 +94,24 @@ impl State for SandboxMode {
                     Some(Transition::Pop)
                 })));
             }
+            if ui
+                .per_obj
+                .action(ctx, Key::M, "show what's reachable from here")
+            {
+                return Transition::Push(WizardState::new(Box::new(move |wiz, ctx, ui| {
+                    let (_, constraints) =
+                        wiz.wrap(ctx).choose("Reachable by what mode?", || {
+                            vec![
+                                Choice::new("walking", PathConstraints::Pedestrian),
+                                Choice::new("biking", PathConstraints::Bike),
+                                Choice::new("transit", PathConstraints::Bus),
+                                Choice::new("driving", PathConstraints::Car),
+                            ]
+                        })?;
+                    ui.overlay = Overlays::isochrone(b, constraints, ctx, ui);
+                    Some(Transition::Pop)
+                })));
+            }
         }
         if let Some(ID::Intersection(i)) = ui.primary.current_selection {
             if ui.primary.map.get_i(i).is_traffic_signal()
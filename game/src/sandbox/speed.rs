@@ -239,8 +239,7 @@ impl SpeedControls {
             None => {}
         }
 
-        // TODO How to communicate these keys?
-        if ctx.input.new_was_pressed(hotkey(Key::LeftBracket).unwrap()) {
+        if ctx.action_pressed(hotkey(Key::LeftBracket), "slow down the simulation") {
             match self.setting {
                 SpeedSetting::Realtime => self.pause(ctx),
                 SpeedSetting::Faster => {
@@ -253,10 +252,7 @@ impl SpeedControls {
                 }
             }
         }
-        if ctx
-            .input
-            .new_was_pressed(hotkey(Key::RightBracket).unwrap())
-        {
+        if ctx.action_pressed(hotkey(Key::RightBracket), "speed up the simulation") {
             match self.setting {
                 SpeedSetting::Realtime => {
                     if self.paused {
@@ -337,7 +333,7 @@ pub struct TimeWarpScreen {
 
 impl State for TimeWarpScreen {
     fn event(&mut self, ctx: &mut EventCtx, ui: &mut UI) -> Transition {
-        if ctx.input.new_was_pressed(hotkey(Key::Escape).unwrap()) {
+        if ctx.action_pressed(hotkey(Key::Escape), "stop time warp") {
             return Transition::Pop;
         }
         if ctx.input.nonblocking_is_update_event() {
@@ -14,7 +14,10 @@ use ezgui::{
 };
 use geom::{Distance, Duration, PolyLine, Polygon, Pt2D, Statistic, Time};
 use map_model::{BuildingID, IntersectionID, RoadID};
-use sim::{AgentID, BorderSpawnOverTime, CarID, OriginDestination, Scenario, VehicleType};
+use sim::{
+    AgentID, BorderSpawnOverTime, CarID, DepartureDistribution, OriginDestination, Scenario,
+    VehicleType,
+};
 
 pub struct TutorialMode {
     state: TutorialState,
@@ -286,7 +289,10 @@ impl State for TutorialMode {
                         return Transition::Push(msg(
                             "Not quite",
                             vec![
-                                format!("This lane has {:.0}% spots free", percent * 100.0),
+                                ctx.tr(
+                                    "This lane has {percent}% spots free",
+                                    &[("percent", format!("{:.0}", percent * 100.0))],
+                                ),
                                 "Try using the 'parking availability' layer from the minimap \
                                  controls"
                                     .to_string(),
@@ -329,9 +335,10 @@ impl State for TutorialMode {
                             "All trips completed",
                             vec![
                                 "Your changes made things worse!".to_string(),
-                                format!(
-                                    "The slowest trip originally took {}, but now it took {}",
-                                    baseline, max
+                                ctx.tr(
+                                    "The slowest trip originally took {before}, but now it took \
+                                     {after}",
+                                    &[("before", baseline.to_string()), ("after", max.to_string())],
                                 ),
                                 "".to_string(),
                                 "Try again!".to_string(),
@@ -344,9 +351,10 @@ impl State for TutorialMode {
                             "All trips completed",
                             vec![
                                 "Nice, you helped things a bit!".to_string(),
-                                format!(
-                                    "The slowest trip originally took {}, but now it took {}",
-                                    baseline, max
+                                ctx.tr(
+                                    "The slowest trip originally took {before}, but now it took \
+                                     {after}",
+                                    &[("before", baseline.to_string()), ("after", max.to_string())],
                                 ),
                                 "".to_string(),
                                 "See if you can do a little better though.".to_string(),
@@ -355,9 +363,10 @@ impl State for TutorialMode {
                     }
                     return Transition::Push(msg(
                         "All trips completed",
-                        vec![format!(
-                            "Awesome! The slowest trip originally took {}, but now it only took {}",
-                            baseline, max
+                        vec![ctx.tr(
+                            "Awesome! The slowest trip originally took {before}, but now it only \
+                             took {after}",
+                            &[("before", baseline.to_string()), ("after", max.to_string())],
                         )],
                     ));
                 }
@@ -558,6 +567,7 @@ fn start_bike_lane_scenario(ui: &mut UI) {
         percent_use_transit: 0.0,
         start_time: Time::START_OF_DAY,
         stop_time: Time::START_OF_DAY + Duration::seconds(10.0),
+        departure: DepartureDistribution::Uniform,
         start_from_border: RoadID(303).backwards(),
         goal: OriginDestination::GotoBldg(BuildingID(3)),
     });
@@ -98,6 +98,31 @@ impl UI {
         *self = UI::new(flags, self.opts.clone(), ctx, false);
     }
 
+    // Redraws everything in the new colors, without resetting the simulation.
+    pub fn change_color_scheme(&mut self, ctx: &mut EventCtx, color_scheme: Option<String>) {
+        self.opts.color_scheme = color_scheme;
+        self.cs = ColorScheme::load(self.opts.color_scheme.clone());
+
+        // Keep the same categories hidden
+        let mut agent_cs = AgentColorScheme::new(self.agent_cs.acs, &self.cs);
+        for (row, old) in agent_cs.rows.iter_mut().zip(self.agent_cs.rows.iter()) {
+            row.2 = old.2;
+        }
+        self.agent_cs = agent_cs;
+
+        let cs = &self.cs;
+        let primary = &mut self.primary;
+        let secondary = &mut self.secondary;
+        ctx.loading_screen("change color scheme", |ctx, mut timer| {
+            primary.draw_map =
+                DrawMap::new(&primary.map, &primary.current_flags, cs, ctx, &mut timer);
+            if let Some(s) = secondary {
+                s.draw_map = DrawMap::new(&s.map, &s.current_flags, cs, ctx, &mut timer);
+            }
+        });
+        Overlays::recolor(ctx, self);
+    }
+
     pub fn draw_ctx(&self) -> DrawCtx<'_> {
         DrawCtx {
             cs: &self.cs,
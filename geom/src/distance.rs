@@ -1,4 +1,4 @@
-use crate::{trim_f64, Duration, Speed};
+use crate::{trim_f64, Duration, Speed, UnitSystem};
 use serde_derive::{Deserialize, Serialize};
 use std::{cmp, f64, fmt, ops};
 
@@ -63,7 +63,17 @@ impl Distance {
 impl fmt::Display for Distance {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         // TODO commas every third place
-        write!(f, "{}m", self.0)
+        match UnitSystem::current() {
+            UnitSystem::Metric => write!(f, "{}m", self.0),
+            UnitSystem::Imperial => {
+                let feet = self.0 * 3.28084;
+                if feet.abs() >= 5280.0 {
+                    write!(f, "{:.1}mi", feet / 5280.0)
+                } else {
+                    write!(f, "{:.1}ft", feet)
+                }
+            }
+        }
     }
 }
 
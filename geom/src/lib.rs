@@ -13,6 +13,7 @@ mod ring;
 mod speed;
 mod stats;
 mod time;
+mod units;
 
 pub use crate::angle::Angle;
 pub use crate::bounds::{Bounds, GPSBounds};
@@ -29,6 +30,7 @@ pub use crate::ring::Ring;
 pub use crate::speed::Speed;
 pub use crate::stats::{DurationHistogram, PercentageHistogram, Statistic};
 pub use crate::time::Time;
+pub use crate::units::UnitSystem;
 
 // About 0.4 inches... which is quite tiny on the scale of things. :)
 pub const EPSILON_DIST: Distance = Distance::const_meters(0.01);
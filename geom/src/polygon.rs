@@ -1,4 +1,5 @@
 use crate::{Angle, Bounds, Distance, HashablePt2D, Pt2D, Ring};
+use geo::algorithm::convexhull::ConvexHull;
 use geo_booleanop::boolean::BooleanOp;
 use geo_offset::Offset;
 use serde_derive::{Deserialize, Serialize};
@@ -275,6 +276,31 @@ impl Polygon {
         from_multi(to_geo(self.points()).intersection(&to_geo(other.points())))
     }
 
+    // None if there aren't enough points to enclose anything
+    pub fn convex_hull(pts: &Vec<Pt2D>) -> Option<Polygon> {
+        if pts.len() < 3 {
+            return None;
+        }
+        let hull = geo::MultiPoint(
+            pts.iter()
+                .map(|pt| geo::Point::new(pt.x(), pt.y()))
+                .collect(),
+        )
+        .convex_hull();
+        let ring: Vec<Pt2D> = hull
+            .into_inner()
+            .0
+            .into_points()
+            .into_iter()
+            .map(|pt| Pt2D::new(pt.x(), pt.y()))
+            .collect();
+        // The ring is closed, so a triangle has 4 points
+        if ring.len() < 4 {
+            return None;
+        }
+        Some(Polygon::new(&ring))
+    }
+
     pub fn polylabel(&self) -> Pt2D {
         let pt = polylabel::polylabel(&to_geo(&self.points()), &1.0);
         Pt2D::new(pt.x(), pt.y())
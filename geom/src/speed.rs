@@ -1,4 +1,4 @@
-use crate::{trim_f64, Distance, Duration, EPSILON_DIST};
+use crate::{trim_f64, Distance, Duration, UnitSystem, EPSILON_DIST};
 use serde_derive::{Deserialize, Serialize};
 use std::{f64, fmt, ops};
 
@@ -107,6 +107,9 @@ impl ops::Mul<Duration> for Speed {
 
 impl fmt::Display for Speed {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{} mph", (self.0 * 2.23694).round())
+        match UnitSystem::current() {
+            UnitSystem::Metric => write!(f, "{} km/h", (self.0 * 3.6).round()),
+            UnitSystem::Imperial => write!(f, "{} mph", (self.0 * 2.23694).round()),
+        }
     }
 }
@@ -0,0 +1,29 @@
+use serde_derive::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// How Distance and Speed are displayed. Display can't take any arguments, so this is global for the
+// whole process; set it once at startup or when the player changes it. Tools that never set it
+// print imperial.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum UnitSystem {
+    Metric,
+    Imperial,
+}
+
+static USE_METRIC: AtomicBool = AtomicBool::new(false);
+
+impl UnitSystem {
+    pub fn current() -> UnitSystem {
+        if USE_METRIC.load(Ordering::Relaxed) {
+            UnitSystem::Metric
+        } else {
+            UnitSystem::Imperial
+        }
+    }
+
+    pub fn set(self) {
+        USE_METRIC.store(self == UnitSystem::Metric, Ordering::Relaxed);
+    }
+}
+
+impl abstutil::Cloneable for UnitSystem {}
@@ -1,6 +1,11 @@
+mod timelapse;
+
 use abstutil::{CmdArgs, Timer};
-use geom::Time;
-use sim::{GetDrawAgents, Scenario, SimFlags};
+use geom::{Duration, Time};
+use map_model::{Map, MapEdits};
+use sim::{
+    CalibrationReport, GetDrawAgents, ObservedCount, ReplicationSummary, Scenario, SimFlags,
+};
 
 fn main() {
     let mut args = CmdArgs::new();
@@ -10,8 +15,47 @@ fn main() {
     let enable_profiler = args.enabled("--enable_profiler");
     // Every 0.1s, pretend to draw everything to make sure there are no bugs.
     let paranoia = args.enabled("--paranoia");
+    // A JSON list of ObservedCounts to compare against at the end
+    let calibrate = args.optional("--calibrate");
+    // When calibrating a scenario, also write a copy with demand scaled to better match the counts
+    let scale_scenario = args.enabled("--scale_scenario");
+    // Run a scenario this many times with different RNG seeds, starting from --rng_seed, and
+    // summarize the results
+    let replications = args.optional_parse("--replications", |s| s.parse::<u8>());
+    // Do all of the replications at once
+    let parallel = args.enabled("--parallel");
+    // Map edits to apply for replications
+    let edits_name = args.optional("--edits");
+    // Instead of running until done, write a PNG of the map and agents every --timelapse_every
+    // (1 minute by default) between --timelapse_from (the start of the sim by default) and
+    // --timelapse_to into this directory. --timelapse_bounds=x1,y1,x2,y2 picks the part of the
+    // map to show.
+    let timelapse_dir = args.optional("--timelapse");
+    let timelapse_from = args.optional_parse("--timelapse_from", Time::parse);
+    let timelapse_to = args.optional_parse("--timelapse_to", Time::parse);
+    let timelapse_every = args
+        .optional_parse("--timelapse_every", Duration::parse)
+        .unwrap_or(Duration::minutes(1));
+    let timelapse_bounds = args.optional_parse("--timelapse_bounds", timelapse::parse_bounds);
+    let timelapse_width = args
+        .optional_parse("--timelapse_width", |s| s.parse::<u32>())
+        .unwrap_or(1920);
     args.done();
 
+    if scale_scenario {
+        if calibrate.is_none() {
+            panic!("--scale_scenario needs --calibrate");
+        }
+        if !sim_flags.load.starts_with("../data/system/scenarios/") {
+            panic!("--scale_scenario needs a scenario, not {}", sim_flags.load);
+        }
+    }
+
+    if let Some(n) = replications {
+        run_replications(&sim_flags, n, parallel, edits_name);
+        return;
+    }
+
     let mut timer = Timer::new("setup headless");
     let (map, mut sim, mut rng) = sim_flags.load(&mut timer);
 
@@ -28,6 +72,21 @@ fn main() {
     }
     timer.done();
 
+    if let Some(dir) = timelapse_dir {
+        let mut timer = Timer::new("render time-lapse");
+        timelapse::Timelapse {
+            dir,
+            from: timelapse_from.unwrap_or_else(|| sim.time()),
+            to: timelapse_to.expect("--timelapse needs --timelapse_to"),
+            every: timelapse_every,
+            bounds: timelapse_bounds,
+            width_px: timelapse_width,
+        }
+        .run(&map, &mut sim, &mut timer);
+        timer.done();
+        return;
+    }
+
     if enable_profiler {
         #[cfg(feature = "profiler")]
         {
@@ -61,6 +120,22 @@ fn main() {
     );
     timer.done();
     println!("Done at {}", sim.time());
+
+    if let Some(path) = calibrate {
+        let mut timer = Timer::new("calibrate against observed counts");
+        let counts: Vec<ObservedCount> = abstutil::read_json(path, &mut timer);
+        let report = CalibrationReport::new(counts, sim.get_analytics(), &map, &mut timer);
+        for line in report.describe() {
+            println!("{}", line);
+        }
+        if scale_scenario {
+            let mut scenario: Scenario = abstutil::read_binary(sim_flags.load.clone(), &mut timer);
+            report.scale_demand(&mut scenario);
+            scenario.scenario_name = format!("{}_calibrated", scenario.scenario_name);
+            scenario.save();
+            println!("Saved {}", scenario.scenario_name);
+        }
+    }
     if enable_profiler && save_at.is_none() {
         #[cfg(feature = "profiler")]
         {
@@ -68,3 +143,38 @@ fn main() {
         }
     }
 }
+
+fn run_replications(sim_flags: &SimFlags, n: u8, parallel: bool, edits_name: Option<String>) {
+    if !sim_flags.load.starts_with("../data/system/scenarios/") {
+        panic!("--replications needs a scenario, not {}", sim_flags.load);
+    }
+    if n == 0 {
+        panic!("--replications needs at least 1 run");
+    }
+    let mut timer = Timer::new("run replications");
+    let scenario: Scenario = abstutil::read_binary(sim_flags.load.clone(), &mut timer);
+    let mut map = Map::new(abstutil::path_map(&scenario.map_name), false, &mut timer);
+    if let Some(edits) = edits_name {
+        map.apply_edits(
+            MapEdits::load(map.get_name(), &edits, &mut timer),
+            &mut timer,
+        );
+        map.mark_edits_fresh();
+        map.recalculate_pathfinding_after_edits(&mut timer);
+    }
+
+    let first = sim_flags.rng_seed.unwrap_or(0);
+    let seeds = (0..n).map(|i| first.wrapping_add(i)).collect();
+    let summary = ReplicationSummary::run(
+        &map,
+        &scenario,
+        &sim_flags.opts,
+        seeds,
+        parallel,
+        &mut timer,
+    );
+    timer.done();
+    for line in summary.describe() {
+        println!("{}", line);
+    }
+}
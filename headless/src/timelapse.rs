@@ -0,0 +1,148 @@
+use abstutil::Timer;
+use geom::{Bounds, Circle, Distance, Duration, Polygon, Pt2D, Time};
+use map_model::Map;
+use raster::Rgba;
+use sim::{GetDrawAgents, Sim, VehicleType};
+
+// Steps the sim at a fixed interval and writes a PNG of the map and agents for each step. Doesn't
+// need a GPU, so the same inputs always produce the same frames.
+pub struct Timelapse {
+    // Frames go in this directory, named by their index
+    pub dir: String,
+    pub from: Time,
+    pub to: Time,
+    pub every: Duration,
+    // In map-space. Defaults to the whole map.
+    pub bounds: Option<Bounds>,
+    pub width_px: u32,
+}
+
+impl Timelapse {
+    pub fn run(&self, map: &Map, sim: &mut Sim, timer: &mut Timer) {
+        if sim.time() > self.from {
+            panic!(
+                "Time-lapse should start at {}, but the sim is already at {}",
+                self.from,
+                sim.time()
+            );
+        }
+        if self.to < self.from {
+            panic!(
+                "Time-lapse ends at {}, before it starts at {}",
+                self.to, self.from
+            );
+        }
+        if self.every <= Duration::ZERO {
+            panic!("Time-lapse needs a positive interval, not {}", self.every);
+        }
+        let dt = self.from - sim.time();
+        if dt > Duration::ZERO {
+            sim.timed_step(map, dt, timer);
+        }
+
+        let bounds = self
+            .bounds
+            .clone()
+            .unwrap_or_else(|| map.get_bounds().clone());
+        let background = raster::batch_to_png(
+            map_model::unzoomed_map(map, &raster::unzoomed_color, timer),
+            &bounds,
+            Rgba::WHITE,
+            self.width_px,
+        );
+        // Agents are drawn in detail only when zoomed in enough to see them.
+        let px_per_meter = f64::from(self.width_px) / bounds.width();
+
+        let num_frames = ((self.to - self.from) / self.every).floor() as usize + 1;
+        if let Err(err) = std::fs::create_dir_all(&self.dir) {
+            panic!("Couldn't create {}: {}", self.dir, err);
+        }
+        timer.start_iter("render time-lapse frames", num_frames);
+        for frame in 0..num_frames {
+            timer.next();
+            if frame > 0 {
+                sim.step(map, self.every);
+            }
+            let mut img = background.clone();
+            raster::draw_batch_on_png(
+                &mut img,
+                draw_agents(sim, map, &bounds, px_per_meter),
+                &bounds,
+            );
+            let path = format!("{}/frame_{:05}.png", self.dir, frame);
+            if let Err(err) = img.save(&path) {
+                panic!("Couldn't write {}: {}", path, err);
+            }
+        }
+        println!(
+            "Wrote {} frames to {}. To make a video: ffmpeg -framerate 10 -i {}/frame_%05d.png \
+             -pix_fmt yuv420p timelapse.mp4",
+            num_frames, self.dir, self.dir
+        );
+    }
+}
+
+// Parses "x1,y1,x2,y2" in map-space
+pub fn parse_bounds(raw: &str) -> Result<Bounds, abstutil::Error> {
+    let parts: Vec<&str> = raw.split(',').collect();
+    if parts.len() != 4 {
+        return Err(abstutil::Error::new(format!(
+            "Bounds should be x1,y1,x2,y2, not {}",
+            raw
+        )));
+    }
+    let mut nums = Vec::new();
+    for p in parts {
+        match p.trim().parse::<f64>() {
+            Ok(x) => nums.push(x),
+            Err(err) => {
+                return Err(abstutil::Error::new(format!("Bad bounds {}: {}", raw, err)));
+            }
+        }
+    }
+    Ok(Bounds::from(&vec![
+        Pt2D::new(nums[0], nums[1]),
+        Pt2D::new(nums[2], nums[3]),
+    ]))
+}
+
+fn draw_agents(sim: &Sim, map: &Map, bounds: &Bounds, px_per_meter: f64) -> Vec<(Rgba, Polygon)> {
+    let mut batch = Vec::new();
+    let color = |vt: Option<VehicleType>| match vt {
+        Some(VehicleType::Car) => raster::UNZOOMED_CAR,
+        Some(VehicleType::Bike) => raster::UNZOOMED_BIKE,
+        Some(VehicleType::Bus) => raster::UNZOOMED_BUS,
+        None => raster::UNZOOMED_PEDESTRIAN,
+    };
+
+    if px_per_meter >= 1.0 {
+        for car in sim.get_all_draw_cars(map) {
+            if car.body.points().iter().any(|pt| bounds.contains(*pt)) {
+                batch.push((
+                    color(Some(car.id.1)),
+                    car.body.make_polygons(Distance::meters(2.0)),
+                ));
+            }
+        }
+        for ped in sim.get_all_draw_peds(map) {
+            if bounds.contains(ped.pos) {
+                batch.push((
+                    color(None),
+                    Circle::new(ped.pos, Distance::meters(0.75)).to_polygon(),
+                ));
+            }
+        }
+    } else {
+        // Keep the dots a few pixels wide no matter how far out we are
+        let radius = Distance::meters(3.0 / px_per_meter);
+        for agent in sim.get_unzoomed_agents(map) {
+            if bounds.contains(agent.pos) {
+                batch.push((
+                    color(agent.vehicle_type),
+                    Circle::new(agent.pos, radius).to_polygon(),
+                ));
+            }
+        }
+    }
+    batch
+}
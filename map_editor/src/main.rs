@@ -7,9 +7,9 @@ use ezgui::{
     hotkey, Canvas, Choice, Color, Drawable, EventCtx, EventLoopMode, GeomBatch, GfxCtx, Key, Line,
     ModalMenu, Text, Wizard, GUI,
 };
-use geom::{Distance, Line, Polygon, Pt2D};
+use geom::{Circle, Distance, Line, Polygon, Pt2D};
 use map_model::raw::{OriginalBuilding, OriginalIntersection, OriginalRoad, RestrictionType};
-use map_model::{osm, LANE_THICKNESS};
+use map_model::{osm, MapIssue, Severity, ValidationReport, LANE_THICKNESS};
 use model::{Model, ID};
 use std::collections::HashSet;
 
@@ -38,6 +38,9 @@ enum State {
     // bool is show_tooltip
     PreviewIntersection(Drawable, Vec<(Text, Pt2D)>, bool),
     EnteringWarp(Wizard),
+    // Index of the current issue
+    ReviewingIssues(Vec<MapIssue>, usize),
+    ShowingMessage(String, Wizard),
     StampingRoads(String, String, String, String),
 }
 
@@ -87,6 +90,7 @@ impl UI {
                     (hotkey(Key::G), "preview all intersections"),
                     (None, "find overlapping intersections"),
                     (hotkey(Key::Z), "find short roads"),
+                    (hotkey(Key::V), "review map validation report"),
                 ],
                 ctx,
             ),
@@ -310,6 +314,16 @@ impl GUI for UI {
                         {
                             let (draw, labels) = preview_all_intersections(&self.model, ctx);
                             self.state = State::PreviewIntersection(draw, labels, false);
+                        } else if self.menu.action("review map validation report") {
+                            match load_validation_issues(&self.model) {
+                                Ok(issues) => {
+                                    ctx.canvas.center_on_map_pt(issues[0].pt);
+                                    self.state = State::ReviewingIssues(issues, 0);
+                                }
+                                Err(msg) => {
+                                    self.state = State::ShowingMessage(msg, Wizard::new());
+                                }
+                            }
                         } else if self.menu.action("find overlapping intersections") {
                             let (draw, labels) = find_overlapping_intersections(&self.model, ctx);
                             self.state = State::PreviewIntersection(draw, labels, false);
@@ -508,6 +522,28 @@ impl GUI for UI {
                     self.model.world.handle_mouseover(ctx);
                 }
             }
+            State::ReviewingIssues(ref issues, ref mut idx) => {
+                if *idx + 1 < issues.len() && ctx.input.key_pressed(Key::N, "next issue") {
+                    *idx += 1;
+                    ctx.canvas.center_on_map_pt(issues[*idx].pt);
+                } else if *idx > 0 && ctx.input.key_pressed(Key::B, "previous issue") {
+                    *idx -= 1;
+                    ctx.canvas.center_on_map_pt(issues[*idx].pt);
+                } else if ctx.input.key_pressed(Key::Enter, "stop reviewing issues") {
+                    self.state = State::viewing();
+                    self.model.world.handle_mouseover(ctx);
+                }
+            }
+            State::ShowingMessage(ref msg, ref mut wizard) => {
+                if wizard
+                    .wrap(ctx)
+                    .acknowledge("Map validation report", || vec![msg.clone()])
+                    .is_some()
+                    || wizard.aborted()
+                {
+                    self.state = State::viewing();
+                }
+            }
             State::EnteringWarp(ref mut wizard) => {
                 if let Some(line) = wizard.wrap(ctx).input_string("Warp to what?") {
                     let mut ok = false;
@@ -561,6 +597,21 @@ impl GUI for UI {
         self.sidebar = Text::new().with_bg();
         self.sidebar.override_width = Some(0.3 * ctx.canvas.window_width);
         self.sidebar.override_height = Some(ctx.canvas.window_height);
+        if let State::ReviewingIssues(ref issues, idx) = self.state {
+            let issue = &issues[idx];
+            self.sidebar.add_highlighted(
+                Line(format!(
+                    "Issue {}/{}: {:?}",
+                    idx + 1,
+                    issues.len(),
+                    issue.severity
+                )),
+                Color::BLUE,
+            );
+            self.sidebar.add(Line(format!("{:?}", issue.object)));
+            self.sidebar.add(Line(&issue.description));
+            self.sidebar.add(Line(""));
+        }
         if let Some(id) = self.model.world.get_selection() {
             self.model.populate_obj_info(id, &mut self.sidebar);
         } else {
@@ -608,7 +659,8 @@ impl GUI for UI {
             State::EditingLanes(_, ref wizard)
             | State::EditingRoadAttribs(_, ref wizard)
             | State::SavingModel(ref wizard)
-            | State::EnteringWarp(ref wizard) => {
+            | State::EnteringWarp(ref wizard)
+            | State::ShowingMessage(_, ref wizard) => {
                 wizard.draw(g);
             }
             State::Viewing { ref short_roads } => {
@@ -618,6 +670,17 @@ impl GUI for UI {
                     }
                 }
             }
+            State::ReviewingIssues(ref issues, idx) => {
+                let issue = &issues[idx];
+                let color = match issue.severity {
+                    Severity::Error => Color::RED,
+                    Severity::Warning => Color::YELLOW,
+                };
+                g.draw_polygon(
+                    color.alpha(0.5),
+                    &Circle::new(issue.pt, Distance::meters(10.0)).to_polygon(),
+                );
+            }
             State::MovingIntersection(_)
             | State::MovingBuilding(_)
             | State::MovingRoadPoint(_, _)
@@ -767,6 +830,30 @@ fn find_short_roads(model: &Model) -> HashSet<OriginalRoad> {
     roads
 }
 
+// Produced by running precompute with --validate. Explains what's wrong if there's nothing to
+// review.
+fn load_validation_issues(model: &Model) -> Result<Vec<MapIssue>, String> {
+    let path = abstutil::path_map_validation(&model.map.name);
+    match abstutil::maybe_read_json::<ValidationReport>(path.clone(), &mut Timer::throwaway()) {
+        Ok(report) => {
+            println!(
+                "{} errors, {} warnings",
+                report.count(Severity::Error),
+                report.count(Severity::Warning)
+            );
+            if report.issues.is_empty() {
+                Err(format!("{} doesn't have any issues", path))
+            } else {
+                Ok(report.issues)
+            }
+        }
+        Err(err) => Err(format!(
+            "Couldn't load {} ({}). Run precompute with --validate first.",
+            path, err
+        )),
+    }
+}
+
 fn main() {
     ezgui::run(ezgui::Settings::new("Synthetic map editor"), |ctx| {
         UI::new(ctx)
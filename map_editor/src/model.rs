@@ -223,6 +223,7 @@ impl Model {
             IntersectionType::StopSign => Color::RED,
             IntersectionType::Border => Color::BLUE,
             IntersectionType::Construction => Color::ORANGE,
+            IntersectionType::Roundabout | IntersectionType::Merge => Color::CYAN,
         };
 
         let poly = if self.intersection_geom && !self.map.roads_per_intersection(id).is_empty() {
@@ -271,6 +272,8 @@ impl Model {
                 }
             }
             IntersectionType::Border => IntersectionType::StopSign,
+            // Merges are detected when building the map, but just in case
+            IntersectionType::Roundabout | IntersectionType::Merge => IntersectionType::StopSign,
             // These shouldn't exist in a basemap!
             IntersectionType::Construction => unreachable!(),
         };
@@ -0,0 +1,299 @@
+use crate::pathfind::bus_ride_time;
+use crate::{
+    Building, BuildingID, BusStopID, FullNeighborhoodInfo, LaneID, Map, PathConstraints,
+    TravelTimeGraph,
+};
+use abstutil::Timer;
+use geom::Duration;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum AccessMode {
+    Walk,
+    Bike,
+    Transit,
+    Drive,
+}
+
+impl AccessMode {
+    pub fn all() -> Vec<AccessMode> {
+        vec![
+            AccessMode::Walk,
+            AccessMode::Bike,
+            AccessMode::Transit,
+            AccessMode::Drive,
+        ]
+    }
+
+    // Buses in PathConstraints are the vehicles; here it means riding them.
+    pub fn from_constraints(constraints: PathConstraints) -> AccessMode {
+        match constraints {
+            PathConstraints::Pedestrian => AccessMode::Walk,
+            PathConstraints::Bike => AccessMode::Bike,
+            PathConstraints::Bus => AccessMode::Transit,
+            PathConstraints::Car => AccessMode::Drive,
+        }
+    }
+
+    fn constraints(self) -> PathConstraints {
+        match self {
+            AccessMode::Walk | AccessMode::Transit => PathConstraints::Pedestrian,
+            AccessMode::Bike => PathConstraints::Bike,
+            AccessMode::Drive => PathConstraints::Car,
+        }
+    }
+
+    pub fn graph(self, map: &Map) -> TravelTimeGraph {
+        if self == AccessMode::Transit {
+            TravelTimeGraph::new(map, self.constraints(), Some(&transit_waits(map)))
+        } else {
+            TravelTimeGraph::new(map, self.constraints(), None)
+        }
+    }
+}
+
+impl fmt::Display for AccessMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AccessMode::Walk => write!(f, "walking"),
+            AccessMode::Bike => write!(f, "biking"),
+            AccessMode::Transit => write!(f, "transit"),
+            AccessMode::Drive => write!(f, "driving"),
+        }
+    }
+}
+
+// Some kind of building people want to reach, matched by OSM tags. A tag with no value matches
+// any value.
+#[derive(Clone, Debug)]
+pub struct Opportunity {
+    pub name: String,
+    pub tags: Vec<(String, Option<String>)>,
+}
+
+impl Opportunity {
+    // There's no employment data, so jobs are just buildings that look like workplaces.
+    pub fn defaults() -> Vec<Opportunity> {
+        vec![
+            Opportunity {
+                name: "jobs".to_string(),
+                tags: vec![
+                    ("office".to_string(), None),
+                    ("building".to_string(), Some("commercial".to_string())),
+                    ("building".to_string(), Some("industrial".to_string())),
+                    ("building".to_string(), Some("office".to_string())),
+                    ("building".to_string(), Some("retail".to_string())),
+                ],
+            },
+            Opportunity {
+                name: "shops".to_string(),
+                tags: vec![("shop".to_string(), None)],
+            },
+            Opportunity {
+                name: "amenities".to_string(),
+                tags: vec![("amenity".to_string(), None)],
+            },
+        ]
+    }
+
+    pub fn matches(&self, b: &Building) -> bool {
+        self.tags.iter().any(|(k, v)| match (b.osm_tags.get(k), v) {
+            (Some(_), None) => true,
+            (Some(actual), Some(expected)) => actual == expected,
+            (None, _) => false,
+        })
+    }
+}
+
+// How many of each kind of opportunity can be reached from every building within some time.
+pub struct AccessibilityScores {
+    pub map_name: String,
+    pub edits_name: String,
+    pub limit: Duration,
+    pub opportunities: Vec<Opportunity>,
+    // The counts are in the same order as opportunities
+    pub per_bldg: BTreeMap<BuildingID, BTreeMap<AccessMode, Vec<usize>>>,
+}
+
+impl AccessibilityScores {
+    pub fn new(
+        map: &Map,
+        opportunities: Vec<Opportunity>,
+        limit: Duration,
+        timer: &mut Timer,
+    ) -> AccessibilityScores {
+        let mut per_bldg: BTreeMap<BuildingID, BTreeMap<AccessMode, Vec<usize>>> = map
+            .all_buildings()
+            .iter()
+            .map(|b| (b.id, BTreeMap::new()))
+            .collect();
+
+        for mode in AccessMode::all() {
+            timer.start(format!("accessibility by {}", mode));
+            let graph = mode.graph(map);
+
+            // Many buildings share a lane, so only search once per lane.
+            let mut counts_per_lane: HashMap<LaneID, Vec<usize>> = HashMap::new();
+            let mut bldgs_per_lane: BTreeMap<LaneID, Vec<BuildingID>> = BTreeMap::new();
+            for b in map.all_buildings() {
+                if let Some(l) = graph.start_lane(map, b.id) {
+                    let counts = counts_per_lane
+                        .entry(l)
+                        .or_insert_with(|| vec![0; opportunities.len()]);
+                    for (idx, o) in opportunities.iter().enumerate() {
+                        if o.matches(b) {
+                            counts[idx] += 1;
+                        }
+                    }
+                    bldgs_per_lane.entry(l).or_insert_with(Vec::new).push(b.id);
+                }
+            }
+
+            let graph_ref = &graph;
+            let counts_ref = &counts_per_lane;
+            let num_opportunities = opportunities.len();
+            let results = timer.parallelize(
+                &format!("search from every lane by {}", mode),
+                bldgs_per_lane.keys().cloned().collect(),
+                |start| {
+                    let mut total = vec![0; num_opportunities];
+                    for l in graph_ref.travel_times(start, limit).keys() {
+                        if let Some(counts) = counts_ref.get(l) {
+                            for (idx, cnt) in counts.iter().enumerate() {
+                                total[idx] += cnt;
+                            }
+                        }
+                    }
+                    (start, total)
+                },
+            );
+            for (l, total) in results {
+                for b in &bldgs_per_lane[&l] {
+                    per_bldg.get_mut(b).unwrap().insert(mode, total.clone());
+                }
+            }
+            timer.stop(format!("accessibility by {}", mode));
+        }
+
+        AccessibilityScores {
+            map_name: map.get_name().to_string(),
+            edits_name: map.get_edits().edits_name.clone(),
+            limit,
+            opportunities,
+            per_bldg,
+        }
+    }
+
+    // The average over the buildings in each neighborhood. Buildings that can't use some mode at
+    // all count as 0 for it.
+    pub fn per_neighborhood(&self, map: &Map) -> BTreeMap<String, BTreeMap<AccessMode, Vec<f64>>> {
+        let mut results = BTreeMap::new();
+        for (name, n) in FullNeighborhoodInfo::load_all(map) {
+            if n.buildings.is_empty() {
+                continue;
+            }
+            let mut per_mode = BTreeMap::new();
+            for mode in AccessMode::all() {
+                let mut sums = vec![0.0; self.opportunities.len()];
+                for b in &n.buildings {
+                    if let Some(counts) = self.per_bldg[b].get(&mode) {
+                        for (idx, cnt) in counts.iter().enumerate() {
+                            sums[idx] += *cnt as f64;
+                        }
+                    }
+                }
+                per_mode.insert(
+                    mode,
+                    sums.into_iter()
+                        .map(|x| x / (n.buildings.len() as f64))
+                        .collect(),
+                );
+            }
+            results.insert(name, per_mode);
+        }
+        results
+    }
+
+    // Describes how every neighborhood changed. Both need to be for the same map, opportunities,
+    // and time limit.
+    pub fn compare(
+        before: &AccessibilityScores,
+        after: &AccessibilityScores,
+        map: &Map,
+    ) -> Vec<String> {
+        assert_eq!(before.map_name, after.map_name);
+        assert_eq!(before.limit, after.limit);
+        assert_eq!(before.opportunities.len(), after.opportunities.len());
+
+        let mut lines = vec![format!(
+            "Reachable within {}, {} vs {}",
+            after.limit, before.edits_name, after.edits_name
+        )];
+        let before_n = before.per_neighborhood(map);
+        let after_n = after.per_neighborhood(map);
+        let mut any_changes = false;
+        for (name, after_modes) in &after_n {
+            for (mode, after_counts) in after_modes {
+                for (idx, o) in after.opportunities.iter().enumerate() {
+                    let x1 = before_n[name][mode][idx];
+                    let x2 = after_counts[idx];
+                    if (x2 - x1).abs() < 0.05 {
+                        continue;
+                    }
+                    any_changes = true;
+                    let pct = if x1 > 0.0 {
+                        format!(" ({:+.1}%)", 100.0 * (x2 - x1) / x1)
+                    } else {
+                        String::new()
+                    };
+                    lines.push(format!(
+                        "{}: {} by {}: {:.1} -> {:.1}{}",
+                        name, o.name, mode, x1, x2, pct
+                    ));
+                }
+            }
+        }
+        if !any_changes {
+            lines.push("No neighborhood changed".to_string());
+        }
+        lines
+    }
+}
+
+// There are no schedules, but the simulation starts one bus per stop on every route, so the
+// headway is how long a bus takes to go around the loop divided by the number of stops. People
+// show up at random, so on average they wait half of that. When several routes serve a stop, take
+// the shortest wait.
+fn transit_waits(map: &Map) -> BTreeMap<BusStopID, Duration> {
+    let mut waits: BTreeMap<BusStopID, Duration> = BTreeMap::new();
+    for route in map.get_all_bus_routes() {
+        if route.stops.is_empty() {
+            continue;
+        }
+        // Routes loop around
+        let mut cycle = Duration::ZERO;
+        let mut complete = true;
+        for (stop1, stop2) in route
+            .stops
+            .iter()
+            .zip(route.stops.iter().skip(1).chain(route.stops.iter().take(1)))
+        {
+            if let Some(dt) = bus_ride_time(map, *stop1, *stop2) {
+                cycle += dt;
+            } else {
+                complete = false;
+            }
+        }
+        if !complete {
+            // Buses can't finish the loop, so nobody should count on them.
+            continue;
+        }
+        let wait = cycle / (2.0 * route.stops.len() as f64);
+        for stop in &route.stops {
+            let entry = waits.entry(*stop).or_insert(wait);
+            *entry = (*entry).min(wait);
+        }
+    }
+    waits
+}
@@ -195,6 +195,9 @@ impl MapEdits {
                     }
                     assert!(found);
                 }
+                // There's nothing to edit about these besides closing them, and reopening one
+                // just restores its original control.
+                IntersectionType::Roundabout | IntersectionType::Merge => {}
                 IntersectionType::Border => unreachable!(),
             }
         }
@@ -21,6 +21,10 @@ pub enum IntersectionType {
     TrafficSignal,
     Border,
     Construction,
+    // Entering traffic yields to vehicles already circulating.
+    Roundabout,
+    // A freeway on- or off-ramp. Ramp traffic yields to the mainline.
+    Merge,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -58,6 +62,14 @@ impl Intersection {
         self.intersection_type == IntersectionType::TrafficSignal
     }
 
+    pub fn is_roundabout(&self) -> bool {
+        self.intersection_type == IntersectionType::Roundabout
+    }
+
+    pub fn is_merge(&self) -> bool {
+        self.intersection_type == IntersectionType::Merge
+    }
+
     pub fn get_incoming_lanes(&self, map: &Map, constraints: PathConstraints) -> Vec<LaneID> {
         self.incoming_lanes
             .iter()
@@ -0,0 +1,97 @@
+use crate::{AccessMode, BuildingID, LaneID, Map, PathConstraints, Position, TravelTimeGraph};
+use geom::{Duration, Polygon, Pt2D};
+use std::collections::BTreeSet;
+
+// Everything reachable from one place within a few different time limits.
+pub struct Isochrone {
+    pub constraints: PathConstraints,
+    // In increasing order of the time limit
+    pub bands: Vec<IsochroneBand>,
+}
+
+pub struct IsochroneBand {
+    pub limit: Duration,
+    // Everything reachable within the limit, so this includes the smaller bands too
+    pub lanes: BTreeSet<LaneID>,
+    pub buildings: BTreeSet<BuildingID>,
+    // The convex hull of everything reachable
+    pub polygon: Option<Polygon>,
+}
+
+impl Isochrone {
+    pub fn from_building(
+        map: &Map,
+        b: BuildingID,
+        constraints: PathConstraints,
+        limits: Vec<Duration>,
+    ) -> Isochrone {
+        let graph = AccessMode::from_constraints(constraints).graph(map);
+        let start = graph.start_lane(map, b);
+        Isochrone::new(map, &graph, start, constraints, limits)
+    }
+
+    pub fn from_position(
+        map: &Map,
+        pos: Position,
+        constraints: PathConstraints,
+        limits: Vec<Duration>,
+    ) -> Isochrone {
+        let graph = AccessMode::from_constraints(constraints).graph(map);
+        let start = graph.nearest_lane(map, pos.lane());
+        Isochrone::new(map, &graph, start, constraints, limits)
+    }
+
+    fn new(
+        map: &Map,
+        graph: &TravelTimeGraph,
+        start: Option<LaneID>,
+        constraints: PathConstraints,
+        mut limits: Vec<Duration>,
+    ) -> Isochrone {
+        limits.sort();
+        let times = match (start, limits.last()) {
+            (Some(l), Some(max)) => graph.travel_times(l, *max),
+            _ => Default::default(),
+        };
+
+        // The same for every band, so only look it up once
+        let bldg_lanes: Vec<(BuildingID, LaneID)> = map
+            .all_buildings()
+            .iter()
+            .filter_map(|b| Some((b.id, graph.start_lane(map, b.id)?)))
+            .collect();
+
+        let bands = limits
+            .into_iter()
+            .map(|limit| {
+                let lanes: BTreeSet<LaneID> = times
+                    .iter()
+                    .filter_map(|(l, t)| if *t <= limit { Some(*l) } else { None })
+                    .collect();
+                let buildings: BTreeSet<BuildingID> = bldg_lanes
+                    .iter()
+                    .filter_map(|(b, l)| if lanes.contains(l) { Some(*b) } else { None })
+                    .collect();
+
+                let mut pts: Vec<Pt2D> = Vec::new();
+                for l in &lanes {
+                    let lane = map.get_l(*l);
+                    pts.push(lane.first_pt());
+                    pts.push(lane.last_pt());
+                }
+                for b in &buildings {
+                    pts.push(map.get_b(*b).polygon.center());
+                }
+
+                IsochroneBand {
+                    limit,
+                    polygon: Polygon::convex_hull(&pts),
+                    lanes,
+                    buildings,
+                }
+            })
+            .collect();
+
+        Isochrone { constraints, bands }
+    }
+}
@@ -1,37 +1,49 @@
+mod accessibility;
 mod area;
 mod building;
 mod bus_stop;
 pub mod connectivity;
 mod edits;
 mod intersection;
+mod isochrone;
 mod lane;
 mod make;
 mod map;
+mod merges;
 mod neighborhood;
 pub mod osm;
 mod pathfind;
 pub mod raw;
 mod road;
+mod roundabouts;
 mod stop_signs;
 mod traffic_signals;
 mod traversable;
 mod turn;
+mod unzoomed;
+mod validate;
 
+pub use crate::accessibility::{AccessMode, AccessibilityScores, Opportunity};
 pub use crate::area::{Area, AreaID, AreaType};
 pub use crate::building::{Building, BuildingID, FrontPath, OffstreetParking};
 pub use crate::bus_stop::{BusRoute, BusRouteID, BusStop, BusStopID};
 pub use crate::edits::{EditCmd, EditEffects, MapEdits};
 pub use crate::intersection::{Intersection, IntersectionID, IntersectionType};
+pub use crate::isochrone::{Isochrone, IsochroneBand};
 pub use crate::lane::{Lane, LaneID, LaneType, PARKING_SPOT_LENGTH};
 pub use crate::make::RoadSpec;
 pub use crate::map::Map;
+pub use crate::merges::ControlMerge;
 pub use crate::neighborhood::{FullNeighborhoodInfo, Neighborhood, NeighborhoodBuilder};
-pub use crate::pathfind::{Path, PathConstraints, PathRequest, PathStep};
+pub use crate::pathfind::{Path, PathConstraints, PathRequest, PathStep, TravelTimeGraph};
 pub use crate::road::{DirectedRoadID, Road, RoadID};
+pub use crate::roundabouts::ControlRoundabout;
 pub use crate::stop_signs::{ControlStopSign, RoadWithStopSign};
 pub use crate::traffic_signals::{ControlTrafficSignal, Phase};
 pub use crate::traversable::{Position, Traversable};
 pub use crate::turn::{Turn, TurnGroup, TurnGroupID, TurnID, TurnPriority, TurnType};
+pub use crate::unzoomed::{unzoomed_intersections, unzoomed_map, unzoomed_roads, UnzoomedShape};
+pub use crate::validate::{IssueObject, MapIssue, Severity, ValidationReport};
 use abstutil::Cloneable;
 use geom::Distance;
 
@@ -44,4 +56,5 @@ impl Cloneable for LaneType {}
 impl Cloneable for MapEdits {}
 impl Cloneable for Neighborhood {}
 impl Cloneable for NeighborhoodBuilder {}
+impl Cloneable for PathConstraints {}
 impl Cloneable for raw::RestrictionType {}
@@ -5,6 +5,9 @@ use geom::{Distance, Line, PolyLine, Polygon, Pt2D};
 use std::collections::BTreeMap;
 
 const DEGENERATE_INTERSECTION_HALF_LENGTH: Distance = Distance::const_meters(2.5);
+// The segments of a roundabout's ring are short and curve sharply, so the perpendicular trick below
+// can wind up eating most of them. The ring only needs to make room for the approach roads.
+const MAX_ROUNDABOUT_TRIM: Distance = Distance::const_meters(5.0);
 
 // The polygon should exist entirely within the thick bands around all original roads -- it just
 // carves up part of that space, doesn't reach past it.
@@ -112,6 +115,12 @@ fn generalized_trim_back(
             if r1 == r2 {
                 continue;
             }
+            // A way only climbs one layer where it meets others. Something farther above or below
+            // that still shares this node (usually sloppy tagging, like a tunnel portal glued to
+            // the end of a bridge) passes over or under, so it shouldn't carve into this road.
+            if (roads[r1].zorder - roads[r2].zorder).abs() > 1 {
+                continue;
+            }
 
             // If two roads go between the same intersections, they'll likely hit at the wrong
             // side. Just use the second half of the polyline to circumvent this. But sadly, doing
@@ -189,6 +198,14 @@ fn generalized_trim_back(
             }
         }
 
+        if roads[r1].roundabout
+            && road_center.length() - shortest_center.length() > MAX_ROUNDABOUT_TRIM
+            && road_center.length() >= MAX_ROUNDABOUT_TRIM + 3.0 * geom::EPSILON_DIST
+        {
+            shortest_center =
+                road_center.exact_slice(Distance::ZERO, road_center.length() - MAX_ROUNDABOUT_TRIM);
+        }
+
         let new_center = if roads[r1].dst_i == i {
             shortest_center
         } else {
@@ -14,15 +14,16 @@ pub fn get_lane_types(osm_tags: &BTreeMap<String, String>) -> (Vec<LaneType>, Ve
     }
 
     // Easy special cases first.
-    if osm_tags.get("junction") == Some(&"roundabout".to_string()) {
-        return (vec![LaneType::Driving, LaneType::Sidewalk], Vec::new());
-    }
     if osm_tags.get(osm::HIGHWAY) == Some(&"footway".to_string()) {
         return (vec![LaneType::Sidewalk], Vec::new());
     }
 
+    // Roundabouts are implicitly one-way, but may have more than one lane.
+    let roundabout = osm_tags.get(osm::JUNCTION) == Some(&"roundabout".to_string());
+
     // TODO Reversible roads should be handled differently?
-    let oneway = osm_tags.get("oneway") == Some(&"yes".to_string())
+    let oneway = roundabout
+        || osm_tags.get("oneway") == Some(&"yes".to_string())
         || osm_tags.get("oneway") == Some(&"reversible".to_string());
 
     // How many driving lanes in each direction?
@@ -137,6 +138,10 @@ pub fn get_lane_types(osm_tags: &BTreeMap<String, String>) -> (Vec<LaneType>, Ve
         fwd_side.push(LaneType::Sidewalk);
     } else if osm_tags.get(osm::SIDEWALK) == Some(&"left".to_string()) {
         back_side.push(LaneType::Sidewalk);
+    } else if roundabout && osm_tags.get(osm::INFERRED_SIDEWALKS) == Some(&"true".to_string()) {
+        // TODO Pedestrians should really cross the approaches instead of walking around the ring,
+        // but until then, keep them connected.
+        fwd_side.push(LaneType::Sidewalk);
     }
 
     (fwd_side, back_side)
@@ -3,7 +3,7 @@ pub mod lane_specs;
 
 pub use self::geometry::intersection_polygon;
 use crate::raw::{OriginalIntersection, OriginalRoad, RawMap, RawRoad};
-use crate::{IntersectionType, LaneType, LANE_THICKNESS};
+use crate::{osm, IntersectionType, LaneType, LANE_THICKNESS};
 use abstutil::Timer;
 use geom::{Bounds, Distance, PolyLine, Pt2D};
 use std::collections::{BTreeMap, BTreeSet};
@@ -26,6 +26,9 @@ pub struct Road {
     pub fwd_width: Distance,
     pub back_width: Distance,
     pub lane_specs: Vec<LaneSpec>,
+    // Part of the ring of a roundabout
+    pub roundabout: bool,
+    pub zorder: isize,
 }
 
 impl Road {
@@ -51,6 +54,8 @@ impl Road {
             fwd_width,
             back_width,
             lane_specs,
+            roundabout: r.osm_tags.get(osm::JUNCTION) == Some(&"roundabout".to_string()),
+            zorder: r.get_zorder(),
         }
     }
 }
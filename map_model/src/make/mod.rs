@@ -3,10 +3,12 @@ mod bus_stops;
 pub mod initial;
 mod remove_disconnected;
 mod sidewalk_finder;
+mod turn_lanes;
 mod turns;
 
 pub use self::buildings::make_all_buildings;
 pub use self::bus_stops::{fix_bus_route, make_bus_stops};
 pub use self::initial::lane_specs::{get_lane_types, RoadSpec};
 pub use self::remove_disconnected::remove_disconnected_roads;
+pub use self::turn_lanes::get_lane_turn_restrictions;
 pub use self::turns::make_all_turns;
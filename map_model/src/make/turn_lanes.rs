@@ -0,0 +1,109 @@
+use crate::{osm, LaneID, LaneType, Road, TurnType};
+use abstutil::Warn;
+use std::collections::BTreeSet;
+
+// Interpret OSM turn:lanes tags as the movements each vehicle lane is allowed to make at the end
+// of the road. Lanes without any restriction are omitted.
+pub fn get_lane_turn_restrictions(road: &Road) -> Warn<Vec<(LaneID, BTreeSet<TurnType>)>> {
+    let mut results = Vec::new();
+    let mut warnings = Vec::new();
+
+    // The tags only describe the end of the original OSM way.
+    let mut directions = Vec::new();
+    if road.osm_tags.contains_key(osm::ENDPT_FWD) {
+        if let Some(tag) = road
+            .osm_tags
+            .get("turn:lanes:forward")
+            .or_else(|| road.osm_tags.get("turn:lanes"))
+        {
+            directions.push((tag, &road.children_forwards));
+        }
+    }
+    if road.osm_tags.contains_key(osm::ENDPT_BACK) {
+        if let Some(tag) = road.osm_tags.get("turn:lanes:backward") {
+            directions.push((tag, &road.children_backwards));
+        }
+    }
+
+    for (tag, children) in directions {
+        // OSM counts lanes for motor vehicles, from left to right. Our lanes are ordered from the
+        // center of the road outwards, which is the same thing.
+        let lanes: Vec<LaneID> = children
+            .iter()
+            .filter(|(_, lt)| *lt == LaneType::Driving || *lt == LaneType::Bus)
+            .map(|(id, _)| *id)
+            .collect();
+        let parts: Vec<&str> = tag.split('|').collect();
+        if parts.len() != lanes.len() {
+            warnings.push(format!(
+                "{} has {} vehicle lanes, but turn:lanes = {}",
+                road.id,
+                lanes.len(),
+                tag
+            ));
+            continue;
+        }
+
+        for (l, part) in lanes.into_iter().zip(parts) {
+            match parse_turn_lane(part) {
+                Ok(Some(types)) => {
+                    results.push((l, types));
+                }
+                Ok(None) => {}
+                Err(value) => {
+                    warnings.push(format!(
+                        "{} has unknown turn:lanes value {} for {}",
+                        road.id, value, l
+                    ));
+                }
+            }
+        }
+    }
+
+    Warn::warnings(results, warnings)
+}
+
+// None means any movement is fine. Unknown values are returned as the error.
+fn parse_turn_lane(part: &str) -> Result<Option<BTreeSet<TurnType>>, String> {
+    let mut types = BTreeSet::new();
+    for value in part.split(';') {
+        match value.trim() {
+            // TODO What is blank supposed to mean? From few observed cases, same as none
+            "none" | "" => {
+                return Ok(None);
+            }
+            "left" | "sharp_left" => {
+                types.insert(TurnType::Left);
+            }
+            "right" | "sharp_right" => {
+                types.insert(TurnType::Right);
+            }
+            "through" => {
+                types.insert(TurnType::Straight);
+                types.insert(TurnType::LaneChangeLeft);
+                types.insert(TurnType::LaneChangeRight);
+            }
+            // TODO Check this more carefully
+            "slight_right" | "slight right" | "merge_to_right" => {
+                types.insert(TurnType::Straight);
+                types.insert(TurnType::LaneChangeRight);
+                types.insert(TurnType::Right);
+            }
+            "slight_left" | "slight left" | "merge_to_left" => {
+                types.insert(TurnType::Straight);
+                types.insert(TurnType::LaneChangeLeft);
+                types.insert(TurnType::Left);
+            }
+            // U-turns aren't modeled
+            "reverse" => {}
+            x => {
+                return Err(x.to_string());
+            }
+        }
+    }
+    if types.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(types))
+    }
+}
@@ -1,3 +1,4 @@
+use crate::merges::{pick_mainline, vehicle_lanes};
 use crate::raw::RestrictionType;
 use crate::{
     Intersection, IntersectionID, Lane, LaneID, LaneType, Road, RoadID, Turn, TurnID, TurnType,
@@ -30,7 +31,7 @@ pub fn make_all_turns(
             continue;
         }
 
-        if is_turn_allowed(&turn, roads, lanes) {
+        if is_turn_allowed(&turn, lanes) {
             final_turns.push(turn);
         } else {
             filtered_turns
@@ -103,6 +104,12 @@ fn make_vehicle_turns(
     lanes: &Vec<Lane>,
     timer: &mut Timer,
 ) -> Vec<Turn> {
+    if i.is_merge() {
+        if let Some(turns) = make_merge_turns(i, all_roads, lanes) {
+            return turns;
+        }
+    }
+
     let sorted_roads: Vec<&Road> = i
         .get_roads_sorted_by_incoming_angle(all_roads)
         .iter()
@@ -209,7 +216,9 @@ fn make_vehicle_turns(
                                 if !incoming.contains(&l1) || !outgoing.contains(l2) {
                                     continue;
                                 }
-                                if let Some(mut t) = make_vehicle_turn(lanes, i.id, l1, *l2, tt) {
+                                if let Some(mut t) =
+                                    make_vehicle_turn(all_roads, lanes, i.id, l1, *l2, tt)
+                                {
                                     if idx1 < idx2 {
                                         t.turn_type = TurnType::LaneChangeRight;
                                     } else if idx1 > idx2 {
@@ -224,7 +233,7 @@ fn make_vehicle_turns(
                     TurnType::Right => {
                         for (idx, l1) in incoming.iter().enumerate() {
                             for l2 in &outgoing {
-                                let turn = make_vehicle_turn(lanes, i.id, *l1, *l2, tt);
+                                let turn = make_vehicle_turn(all_roads, lanes, i.id, *l1, *l2, tt);
                                 if idx == incoming.len() - 1 {
                                     result.push(turn);
                                 } else {
@@ -236,7 +245,7 @@ fn make_vehicle_turns(
                     TurnType::Left => {
                         for (idx, l1) in incoming.iter().enumerate() {
                             for l2 in &outgoing {
-                                let turn = make_vehicle_turn(lanes, i.id, *l1, *l2, tt);
+                                let turn = make_vehicle_turn(all_roads, lanes, i.id, *l1, *l2, tt);
                                 if idx == 0 {
                                     result.push(turn);
                                 } else {
@@ -258,6 +267,92 @@ fn make_vehicle_turns(
     result.into_iter().filter_map(|x| x).collect()
 }
 
+// Line up lanes from the left, instead of connecting everything to everything. This covers every
+// lane type at once. On-ramps either continue as new lanes or merge into the right-most lane.
+// Off-ramps get the exit-only lanes, or failing that, the right-most lane. Returns None if the
+// intersection isn't a simple merge or split.
+fn make_merge_turns(i: &Intersection, roads: &Vec<Road>, lanes: &Vec<Lane>) -> Option<Vec<Turn>> {
+    let mut incoming: Vec<(&Road, Vec<LaneID>)> = Vec::new();
+    let mut outgoing: Vec<(&Road, Vec<LaneID>)> = Vec::new();
+    for r in &i.roads {
+        let road = &roads[r.0];
+        let inc = vehicle_lanes(road.incoming_lanes(i.id));
+        if !inc.is_empty() {
+            incoming.push((road, inc));
+        }
+        let out = vehicle_lanes(road.outgoing_lanes(i.id));
+        if !out.is_empty() {
+            outgoing.push((road, out));
+        }
+    }
+
+    let mut result: Vec<Option<Turn>> = Vec::new();
+    // Built as straight lines like in make_vehicle_turns, then marked as lane-changing.
+    let mut add_turn = |l1: LaneID, l2: LaneID, turn_type: TurnType| {
+        result.push(
+            make_vehicle_turn(roads, lanes, i.id, l1, l2, TurnType::Straight).map(|mut t| {
+                t.turn_type = turn_type;
+                t
+            }),
+        );
+    };
+
+    if incoming.len() >= 2 && outgoing.len() == 1 {
+        let mainline = pick_mainline(&incoming);
+        let out = &outgoing[0].1;
+        // The mainline goes first, then each ramp continues as added lanes, as long as there are
+        // enough.
+        let mut next_lane = 0;
+        for (r, inc) in incoming
+            .iter()
+            .filter(|(r, _)| r.id == mainline)
+            .chain(incoming.iter().filter(|(r, _)| r.id != mainline))
+        {
+            for l1 in inc {
+                if next_lane < out.len() {
+                    add_turn(*l1, out[next_lane], TurnType::Straight);
+                } else {
+                    // The mainline drops a lane here, or the ramp has to merge.
+                    add_turn(*l1, *out.last().unwrap(), TurnType::LaneChangeLeft);
+                }
+                next_lane += 1;
+            }
+            if r.id == mainline {
+                next_lane = next_lane.max(inc.len());
+            }
+        }
+    } else if incoming.len() == 1 && outgoing.len() >= 2 {
+        let mainline = pick_mainline(&outgoing);
+        let main_lanes = &outgoing.iter().find(|(r, _)| r.id == mainline).unwrap().1;
+        let ramp_lanes: Vec<LaneID> = outgoing
+            .iter()
+            .filter(|(r, _)| r.id != mainline)
+            .flat_map(|(_, out)| out.clone())
+            .collect();
+        let inc = &incoming[0].1;
+
+        for (idx, l1) in inc.iter().enumerate() {
+            if idx < main_lanes.len() {
+                add_turn(*l1, main_lanes[idx], TurnType::Straight);
+            } else {
+                // An exit-only lane
+                let dst_idx = (idx - main_lanes.len()).min(ramp_lanes.len() - 1);
+                add_turn(*l1, ramp_lanes[dst_idx], TurnType::Straight);
+            }
+        }
+        if inc.len() <= main_lanes.len() {
+            // No exit-only lanes, so the right-most lane can take any of the ramps.
+            for (_, out) in outgoing.iter().filter(|(r, _)| r.id != mainline) {
+                add_turn(*inc.last().unwrap(), out[0], TurnType::LaneChangeRight);
+            }
+        }
+    } else {
+        return None;
+    }
+
+    Some(result.into_iter().flatten().collect())
+}
+
 fn make_vehicle_turns_for_dead_end(
     i: &Intersection,
     roads: &Vec<Road>,
@@ -275,6 +370,7 @@ fn make_vehicle_turns_for_dead_end(
     for l1 in incoming {
         for l2 in &outgoing {
             result.push(make_vehicle_turn(
+                roads,
                 lanes,
                 i.id,
                 l1,
@@ -304,7 +400,8 @@ fn make_walking_turns(
     let mut result: Vec<Turn> = Vec::new();
 
     if roads.len() == 2 {
-        if let Some(turns) = make_degenerate_crosswalks(i.id, lanes, roads[0], roads[1]) {
+        if let Some(turns) = make_degenerate_crosswalks(i.id, all_roads, lanes, roads[0], roads[1])
+        {
             result.extend(turns);
         }
         // TODO Argh, duplicate logic for SharedSidewalkCorners
@@ -316,20 +413,22 @@ fn make_walking_turns(
                 ) {
                     if l1.last_pt() != l2.first_pt() {
                         let geom = make_shared_sidewalk_corner(i, l1, l2, timer);
-                        result.push(Turn {
-                            id: turn_id(i.id, l1.id, l2.id),
-                            turn_type: TurnType::SharedSidewalkCorner,
-                            other_crosswalk_ids: BTreeSet::new(),
-                            geom: geom.clone(),
-                            lookup_idx: 0,
-                        });
-                        result.push(Turn {
-                            id: turn_id(i.id, l2.id, l1.id),
-                            turn_type: TurnType::SharedSidewalkCorner,
-                            other_crosswalk_ids: BTreeSet::new(),
-                            geom: geom.reversed(),
-                            lookup_idx: 0,
-                        });
+                        result.push(new_turn(
+                            i.id,
+                            l1,
+                            l2,
+                            TurnType::SharedSidewalkCorner,
+                            geom.clone(),
+                            all_roads,
+                        ));
+                        result.push(new_turn(
+                            i.id,
+                            l2,
+                            l1,
+                            TurnType::SharedSidewalkCorner,
+                            geom.reversed(),
+                            all_roads,
+                        ));
                     }
                 }
             }
@@ -340,20 +439,22 @@ fn make_walking_turns(
         if let Some(l1) = get_sidewalk(lanes, roads[0].incoming_lanes(i.id)) {
             if let Some(l2) = get_sidewalk(lanes, roads[0].outgoing_lanes(i.id)) {
                 let geom = make_shared_sidewalk_corner(i, l1, l2, timer);
-                result.push(Turn {
-                    id: turn_id(i.id, l1.id, l2.id),
-                    turn_type: TurnType::SharedSidewalkCorner,
-                    other_crosswalk_ids: BTreeSet::new(),
-                    geom: geom.clone(),
-                    lookup_idx: 0,
-                });
-                result.push(Turn {
-                    id: turn_id(i.id, l2.id, l1.id),
-                    turn_type: TurnType::SharedSidewalkCorner,
-                    other_crosswalk_ids: BTreeSet::new(),
-                    geom: geom.reversed(),
-                    lookup_idx: 0,
-                });
+                result.push(new_turn(
+                    i.id,
+                    l1,
+                    l2,
+                    TurnType::SharedSidewalkCorner,
+                    geom.clone(),
+                    all_roads,
+                ));
+                result.push(new_turn(
+                    i.id,
+                    l2,
+                    l1,
+                    TurnType::SharedSidewalkCorner,
+                    geom.reversed(),
+                    all_roads,
+                ));
             }
         }
         return result;
@@ -363,7 +464,7 @@ fn make_walking_turns(
         if let Some(l1) = get_sidewalk(lanes, roads[idx1].incoming_lanes(i.id)) {
             // Make the crosswalk to the other side
             if let Some(l2) = get_sidewalk(lanes, roads[idx1].outgoing_lanes(i.id)) {
-                result.extend(make_crosswalks(i.id, l1, l2));
+                result.extend(make_crosswalks(i.id, l1, l2, all_roads));
             }
 
             // Find the shared corner
@@ -374,20 +475,22 @@ fn make_walking_turns(
             ) {
                 if l1.last_pt() != l2.first_pt() {
                     let geom = make_shared_sidewalk_corner(i, l1, l2, timer);
-                    result.push(Turn {
-                        id: turn_id(i.id, l1.id, l2.id),
-                        turn_type: TurnType::SharedSidewalkCorner,
-                        other_crosswalk_ids: BTreeSet::new(),
-                        geom: geom.clone(),
-                        lookup_idx: 0,
-                    });
-                    result.push(Turn {
-                        id: turn_id(i.id, l2.id, l1.id),
-                        turn_type: TurnType::SharedSidewalkCorner,
-                        other_crosswalk_ids: BTreeSet::new(),
-                        geom: geom.reversed(),
-                        lookup_idx: 0,
-                    });
+                    result.push(new_turn(
+                        i.id,
+                        l1,
+                        l2,
+                        TurnType::SharedSidewalkCorner,
+                        geom.clone(),
+                        all_roads,
+                    ));
+                    result.push(new_turn(
+                        i.id,
+                        l2,
+                        l1,
+                        TurnType::SharedSidewalkCorner,
+                        geom.reversed(),
+                        all_roads,
+                    ));
                 }
             } else if let Some(l2) = get_sidewalk(
                 lanes,
@@ -395,7 +498,7 @@ fn make_walking_turns(
             ) {
                 // Adjacent road is missing a sidewalk on the near side, but has one on the far
                 // side
-                result.extend(make_crosswalks(i.id, l1, l2));
+                result.extend(make_crosswalks(i.id, l1, l2, all_roads));
             } else {
                 // We may need to add a crosswalk over this intermediate road that has no
                 // sidewalks at all. There might be a few in the way -- think highway onramps.
@@ -404,18 +507,18 @@ fn make_walking_turns(
                     lanes,
                     abstutil::wraparound_get(&roads, (idx1 as isize) - 2).outgoing_lanes(i.id),
                 ) {
-                    result.extend(make_crosswalks(i.id, l1, l2));
+                    result.extend(make_crosswalks(i.id, l1, l2, all_roads));
                 } else if let Some(l2) = get_sidewalk(
                     lanes,
                     abstutil::wraparound_get(&roads, (idx1 as isize) - 2).incoming_lanes(i.id),
                 ) {
-                    result.extend(make_crosswalks(i.id, l1, l2));
+                    result.extend(make_crosswalks(i.id, l1, l2, all_roads));
                 } else if roads.len() > 3 {
                     if let Some(l2) = get_sidewalk(
                         lanes,
                         abstutil::wraparound_get(&roads, (idx1 as isize) - 3).outgoing_lanes(i.id),
                     ) {
-                        result.extend(make_crosswalks(i.id, l1, l2));
+                        result.extend(make_crosswalks(i.id, l1, l2, all_roads));
                     }
                 }
             }
@@ -425,7 +528,7 @@ fn make_walking_turns(
     result
 }
 
-fn make_crosswalks(i: IntersectionID, l1: &Lane, l2: &Lane) -> Vec<Turn> {
+fn make_crosswalks(i: IntersectionID, l1: &Lane, l2: &Lane, roads: &Vec<Road>) -> Vec<Turn> {
     let l1_pt = l1.endpoint(i);
     let l2_pt = l2.endpoint(i);
     if l1_pt == l2_pt {
@@ -441,27 +544,17 @@ fn make_crosswalks(i: IntersectionID, l1: &Lane, l2: &Lane) -> Vec<Turn> {
     let line = Line::new(l1_pt, l2_pt).shift_either_direction(direction * LANE_THICKNESS / 2.0);
     let geom_fwds = PolyLine::new(vec![l1_pt, line.pt1(), line.pt2(), l2_pt]);
 
-    vec![
-        Turn {
-            id: turn_id(i, l1.id, l2.id),
-            turn_type: TurnType::Crosswalk,
-            other_crosswalk_ids: vec![turn_id(i, l2.id, l1.id)].into_iter().collect(),
-            geom: geom_fwds.clone(),
-            lookup_idx: 0,
-        },
-        Turn {
-            id: turn_id(i, l2.id, l1.id),
-            turn_type: TurnType::Crosswalk,
-            other_crosswalk_ids: vec![turn_id(i, l1.id, l2.id)].into_iter().collect(),
-            geom: geom_fwds.reversed(),
-            lookup_idx: 0,
-        },
-    ]
+    let mut fwds = new_turn(i, l1, l2, TurnType::Crosswalk, geom_fwds.clone(), roads);
+    fwds.other_crosswalk_ids.insert(turn_id(i, l2.id, l1.id));
+    let mut back = new_turn(i, l2, l1, TurnType::Crosswalk, geom_fwds.reversed(), roads);
+    back.other_crosswalk_ids.insert(turn_id(i, l1.id, l2.id));
+    vec![fwds, back]
 }
 
 // Only one physical crosswalk for degenerate intersections, right in the middle.
 fn make_degenerate_crosswalks(
     i: IntersectionID,
+    roads: &Vec<Road>,
     lanes: &Vec<Lane>,
     r1: &Road,
     r2: &Road,
@@ -486,37 +579,42 @@ fn make_degenerate_crosswalks(
 
     Some(
         vec![
-            Turn {
-                id: turn_id(i, l1_in.id, l1_out.id),
-                turn_type: TurnType::Crosswalk,
-                other_crosswalk_ids: all_ids.clone(),
-                geom: PolyLine::new(vec![l1_in.last_pt(), pt1, pt2, l1_out.first_pt()]),
-                lookup_idx: 0,
-            },
-            Turn {
-                id: turn_id(i, l1_out.id, l1_in.id),
-                turn_type: TurnType::Crosswalk,
-                other_crosswalk_ids: all_ids.clone(),
-                geom: PolyLine::new(vec![l1_out.first_pt(), pt2, pt1, l1_in.last_pt()]),
-                lookup_idx: 0,
-            },
-            Turn {
-                id: turn_id(i, l2_in.id, l2_out.id),
-                turn_type: TurnType::Crosswalk,
-                other_crosswalk_ids: all_ids.clone(),
-                geom: PolyLine::new(vec![l2_in.last_pt(), pt2, pt1, l2_out.first_pt()]),
-                lookup_idx: 0,
-            },
-            Turn {
-                id: turn_id(i, l2_out.id, l2_in.id),
-                turn_type: TurnType::Crosswalk,
-                other_crosswalk_ids: all_ids.clone(),
-                geom: PolyLine::new(vec![l2_out.first_pt(), pt1, pt2, l2_in.last_pt()]),
-                lookup_idx: 0,
-            },
+            new_turn(
+                i,
+                l1_in,
+                l1_out,
+                TurnType::Crosswalk,
+                PolyLine::new(vec![l1_in.last_pt(), pt1, pt2, l1_out.first_pt()]),
+                roads,
+            ),
+            new_turn(
+                i,
+                l1_out,
+                l1_in,
+                TurnType::Crosswalk,
+                PolyLine::new(vec![l1_out.first_pt(), pt2, pt1, l1_in.last_pt()]),
+                roads,
+            ),
+            new_turn(
+                i,
+                l2_in,
+                l2_out,
+                TurnType::Crosswalk,
+                PolyLine::new(vec![l2_in.last_pt(), pt2, pt1, l2_out.first_pt()]),
+                roads,
+            ),
+            new_turn(
+                i,
+                l2_out,
+                l2_in,
+                TurnType::Crosswalk,
+                PolyLine::new(vec![l2_out.first_pt(), pt1, pt2, l2_in.last_pt()]),
+                roads,
+            ),
         ]
         .into_iter()
         .map(|mut t| {
+            t.other_crosswalk_ids = all_ids.clone();
             t.other_crosswalk_ids.remove(&t.id);
             t
         })
@@ -610,6 +708,28 @@ fn turn_id(parent: IntersectionID, src: LaneID, dst: LaneID) -> TurnID {
     TurnID { parent, src, dst }
 }
 
+// Every turn is built here, so the z-order of both ends is filled out in one place.
+fn new_turn(
+    i: IntersectionID,
+    src: &Lane,
+    dst: &Lane,
+    turn_type: TurnType,
+    geom: PolyLine,
+    roads: &Vec<Road>,
+) -> Turn {
+    Turn {
+        id: turn_id(i, src.id, dst.id),
+        turn_type,
+        other_crosswalk_ids: BTreeSet::new(),
+        geom,
+        lookup_idx: 0,
+        zorder: (
+            roads[src.parent.0].get_zorder(),
+            roads[dst.parent.0].get_zorder(),
+        ),
+    }
+}
+
 fn get_sidewalk<'a>(lanes: &'a Vec<Lane>, children: &Vec<(LaneID, LaneType)>) -> Option<&'a Lane> {
     for (id, lt) in children {
         if *lt == LaneType::Sidewalk {
@@ -635,6 +755,7 @@ fn filter_lanes(lanes: &Vec<(LaneID, LaneType)>, filter: LaneType) -> Vec<LaneID
 }
 
 fn make_vehicle_turn(
+    roads: &Vec<Road>,
     lanes: &Vec<Lane>,
     i: IntersectionID,
     l1: LaneID,
@@ -677,13 +798,7 @@ fn make_vehicle_turn(
         PolyLine::new(curve)
     };
 
-    Some(Turn {
-        id: turn_id(i, l1, l2),
-        turn_type,
-        other_crosswalk_ids: BTreeSet::new(),
-        geom,
-        lookup_idx: 0,
-    })
+    Some(new_turn(i, src, dst, turn_type, geom, roads))
 }
 
 fn to_pt(pt: Pt2D) -> Point2d<f64> {
@@ -694,10 +809,9 @@ fn from_pt(pt: Point2d<f64>) -> Pt2D {
     Pt2D::new(pt.x, pt.y)
 }
 
-fn is_turn_allowed(turn: &Turn, roads: &Vec<Road>, lanes: &Vec<Lane>) -> bool {
+fn is_turn_allowed(turn: &Turn, lanes: &Vec<Lane>) -> bool {
     let l = &lanes[turn.id.src.0];
-    let r = &roads[l.parent.0];
-    if let Some(types) = l.get_turn_restrictions(r) {
+    if let Some(ref types) = l.allowed_turns {
         types.contains(&turn.turn_type)
     } else {
         true
@@ -1,10 +1,12 @@
+use crate::merges;
 use crate::pathfind::Pathfinder;
 use crate::raw::{OriginalIntersection, OriginalRoad, RawMap};
 use crate::{
     connectivity, make, Area, AreaID, Building, BuildingID, BusRoute, BusRouteID, BusStop,
-    BusStopID, ControlStopSign, ControlTrafficSignal, EditCmd, EditEffects, Intersection,
-    IntersectionID, IntersectionType, Lane, LaneID, LaneType, MapEdits, Path, PathConstraints,
-    PathRequest, Position, Road, RoadID, Turn, TurnGroupID, TurnID, TurnType, LANE_THICKNESS,
+    BusStopID, ControlMerge, ControlRoundabout, ControlStopSign, ControlTrafficSignal, EditCmd,
+    EditEffects, Intersection, IntersectionID, IntersectionType, Lane, LaneID, LaneType, MapEdits,
+    Path, PathConstraints, PathRequest, Position, Road, RoadID, Turn, TurnGroupID, TurnID,
+    TurnType, LANE_THICKNESS,
 };
 use abstutil::{deserialize_btreemap, serialize_btreemap, Error, Timer};
 use geom::{Bounds, Distance, GPSBounds, Polygon, Pt2D};
@@ -34,6 +36,8 @@ pub struct Map {
     // Note that border nodes belong in neither!
     stop_signs: BTreeMap<IntersectionID, ControlStopSign>,
     traffic_signals: BTreeMap<IntersectionID, ControlTrafficSignal>,
+    roundabouts: BTreeMap<IntersectionID, ControlRoundabout>,
+    merges: BTreeMap<IntersectionID, ControlMerge>,
 
     gps_bounds: GPSBounds,
     bounds: Bounds,
@@ -86,6 +90,8 @@ impl Map {
             ]),
             stop_signs: BTreeMap::new(),
             traffic_signals: BTreeMap::new(),
+            roundabouts: BTreeMap::new(),
+            merges: BTreeMap::new(),
             gps_bounds: GPSBounds::new(),
             bounds: Bounds::new(),
             turn_lookup: Vec::new(),
@@ -114,6 +120,8 @@ impl Map {
             let mut stop_signs: BTreeMap<IntersectionID, ControlStopSign> = BTreeMap::new();
             let mut traffic_signals: BTreeMap<IntersectionID, ControlTrafficSignal> =
                 BTreeMap::new();
+            let mut roundabouts: BTreeMap<IntersectionID, ControlRoundabout> = BTreeMap::new();
+            let mut merges: BTreeMap<IntersectionID, ControlMerge> = BTreeMap::new();
             for i in &m.intersections {
                 match i.intersection_type {
                     IntersectionType::StopSign => {
@@ -122,11 +130,19 @@ impl Map {
                     IntersectionType::TrafficSignal => {
                         traffic_signals.insert(i.id, ControlTrafficSignal::new(&m, i.id, timer));
                     }
+                    IntersectionType::Roundabout => {
+                        roundabouts.insert(i.id, ControlRoundabout::new(&m, i.id));
+                    }
+                    IntersectionType::Merge => {
+                        merges.insert(i.id, ControlMerge::new(&m, i.id));
+                    }
                     IntersectionType::Border | IntersectionType::Construction => {}
                 };
             }
             m.stop_signs = stop_signs;
             m.traffic_signals = traffic_signals;
+            m.roundabouts = roundabouts;
+            m.merges = merges;
         }
 
         // Here's a fun one: we can't set up walking_using_transit yet, because we haven't
@@ -263,6 +279,14 @@ impl Map {
         self.traffic_signals.get(&id)
     }
 
+    pub fn maybe_get_roundabout(&self, id: IntersectionID) -> Option<&ControlRoundabout> {
+        self.roundabouts.get(&id)
+    }
+
+    pub fn maybe_get_merge(&self, id: IntersectionID) -> Option<&ControlMerge> {
+        self.merges.get(&id)
+    }
+
     pub fn get_r(&self, id: RoadID) -> &Road {
         &self.roads[id.0]
     }
@@ -295,6 +319,14 @@ impl Map {
         &self.traffic_signals[&id]
     }
 
+    pub fn get_roundabout(&self, id: IntersectionID) -> &ControlRoundabout {
+        &self.roundabouts[&id]
+    }
+
+    pub fn get_merge(&self, id: IntersectionID) -> &ControlMerge {
+        &self.merges[&id]
+    }
+
     pub fn lookup_turn_by_idx(&self, idx: usize) -> Option<TurnID> {
         self.turn_lookup.get(idx).cloned()
     }
@@ -756,6 +788,8 @@ fn make_half_map(
         boundary_polygon: raw.boundary_polygon.clone(),
         stop_signs: BTreeMap::new(),
         traffic_signals: BTreeMap::new(),
+        roundabouts: BTreeMap::new(),
+        merges: BTreeMap::new(),
         gps_bounds,
         bounds,
         turn_lookup: Vec::new(),
@@ -860,6 +894,7 @@ fn make_half_map(
                 building_paths: Vec::new(),
                 bus_stops: Vec::new(),
                 parking_blackhole: None,
+                allowed_turns: None,
             });
         }
         if road.get_name() == "???" {
@@ -871,10 +906,22 @@ fn make_half_map(
         map.roads.push(road);
     }
 
+    for r in &map.roads {
+        for (l, types) in make::get_lane_turn_restrictions(r).get(timer) {
+            map.lanes[l.0].allowed_turns = Some(types);
+        }
+    }
+
     for i in map.intersections.iter_mut() {
         if is_border(i, &map.lanes) {
             i.intersection_type = IntersectionType::Border;
         }
+        // OSM doesn't tag these specially, so detect them. Leave signalized ramp meters alone.
+        if i.intersection_type == IntersectionType::StopSign
+            && merges::is_merge(i.id, &i.roads.iter().map(|r| &map.roads[r.0]).collect())
+        {
+            i.intersection_type = IntersectionType::Merge;
+        }
         if i.is_border() {
             if i.roads.len() != 1 {
                 panic!(
@@ -996,6 +1043,7 @@ impl EditCmd {
                 effects.changed_intersections.insert(lane.src_i);
                 effects.changed_intersections.insert(lane.dst_i);
                 let (src_i, dst_i) = (lane.src_i, lane.dst_i);
+                update_turn_restrictions(lane.parent, map, timer);
                 recalculate_turns(src_i, map, effects, timer);
                 recalculate_turns(dst_i, map, effects, timer);
                 true
@@ -1042,6 +1090,7 @@ impl EditCmd {
                 effects.changed_intersections.insert(lane.src_i);
                 effects.changed_intersections.insert(lane.dst_i);
                 let (src_i, dst_i) = (lane.src_i, lane.dst_i);
+                update_turn_restrictions(r.id, map, timer);
                 recalculate_turns(src_i, map, effects, timer);
                 recalculate_turns(dst_i, map, effects, timer);
                 true
@@ -1072,8 +1121,11 @@ impl EditCmd {
                 map.intersections[id.0].intersection_type = IntersectionType::Construction;
                 map.stop_signs.remove(id);
                 map.traffic_signals.remove(id);
+                map.roundabouts.remove(id);
+                map.merges.remove(id);
                 effects.changed_intersections.insert(*id);
                 recalculate_turns(*id, map, effects, timer);
+                recalculate_roundabouts(*id, map);
                 true
             }
             EditCmd::UncloseIntersection(id, orig_it) => {
@@ -1093,8 +1145,14 @@ impl EditCmd {
                         map.traffic_signals
                             .insert(id, ControlTrafficSignal::new(map, id, timer));
                     }
+                    // recalculate_roundabouts handles these
+                    IntersectionType::Roundabout => {}
+                    IntersectionType::Merge => {
+                        map.merges.insert(id, ControlMerge::new(map, id));
+                    }
                     IntersectionType::Border | IntersectionType::Construction => unreachable!(),
                 }
+                recalculate_roundabouts(id, map);
                 effects.changed_intersections.insert(id);
                 true
             }
@@ -1142,6 +1200,17 @@ impl EditCmd {
     }
 }
 
+// Lane edits shift which lane each part of turn:lanes refers to.
+fn update_turn_restrictions(r: RoadID, map: &mut Map, timer: &mut Timer) {
+    let road = &map.roads[r.0];
+    for l in road.all_lanes() {
+        map.lanes[l.0].allowed_turns = None;
+    }
+    for (l, types) in make::get_lane_turn_restrictions(road).get(timer) {
+        map.lanes[l.0].allowed_turns = Some(types);
+    }
+}
+
 // This clobbers previously set traffic signal overrides.
 // TODO Step 1: Detect and warn about that
 // TODO Step 2: Avoid when possible
@@ -1183,6 +1252,12 @@ fn recalculate_turns(
     match i.intersection_type {
         // Stop sign policy doesn't depend on incoming lane types. Leave edits alone.
         IntersectionType::StopSign => {}
+        IntersectionType::Roundabout => {
+            map.roundabouts.insert(id, ControlRoundabout::new(map, id));
+        }
+        IntersectionType::Merge => {
+            map.merges.insert(id, ControlMerge::new(map, id));
+        }
         IntersectionType::TrafficSignal => {
             map.traffic_signals
                 .insert(id, ControlTrafficSignal::new(map, id, timer));
@@ -1190,3 +1265,20 @@ fn recalculate_turns(
         IntersectionType::Border | IntersectionType::Construction => unreachable!(),
     }
 }
+
+// Every intersection of a roundabout remembers the whole ring, so closing or reopening one of them
+// changes the others too.
+fn recalculate_roundabouts(changed: IntersectionID, map: &mut Map) {
+    let mut stale: BTreeSet<IntersectionID> = map
+        .roundabouts
+        .values()
+        .filter(|r| r.members.contains(&changed))
+        .map(|r| r.id)
+        .collect();
+    if map.get_i(changed).is_roundabout() {
+        stale.extend(ControlRoundabout::new(map, changed).members);
+    }
+    for i in stale {
+        map.roundabouts.insert(i, ControlRoundabout::new(map, i));
+    }
+}
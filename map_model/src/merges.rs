@@ -0,0 +1,93 @@
+use crate::{IntersectionID, LaneID, LaneType, Map, Road, RoadID, TurnID, TurnPriority};
+use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+// Where freeway ramps join or leave the mainline. Traffic on the mainline never stops; vehicles
+// coming from an on-ramp yield, merging once there's a big enough gap in the lane they're joining.
+// Off-ramps just split traffic, so nobody has to yield there.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ControlMerge {
+    pub id: IntersectionID,
+    // Incoming roads that have to yield to the mainline. Empty for off-ramps.
+    pub yielding_roads: BTreeSet<RoadID>,
+}
+
+impl ControlMerge {
+    pub fn new(map: &Map, id: IntersectionID) -> ControlMerge {
+        let incoming: Vec<(&Road, Vec<LaneID>)> = map
+            .get_i(id)
+            .roads
+            .iter()
+            .map(|r| {
+                let road = map.get_r(*r);
+                (road, vehicle_lanes(road.incoming_lanes(id)))
+            })
+            .filter(|(_, lanes)| !lanes.is_empty())
+            .collect();
+
+        let mut yielding_roads = BTreeSet::new();
+        if incoming.len() > 1 {
+            let mainline = pick_mainline(&incoming);
+            for (r, _) in incoming {
+                if r.id != mainline {
+                    yielding_roads.insert(r.id);
+                }
+            }
+        }
+        ControlMerge { id, yielding_roads }
+    }
+
+    pub fn get_priority(&self, turn: TurnID, map: &Map) -> TurnPriority {
+        if self.yielding_roads.contains(&map.get_l(turn.src).parent) {
+            TurnPriority::Yield
+        } else {
+            TurnPriority::Protected
+        }
+    }
+}
+
+// Is this a place where freeway ramps join or split off from the mainline? Any number of ramps can
+// meet at once, as long as traffic there only flows together or only flows apart.
+pub(crate) fn is_merge(i: IntersectionID, roads: &Vec<&Road>) -> bool {
+    if roads.len() < 3
+        || !roads.iter().all(|r| {
+            r.is_freeway() && (r.children_forwards.is_empty() || r.children_backwards.is_empty())
+        })
+    {
+        return false;
+    }
+    let incoming = roads
+        .iter()
+        .filter(|r| !vehicle_lanes(r.incoming_lanes(i)).is_empty())
+        .count();
+    let outgoing = roads
+        .iter()
+        .filter(|r| !vehicle_lanes(r.outgoing_lanes(i)).is_empty())
+        .count();
+    incoming + outgoing == roads.len() && (incoming == 1) != (outgoing == 1)
+}
+
+// Out of the roads feeding into or out of a merge, which one continues the mainline? Prefer the
+// "bigger" road, then the one with more lanes.
+pub(crate) fn pick_mainline(roads: &Vec<(&Road, Vec<LaneID>)>) -> RoadID {
+    roads
+        .iter()
+        .max_by_key(|(r, lanes)| (r.get_rank(), lanes.len()))
+        .unwrap()
+        .0
+        .id
+}
+
+// Ordered from the left-most lane to the right-most
+pub(crate) fn vehicle_lanes(lanes: &Vec<(LaneID, LaneType)>) -> Vec<LaneID> {
+    lanes
+        .iter()
+        .filter_map(|(id, lt)| {
+            if lt.is_for_moving_vehicles() {
+                Some(*id)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
@@ -9,6 +9,10 @@ pub const PARKING_RIGHT: &str = "parking:lane:right";
 pub const PARKING_LEFT: &str = "parking:lane:left";
 pub const PARKING_BOTH: &str = "parking:lane:both";
 pub const SIDEWALK: &str = "sidewalk";
+pub const JUNCTION: &str = "junction";
+pub const LAYER: &str = "layer";
+pub const BRIDGE: &str = "bridge";
+pub const TUNNEL: &str = "tunnel";
 
 // The rest of these are all inserted by A/B Street to plumb data between different stages of map
 // construction. They could be plumbed another way, but this is the most convenient.
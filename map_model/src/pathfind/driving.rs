@@ -93,31 +93,40 @@ fn make_input_graph(
     constraints: PathConstraints,
 ) -> InputGraph {
     let mut input_graph = InputGraph::new();
-    let num_lanes = map.all_lanes().len();
+    let last_lane = LaneID(map.all_lanes().len() - 1);
+    let mut last_lane_used = false;
+    for (l, turn) in vehicle_movements(map, constraints) {
+        input_graph.add_edge(
+            nodes.get(l.id),
+            nodes.get(turn.id.dst),
+            cost(l, turn, constraints, map),
+        );
+        last_lane_used |= l.id == last_lane;
+    }
+    // The nodes in the graph MUST exactly be all of the lanes, so we can reuse node
+    // ordering later. If the last lane doesn't have any edges, then this won't work. So
+    // pretend like it points to some arbitrary other node. Since no paths will start from
+    // this unused node, this won't affect results.
+    // TODO Upstream a method in InputGraph to do this more clearly.
+    if !last_lane_used {
+        input_graph.add_edge(nodes.get(last_lane), nodes.get(LaneID(0)), 1);
+    }
+    input_graph.freeze();
+    input_graph
+}
+
+// Every lane a vehicle can use, paired with each turn it can take from there. TravelTimeGraph
+// searches the same movements.
+pub(crate) fn vehicle_movements(map: &Map, constraints: PathConstraints) -> Vec<(&Lane, &Turn)> {
+    let mut movements = Vec::new();
     for l in map.all_lanes() {
-        let from = nodes.get(l.id);
-        let mut any = false;
         if constraints.can_use(l, map) {
             for turn in map.get_turns_for(l.id, constraints) {
-                any = true;
-                input_graph.add_edge(
-                    from,
-                    nodes.get(turn.id.dst),
-                    cost(l, turn, constraints, map),
-                );
+                movements.push((l, turn));
             }
         }
-        // The nodes in the graph MUST exactly be all of the lanes, so we can reuse node
-        // ordering later. If the last lane doesn't have any edges, then this won't work. So
-        // pretend like it points to some arbitrary other node. Since no paths will start from
-        // this unused node, this won't affect results.
-        // TODO Upstream a method in InputGraph to do this more clearly.
-        if !any && l.id.0 == num_lanes - 1 {
-            input_graph.add_edge(from, nodes.get(LaneID(0)), 1);
-        }
     }
-    input_graph.freeze();
-    input_graph
+    movements
 }
 
 pub fn cost(lane: &Lane, turn: &Turn, constraints: PathConstraints, map: &Map) -> usize {
@@ -1,9 +1,12 @@
 mod driving;
 mod node_map;
+mod travel_times;
 mod walking;
 
 pub use self::driving::cost;
 use self::driving::VehiclePathfinder;
+pub(crate) use self::travel_times::bus_ride_time;
+pub use self::travel_times::TravelTimeGraph;
 use self::walking::SidewalkPathfinder;
 use crate::{
     osm, BusRouteID, BusStopID, Lane, LaneID, LaneType, Map, Position, Traversable, TurnID,
@@ -0,0 +1,160 @@
+use crate::pathfind::driving::vehicle_movements;
+use crate::pathfind::walking::{walking_edges, Node as WalkingNode};
+use crate::{
+    BuildingID, BusStopID, LaneID, LaneType, Map, PathConstraints, PathRequest, Traversable,
+};
+use geom::{Duration, Speed};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap, HashMap};
+
+// About 10mph
+const BIKING_SPEED: Speed = Speed::const_meters_per_second(4.47);
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+enum Node {
+    Lane(LaneID),
+    Walking(WalkingNode),
+}
+
+// The same graph that pathfinding uses, but with travel times as costs, to quickly find everything
+// reachable from one place. The contraction hierarchies only answer one query at a time, so this
+// does a plain search instead. Ignores delays at intersections and from other traffic.
+pub struct TravelTimeGraph {
+    constraints: PathConstraints,
+    edges: HashMap<Node, Vec<(Node, Duration)>>,
+}
+
+impl TravelTimeGraph {
+    // For pedestrians, transit_waits says how long people wait to board at each stop. None means
+    // to walk only.
+    pub fn new(
+        map: &Map,
+        constraints: PathConstraints,
+        transit_waits: Option<&BTreeMap<BusStopID, Duration>>,
+    ) -> TravelTimeGraph {
+        let mut edges: HashMap<Node, Vec<(Node, Duration)>> = HashMap::new();
+        if constraints == PathConstraints::Pedestrian {
+            let ride_time = |stop1, stop2| bus_ride_time(map, stop1, stop2);
+            for (from, to, mut time) in walking_edges(map, transit_waits.is_some(), &ride_time) {
+                if let (WalkingNode::SidewalkEndpoint(_, _), WalkingNode::RideBus(stop)) =
+                    (from, to)
+                {
+                    time += transit_waits
+                        .and_then(|waits| waits.get(&stop).cloned())
+                        .unwrap_or(Duration::ZERO);
+                }
+                edges
+                    .entry(Node::Walking(from))
+                    .or_insert_with(Vec::new)
+                    .push((Node::Walking(to), time));
+            }
+        } else {
+            for (l, turn) in vehicle_movements(map, constraints) {
+                let time = traversal_time(Traversable::Turn(turn.id), constraints, map)
+                    + traversal_time(Traversable::Lane(turn.id.dst), constraints, map);
+                edges
+                    .entry(Node::Lane(l.id))
+                    .or_insert_with(Vec::new)
+                    .push((Node::Lane(turn.id.dst), time));
+            }
+        }
+        TravelTimeGraph { constraints, edges }
+    }
+
+    // Where trips from or to a building join this graph. Ignores the path between the building
+    // and the road.
+    pub fn start_lane(&self, map: &Map, b: BuildingID) -> Option<LaneID> {
+        self.nearest_lane(map, map.get_b(b).sidewalk())
+    }
+
+    // The lane itself if it can be used, otherwise the closest one on the same road
+    pub fn nearest_lane(&self, map: &Map, l: LaneID) -> Option<LaneID> {
+        if self.constraints.can_use(map.get_l(l), map) {
+            return Some(l);
+        }
+        let types = match self.constraints {
+            PathConstraints::Pedestrian => vec![LaneType::Sidewalk],
+            PathConstraints::Bike => vec![LaneType::Biking, LaneType::Driving],
+            PathConstraints::Car => vec![LaneType::Driving],
+            PathConstraints::Bus => vec![LaneType::Bus, LaneType::Driving],
+        };
+        map.find_closest_lane(l, types).ok()
+    }
+
+    // How long it takes to reach every lane within the time limit, starting somewhere along the
+    // start lane.
+    pub fn travel_times(&self, start: LaneID, limit: Duration) -> HashMap<LaneID, Duration> {
+        let mut best: HashMap<Node, Duration> = HashMap::new();
+        let mut queue: BinaryHeap<Reverse<(Duration, usize)>> = BinaryHeap::new();
+        // BinaryHeap needs Ord, so refer to nodes by index
+        let mut nodes: Vec<Node> = Vec::new();
+        let starts = if self.constraints == PathConstraints::Pedestrian {
+            vec![
+                Node::Walking(WalkingNode::SidewalkEndpoint(start, true)),
+                Node::Walking(WalkingNode::SidewalkEndpoint(start, false)),
+            ]
+        } else {
+            vec![Node::Lane(start)]
+        };
+        for node in starts {
+            best.insert(node, Duration::ZERO);
+            nodes.push(node);
+            queue.push(Reverse((Duration::ZERO, nodes.len() - 1)));
+        }
+
+        while let Some(Reverse((cost, idx))) = queue.pop() {
+            let current = nodes[idx];
+            if cost > best[&current] {
+                continue;
+            }
+            for (next, dt) in self.edges.get(&current).into_iter().flatten() {
+                let next_cost = cost + *dt;
+                if next_cost > limit {
+                    continue;
+                }
+                if best.get(next).map(|c| next_cost < *c).unwrap_or(true) {
+                    best.insert(*next, next_cost);
+                    nodes.push(*next);
+                    queue.push(Reverse((next_cost, nodes.len() - 1)));
+                }
+            }
+        }
+
+        // A sidewalk counts as reached as soon as either end is
+        let mut results: HashMap<LaneID, Duration> = HashMap::new();
+        for (node, cost) in best {
+            let l = match node {
+                Node::Lane(l) | Node::Walking(WalkingNode::SidewalkEndpoint(l, _)) => l,
+                Node::Walking(WalkingNode::RideBus(_)) => continue,
+            };
+            let entry = results.entry(l).or_insert(cost);
+            *entry = (*entry).min(cost);
+        }
+        results
+    }
+}
+
+// How long a bus takes between two stops, going at the speed limit
+pub(crate) fn bus_ride_time(map: &Map, stop1: BusStopID, stop2: BusStopID) -> Option<Duration> {
+    let path = map.pathfind(PathRequest {
+        start: map.get_bs(stop1).driving_pos,
+        end: map.get_bs(stop2).driving_pos,
+        constraints: PathConstraints::Bus,
+    })?;
+    Some(
+        path.get_steps()
+            .iter()
+            .map(|step| traversal_time(step.as_traversable(), PathConstraints::Bus, map))
+            .fold(Duration::ZERO, |a, b| a + b),
+    )
+}
+
+fn traversal_time(t: Traversable, constraints: PathConstraints, map: &Map) -> Duration {
+    let speed = match constraints {
+        // Walking edges already come with a time
+        PathConstraints::Pedestrian => unreachable!(),
+        PathConstraints::Bike => BIKING_SPEED.min(t.speed_limit(map)),
+        PathConstraints::Car | PathConstraints::Bus => t.speed_limit(map),
+    };
+    t.length(map) / speed
+}
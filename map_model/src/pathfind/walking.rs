@@ -4,7 +4,7 @@ use crate::{
     BusRouteID, BusStopID, LaneID, Map, Path, PathConstraints, PathRequest, PathStep, Position,
 };
 use fast_paths::{FastGraph, InputGraph, PathCalculator};
-use geom::{Distance, Speed};
+use geom::{Duration, Speed};
 use serde_derive::{Deserialize, Serialize};
 use std::cell::RefCell;
 use thread_local::ThreadLocal;
@@ -20,8 +20,10 @@ pub struct SidewalkPathfinder {
     path_calc: ThreadLocal<RefCell<PathCalculator>>,
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
-enum Node {
+const WALKING_SPEED: Speed = Speed::const_meters_per_second(1.34);
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
+pub(crate) enum Node {
     // false is src_i, true is dst_i
     SidewalkEndpoint(LaneID, bool),
     RideBus(BusStopID),
@@ -202,14 +204,55 @@ fn make_input_graph(
     bus_graph: &VehiclePathfinder,
 ) -> InputGraph {
     let mut input_graph = InputGraph::new();
+    // Optimistically assume no waiting time at a stop.
+    let ride_time = |stop1: BusStopID, stop2: BusStopID| {
+        let driving_cost = bus_graph
+            .pathfind(
+                &PathRequest {
+                    start: map.get_bs(stop1).driving_pos,
+                    end: map.get_bs(stop2).driving_pos,
+                    constraints: PathConstraints::Bus,
+                },
+                map,
+            )
+            .unwrap()
+            .1;
+        Some(Duration::seconds(driving_cost as f64))
+    };
+    for (from, to, time) in walking_edges(map, use_transit, &ride_time) {
+        let mut cost = to_s(time);
+        match (from, to) {
+            (Node::SidewalkEndpoint(_, _), Node::RideBus(_))
+            | (Node::RideBus(_), Node::SidewalkEndpoint(_, _)) => {
+                // Add some extra penalty (equivalent to 1m) to using a bus stop. Otherwise a path
+                // might try to pass through it uselessly.
+                cost += 100;
+            }
+            _ => {}
+        }
+        input_graph.add_edge(nodes.get(from), nodes.get(to), cost);
+    }
+    input_graph.freeze();
+    input_graph
+}
+
+// Every way to get between two nodes on foot, and how long it takes. With transit, stops are
+// connected to both ends of their sidewalk, and each adjacent stop along a route is connected
+// using ride_time, if the bus can get there. TravelTimeGraph searches the same edges.
+pub(crate) fn walking_edges(
+    map: &Map,
+    use_transit: bool,
+    ride_time: &dyn Fn(BusStopID, BusStopID) -> Option<Duration>,
+) -> Vec<(Node, Node, Duration)> {
+    let mut edges = Vec::new();
 
     for l in map.all_lanes() {
         if l.is_sidewalk() {
-            let cost = to_s(l.length());
-            let n1 = nodes.get(Node::SidewalkEndpoint(l.id, true));
-            let n2 = nodes.get(Node::SidewalkEndpoint(l.id, false));
-            input_graph.add_edge(n1, n2, cost);
-            input_graph.add_edge(n2, n1, cost);
+            let time = l.length() / WALKING_SPEED;
+            let n1 = Node::SidewalkEndpoint(l.id, true);
+            let n2 = Node::SidewalkEndpoint(l.id, false);
+            edges.push((n1, n2, time));
+            edges.push((n2, n1, time));
         }
     }
 
@@ -217,32 +260,29 @@ fn make_input_graph(
         if t.between_sidewalks() {
             let from = Node::SidewalkEndpoint(t.id.src, map.get_l(t.id.src).dst_i == t.id.parent);
             let to = Node::SidewalkEndpoint(t.id.dst, map.get_l(t.id.dst).dst_i == t.id.parent);
-            input_graph.add_edge(nodes.get(from), nodes.get(to), to_s(t.geom.length()));
+            edges.push((from, to, t.geom.length() / WALKING_SPEED));
         }
     }
 
     if use_transit {
         // Connect bus stops with both sidewalk endpoints, using the appropriate distance.
         for stop in map.all_bus_stops().values() {
-            let ride_bus = nodes.get(Node::RideBus(stop.id));
+            let ride_bus = Node::RideBus(stop.id);
             let lane = map.get_l(stop.sidewalk_pos.lane());
             for endpt in &[true, false] {
-                let cost = if *endpt {
-                    to_s(lane.length() - stop.sidewalk_pos.dist_along())
+                let dist = if *endpt {
+                    lane.length() - stop.sidewalk_pos.dist_along()
                 } else {
-                    to_s(stop.sidewalk_pos.dist_along())
+                    stop.sidewalk_pos.dist_along()
                 };
-                // Add some extra penalty (equivalent to 1m) to using a bus stop. Otherwise a path
-                // might try to pass through it uselessly.
-                let penalty = 100;
-                let sidewalk = nodes.get(Node::SidewalkEndpoint(lane.id, *endpt));
-                input_graph.add_edge(sidewalk, ride_bus, cost + penalty);
-                input_graph.add_edge(ride_bus, sidewalk, cost + penalty);
+                let sidewalk = Node::SidewalkEndpoint(lane.id, *endpt);
+                edges.push((sidewalk, ride_bus, dist / WALKING_SPEED));
+                edges.push((ride_bus, sidewalk, dist / WALKING_SPEED));
             }
         }
 
         // Connect each adjacent stop along a route, with the cost based on how long it'll take a
-        // bus to drive between the stops. Optimistically assume no waiting time at a stop.
+        // bus to drive between the stops.
         for route in map.get_all_bus_routes() {
             for (stop1, stop2) in
                 route
@@ -254,31 +294,16 @@ fn make_input_graph(
                         &route.stops[0],
                     )))
             {
-                let driving_cost = bus_graph
-                    .pathfind(
-                        &PathRequest {
-                            start: map.get_bs(*stop1).driving_pos,
-                            end: map.get_bs(*stop2).driving_pos,
-                            constraints: PathConstraints::Bus,
-                        },
-                        map,
-                    )
-                    .unwrap()
-                    .1;
-                input_graph.add_edge(
-                    nodes.get(Node::RideBus(*stop1)),
-                    nodes.get(Node::RideBus(*stop2)),
-                    driving_cost,
-                );
+                if let Some(time) = ride_time(*stop1, *stop2) {
+                    edges.push((Node::RideBus(*stop1), Node::RideBus(*stop2), time));
+                }
             }
         }
     }
-    input_graph.freeze();
-    input_graph
+
+    edges
 }
 
-fn to_s(dist: Distance) -> usize {
-    let walking_speed = Speed::meters_per_second(1.34);
-    let time = dist / walking_speed;
+fn to_s(time: Duration) -> usize {
     time.inner_seconds().round() as usize
 }
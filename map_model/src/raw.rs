@@ -1,4 +1,5 @@
 use crate::make::get_lane_types;
+use crate::road::zorder_from_tags;
 use crate::{osm, AreaType, IntersectionType, OffstreetParking, RoadSpec};
 use abstutil::{deserialize_btreemap, retain_btreemap, serialize_btreemap, Error, Timer};
 use geom::{GPSBounds, Polygon, Pt2D};
@@ -532,6 +533,10 @@ impl RawRoad {
     pub fn synthetic(&self) -> bool {
         self.osm_tags.get(osm::SYNTHETIC) == Some(&"true".to_string())
     }
+
+    pub fn get_zorder(&self) -> isize {
+        zorder_from_tags(&self.osm_tags)
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
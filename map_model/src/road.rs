@@ -232,11 +232,17 @@ impl Road {
 
     pub fn get_zorder(&self) -> isize {
         // TODO Should probably cache this
-        if let Some(layer) = self.osm_tags.get("layer") {
-            layer.parse::<isize>().unwrap()
-        } else {
-            0
-        }
+        zorder_from_tags(&self.osm_tags)
+    }
+
+    pub fn is_freeway(&self) -> bool {
+        self.osm_tags.get(osm::HIGHWAY) == Some(&"motorway".to_string())
+            || self.osm_tags.get(osm::HIGHWAY) == Some(&"motorway_link".to_string())
+    }
+
+    // Is this one of the segments making up the ring of a roundabout?
+    pub fn is_roundabout(&self) -> bool {
+        self.osm_tags.get(osm::JUNCTION) == Some(&"roundabout".to_string())
     }
 
     pub fn incoming_lanes(&self, i: IntersectionID) -> &Vec<(LaneID, LaneType)> {
@@ -407,3 +413,21 @@ impl Road {
         stops
     }
 }
+
+// Bridges and tunnels often don't bother with an explicit layer, so infer one. Some ways have junk
+// like "1;2" in there, so don't trust the layer to parse.
+pub(crate) fn zorder_from_tags(osm_tags: &BTreeMap<String, String>) -> isize {
+    if let Some(layer) = osm_tags
+        .get(osm::LAYER)
+        .and_then(|l| l.parse::<isize>().ok())
+    {
+        return layer;
+    }
+    if osm_tags.contains_key(osm::BRIDGE) && osm_tags.get(osm::BRIDGE) != Some(&"no".to_string()) {
+        return 1;
+    }
+    if osm_tags.contains_key(osm::TUNNEL) && osm_tags.get(osm::TUNNEL) != Some(&"no".to_string()) {
+        return -1;
+    }
+    0
+}
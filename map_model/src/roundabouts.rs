@@ -0,0 +1,66 @@
+use crate::{IntersectionID, Map, RoadID, TurnID, TurnPriority, TurnType};
+use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+// OSM models a roundabout as a ring of one-way ways tagged junction=roundabout, with a node
+// wherever another road enters or exits. Every one of those nodes becomes an intersection, but
+// they're all controlled the same way: vehicles already circulating have priority, and everybody
+// entering the ring yields to them. Each intersection gets its own ControlRoundabout, but it
+// records the whole ring, so it's easy to reason about the roundabout as one unit.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ControlRoundabout {
+    pub id: IntersectionID,
+    // All of the intersections along the ring, including this one.
+    pub members: BTreeSet<IntersectionID>,
+    // The one-way segments forming the ring itself.
+    pub ring_roads: BTreeSet<RoadID>,
+}
+
+impl ControlRoundabout {
+    pub fn new(map: &Map, id: IntersectionID) -> ControlRoundabout {
+        let mut members = BTreeSet::new();
+        let mut ring_roads = BTreeSet::new();
+
+        // Flood outwards along the ring.
+        let mut queue = vec![id];
+        while let Some(i) = queue.pop() {
+            if members.contains(&i) {
+                continue;
+            }
+            members.insert(i);
+            for r in &map.get_i(i).roads {
+                let road = map.get_r(*r);
+                if !road.is_roundabout() {
+                    continue;
+                }
+                ring_roads.insert(*r);
+                for next in vec![road.src_i, road.dst_i] {
+                    if !members.contains(&next) && map.get_i(next).is_roundabout() {
+                        queue.push(next);
+                    }
+                }
+            }
+        }
+
+        ControlRoundabout {
+            id,
+            members,
+            ring_roads,
+        }
+    }
+
+    pub fn get_priority(&self, turn: TurnID, map: &Map) -> TurnPriority {
+        match map.get_t(turn).turn_type {
+            TurnType::SharedSidewalkCorner => TurnPriority::Protected,
+            // Like stop signs, pedestrians crossing the entries and exits have the right-of-way.
+            TurnType::Crosswalk => TurnPriority::Protected,
+            _ => {
+                if self.ring_roads.contains(&map.get_l(turn.src).parent) {
+                    TurnPriority::Protected
+                } else {
+                    TurnPriority::Yield
+                }
+            }
+        }
+    }
+}
@@ -27,7 +27,7 @@ pub struct Phase {
 impl ControlTrafficSignal {
     pub fn new(map: &Map, id: IntersectionID, timer: &mut Timer) -> ControlTrafficSignal {
         let mut policies = ControlTrafficSignal::get_possible_policies(map, id);
-        if policies.len() == 1 {
+        if !ControlTrafficSignal::has_specialized_policy(map, id) {
             timer.warn(format!("Falling back to greedy_assignment for {}", id));
         }
         policies.remove(0).1
@@ -37,6 +37,24 @@ impl ControlTrafficSignal {
         map: &Map,
         id: IntersectionID,
     ) -> Vec<(String, ControlTrafficSignal)> {
+        let mut results = ControlTrafficSignal::specialized_policies(map, id);
+        results.push((
+            "arbitrary assignment".to_string(),
+            ControlTrafficSignal::greedy_assignment(map, id),
+        ));
+        results.push((
+            "all walk, then free-for-all yield".to_string(),
+            ControlTrafficSignal::all_walk_all_yield(map, id),
+        ));
+        results
+    }
+
+    // Does some policy fit this intersection's shape, or is it stuck with the generic fallbacks?
+    pub fn has_specialized_policy(map: &Map, id: IntersectionID) -> bool {
+        !ControlTrafficSignal::specialized_policies(map, id).is_empty()
+    }
+
+    fn specialized_policies(map: &Map, id: IntersectionID) -> Vec<(String, ControlTrafficSignal)> {
         let mut results = Vec::new();
         if let Some(ts) = ControlTrafficSignal::four_way_four_phase(map, id) {
             results.push(("four-phase".to_string(), ts));
@@ -56,14 +74,6 @@ impl ControlTrafficSignal {
         if let Some(ts) = ControlTrafficSignal::phase_per_road(map, id) {
             results.push(("phase per road".to_string(), ts));
         }
-        results.push((
-            "arbitrary assignment".to_string(),
-            ControlTrafficSignal::greedy_assignment(map, id),
-        ));
-        results.push((
-            "all walk, then free-for-all yield".to_string(),
-            ControlTrafficSignal::all_walk_all_yield(map, id),
-        ));
         results
     }
 
@@ -78,6 +78,8 @@ pub struct Turn {
 
     // Just for convenient debugging lookup.
     pub lookup_idx: usize,
+    // The z-order of the source and destination roads. Turns on a ramp span both.
+    pub zorder: (isize, isize),
 }
 
 impl Turn {
@@ -93,6 +95,9 @@ impl Turn {
         if self.between_sidewalks() && other.between_sidewalks() {
             return false;
         }
+        if !levels_overlap(self.zorder, other.zorder) {
+            return false;
+        }
 
         if self.geom.first_pt() == other.geom.first_pt() {
             return false;
@@ -133,6 +138,8 @@ pub struct TurnGroup {
     // The "overall" path of movement, aka, an "average" of the turn geometry
     pub geom: PolyLine,
     pub angle: Angle,
+    // Like Turn, the z-order of the source and destination roads.
+    pub zorder: (isize, isize),
 }
 
 impl TurnGroup {
@@ -158,6 +165,7 @@ impl TurnGroup {
                             members: vec![turn.id],
                             geom: turn.geom.clone(),
                             angle: turn.angle(),
+                            zorder: turn.zorder,
                         },
                     );
                 }
@@ -201,6 +209,7 @@ impl TurnGroup {
                     id,
                     turn_type: *turn_types.iter().next().unwrap(),
                     angle: map.get_t(members[0]).angle(),
+                    zorder: (map.get_r(from).get_zorder(), map.get_r(to).get_zorder()),
                     members,
                     geom,
                 },
@@ -270,10 +279,21 @@ impl TurnGroup {
         if self.id.to == other.id.to {
             return true;
         }
+        if !levels_overlap(self.zorder, other.zorder) {
+            return false;
+        }
         self.geom.intersection(&other.geom).is_some()
     }
 }
 
+// Movements entirely on different levels can't physically cross, even if their geometry does when
+// flattened.
+fn levels_overlap(z1: (isize, isize), z2: (isize, isize)) -> bool {
+    let (min1, max1) = (z1.0.min(z1.1), z1.0.max(z1.1));
+    let (min2, max2) = (z2.0.min(z2.1), z2.0.max(z2.1));
+    min1.max(min2) <= max1.min(max2)
+}
+
 fn turn_group_geom(polylines: Vec<&PolyLine>, from: RoadID, to: RoadID) -> PolyLine {
     let num_pts = polylines[0].points().len();
     for pl in &polylines {
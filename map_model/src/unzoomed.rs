@@ -0,0 +1,92 @@
+use crate::{AreaType, Intersection, Map, Road};
+use abstutil::Timer;
+use geom::Polygon;
+
+// The game and headless tools both draw the zoomed-out view of a map. They pick their own colors,
+// but should draw the same shapes in the same order.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum UnzoomedShape {
+    // The map boundary
+    Background,
+    Area(AreaType),
+    // Roads are grouped by their OSM rank. Intersections with stop signs use the rank of their
+    // biggest road, so they blend in.
+    HighwayRoad,
+    ArterialRoad,
+    ResidentialRoad,
+    // Traffic signals and borders stand out from the roads
+    InterestingIntersection,
+    Building,
+}
+
+impl UnzoomedShape {
+    fn road(rank: usize) -> UnzoomedShape {
+        if rank >= 16 {
+            UnzoomedShape::HighwayRoad
+        } else if rank >= 6 {
+            UnzoomedShape::ArterialRoad
+        } else {
+            UnzoomedShape::ResidentialRoad
+        }
+    }
+}
+
+// Everything, from the background up to buildings
+pub fn unzoomed_map<T>(
+    map: &Map,
+    color: &dyn Fn(UnzoomedShape) -> T,
+    timer: &mut Timer,
+) -> Vec<(T, Polygon)> {
+    let mut shapes = vec![(
+        color(UnzoomedShape::Background),
+        map.get_boundary_polygon().clone(),
+    )];
+    for a in map.all_areas() {
+        shapes.push((color(UnzoomedShape::Area(a.area_type)), a.polygon.clone()));
+    }
+    shapes.extend(unzoomed_roads(map, color, timer));
+    shapes.extend(unzoomed_intersections(map, color));
+    for b in map.all_buildings() {
+        shapes.push((color(UnzoomedShape::Building), b.polygon.clone()));
+    }
+    shapes
+}
+
+pub fn unzoomed_roads<T>(
+    map: &Map,
+    color: &dyn Fn(UnzoomedShape) -> T,
+    timer: &mut Timer,
+) -> Vec<(T, Polygon)> {
+    let mut road_refs: Vec<&Road> = map.all_roads().iter().collect();
+    road_refs.sort_by_key(|r| r.get_zorder());
+    road_refs
+        .into_iter()
+        .map(|r| {
+            (
+                color(UnzoomedShape::road(r.get_rank())),
+                r.get_thick_polygon().get(timer),
+            )
+        })
+        .collect()
+}
+
+pub fn unzoomed_intersections<T>(
+    map: &Map,
+    color: &dyn Fn(UnzoomedShape) -> T,
+) -> Vec<(T, Polygon)> {
+    let mut intersection_refs: Vec<&Intersection> = map.all_intersections().iter().collect();
+    intersection_refs.sort_by_key(|i| i.get_zorder(map));
+    intersection_refs
+        .into_iter()
+        .map(|i| {
+            // TODO Would be neat to show closed intersections here, but then edits need to
+            // regenerate this
+            let shape = if i.is_stop_sign() {
+                UnzoomedShape::road(i.get_rank(map))
+            } else {
+                UnzoomedShape::InterestingIntersection
+            };
+            (color(shape), i.polygon.clone())
+        })
+        .collect()
+}
@@ -0,0 +1,182 @@
+use crate::raw::{OriginalBuilding, OriginalIntersection, OriginalRoad, RawMap};
+use crate::{connectivity, ControlTrafficSignal, LaneID, Map, PathConstraints};
+use abstutil::Timer;
+use geom::{LonLat, Pt2D};
+use serde_derive::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+// Everything that's wrong with a map, gathered in one place instead of scattered across panics
+// and warnings while the map is built.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ValidationReport {
+    pub map_name: String,
+    pub issues: Vec<MapIssue>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MapIssue {
+    pub severity: Severity,
+    pub object: IssueObject,
+    // In map-space, so the map_editor (which works on the same RawMap) can jump right to it.
+    pub pt: Pt2D,
+    pub gps: LonLat,
+    pub description: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    // The map works, but something looks off.
+    Warning,
+    // Some part of the map isn't usable by the simulation.
+    Error,
+}
+
+// Refer to things by their original IDs, which survive rebuilding the map and are what the
+// map_editor uses.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum IssueObject {
+    Road(OriginalRoad),
+    // Lane IDs change whenever the map is rebuilt, so use the index into the road's lanes, going
+    // forwards and then backwards.
+    Lane(OriginalRoad, usize),
+    Intersection(OriginalIntersection),
+    Building(OriginalBuilding),
+    // Map::new didn't even finish, so there's nothing more specific to point at.
+    Map,
+}
+
+impl ValidationReport {
+    // The RawMap is needed to find things that didn't even make it into the Map.
+    pub fn new(map: &Map, raw: &RawMap, timer: &mut Timer) -> ValidationReport {
+        let mut report = ValidationReport {
+            map_name: map.get_name().to_string(),
+            issues: Vec::new(),
+        };
+
+        timer.start("validate road geometry");
+        for r in map.all_roads() {
+            for warning in r.get_thick_polyline().get_warnings() {
+                report.add(
+                    map,
+                    Severity::Warning,
+                    IssueObject::Road(r.orig_id),
+                    r.center_pts.middle(),
+                    format!("Bad thick polyline: {}", warning),
+                );
+            }
+        }
+        timer.stop("validate road geometry");
+
+        timer.start("validate connectivity");
+        for (constraints, name) in vec![
+            (PathConstraints::Car, "Driving"),
+            (PathConstraints::Bike, "Biking"),
+            (PathConstraints::Pedestrian, "Sidewalk"),
+        ] {
+            let (_, disconnected) = connectivity::find_scc(map, constraints);
+            let mut sorted: Vec<LaneID> = disconnected.into_iter().collect();
+            sorted.sort();
+            for l in sorted {
+                let lane = map.get_l(l);
+                let road = map.get_r(lane.parent);
+                let idx = road.all_lanes().iter().position(|x| *x == l).unwrap();
+                report.add(
+                    map,
+                    Severity::Error,
+                    IssueObject::Lane(road.orig_id, idx),
+                    lane.lane_center_pts.middle(),
+                    format!(
+                        "{} lane {} is disconnected from the rest of the map",
+                        name, l
+                    ),
+                );
+            }
+        }
+        timer.stop("validate connectivity");
+
+        timer.start("validate buildings");
+        let kept: HashSet<i64> = map.all_buildings().iter().map(|b| b.osm_way_id).collect();
+        for (id, b) in &raw.buildings {
+            if !kept.contains(&id.osm_way_id) {
+                report.add(
+                    map,
+                    Severity::Warning,
+                    IssueObject::Building(*id),
+                    b.polygon.center(),
+                    format!("{} isn't close enough to any sidewalk", id),
+                );
+            }
+        }
+        timer.stop("validate buildings");
+
+        timer.start("validate traffic signals");
+        for i in map.all_intersections() {
+            if !i.is_traffic_signal() {
+                continue;
+            }
+            if !ControlTrafficSignal::has_specialized_policy(map, i.id) {
+                report.add(
+                    map,
+                    Severity::Warning,
+                    IssueObject::Intersection(i.orig_id),
+                    i.polygon.center(),
+                    format!("{} falls back to greedy_assignment", i.id),
+                );
+            }
+        }
+        timer.stop("validate traffic signals");
+
+        report
+            .issues
+            .sort_by_key(|issue| std::cmp::Reverse(issue.severity));
+        timer.note(format!(
+            "{} errors, {} warnings",
+            report.count(Severity::Error),
+            report.count(Severity::Warning)
+        ));
+        report
+    }
+
+    // When Map::new panics, there's only the RawMap and the panic message to go on.
+    pub fn failed_to_build(raw: &RawMap, msg: String) -> ValidationReport {
+        let pt = raw.boundary_polygon.center();
+        ValidationReport {
+            map_name: raw.name.clone(),
+            issues: vec![MapIssue {
+                severity: Severity::Error,
+                object: IssueObject::Map,
+                pt,
+                gps: pt.forcibly_to_gps(&raw.gps_bounds),
+                description: format!("Map::new failed: {}", msg),
+            }],
+        }
+    }
+
+    pub fn count(&self, severity: Severity) -> usize {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == severity)
+            .count()
+    }
+
+    pub fn save(&self) {
+        abstutil::write_json(abstutil::path_map_validation(&self.map_name), self);
+    }
+
+    fn add(
+        &mut self,
+        map: &Map,
+        severity: Severity,
+        object: IssueObject,
+        pt: Pt2D,
+        description: String,
+    ) {
+        self.issues.push(MapIssue {
+            severity,
+            object,
+            pt,
+            gps: pt.forcibly_to_gps(map.get_gps_bounds()),
+            description,
+        });
+    }
+}
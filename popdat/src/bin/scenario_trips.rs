@@ -0,0 +1,45 @@
+use abstutil::{CmdArgs, Timer};
+use map_model::Map;
+use popdat::external::{export_scenario, import_scenario, read_csv, write_csv, ExternalTrip};
+use sim::Scenario;
+
+// Converts between Scenarios and a list of trips that doesn't depend on map IDs. The trip list
+// is a CSV or JSON file, depending on the extension.
+//
+// Export: scenario_trips --map=montlake --scenario=weekday --export=trips.csv
+// Import: scenario_trips --map=montlake --scenario=weekday --import=trips.csv
+fn main() {
+    let mut args = CmdArgs::new();
+    let map_name = args.required("--map");
+    let scenario_name = args.required("--scenario");
+    let export = args.optional("--export");
+    let import = args.optional("--import");
+    args.done();
+
+    let mut timer = Timer::new("convert scenario trips");
+    let map: Map = abstutil::read_binary(abstutil::path_map(&map_name), &mut timer);
+
+    if let Some(path) = export {
+        let scenario: Scenario = abstutil::read_binary(
+            abstutil::path_scenario(&map_name, &scenario_name),
+            &mut timer,
+        );
+        let trips = export_scenario(&scenario, &map).get(&mut timer);
+        if path.ends_with(".json") {
+            abstutil::write_json(path, &trips);
+        } else {
+            write_csv(&path, &trips).unwrap();
+        }
+    } else if let Some(path) = import {
+        let trips: Vec<ExternalTrip> = if path.ends_with(".json") {
+            abstutil::read_json(path, &mut timer)
+        } else {
+            read_csv(&path).unwrap()
+        };
+        import_scenario(&scenario_name, trips, &map, &mut timer)
+            .get(&mut timer)
+            .save();
+    } else {
+        panic!("Pass --export or --import");
+    }
+}
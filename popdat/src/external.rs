@@ -0,0 +1,309 @@
+use crate::psrc::{Endpoint, Mode, Purpose};
+use crate::trips::{make_scenario, Borders, Trip, TripEndpt};
+use abstutil::{Timer, Warn};
+use geom::{Distance, Duration, FindClosest, LonLat, Pt2D, Time};
+use map_model::{BuildingID, IntersectionID, Map};
+use serde_derive::{Deserialize, Serialize};
+use sim::{DrivingGoal, Scenario, SidewalkPOI, SidewalkSpot, SpawnTrip};
+use std::collections::HashMap;
+
+// Lon/lat points inside the map farther than this from any building can't be matched.
+const MAX_SNAP_DIST: Distance = Distance::const_meters(100.0);
+
+// A trip described without any IDs that change when the map is rebuilt, so scenarios can survive
+// that and be produced by other tools. As a CSV, the columns are:
+//
+// - departure: HH:MM:SS.S since midnight
+// - mode: Walk, Bike, Drive, or Transit
+// - from_lon, from_lat: WGS84
+// - from_osm_building: optional OSM way ID. Preferred over the lon/lat when it's in the map.
+// - from_osm_border: optional OSM node ID, meaning the trip enters the map at this border. Takes
+//   precedence over everything else. If the node isn't a border of the map anymore, the nearest
+//   border to the lon/lat is used instead.
+// - to_lon, to_lat, to_osm_building, to_osm_border: same for the destination
+//
+// Points outside the map boundary start or end at the nearest border with the right kind of lane.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ExternalTrip {
+    pub departure: String,
+    pub mode: Mode,
+    pub from_lon: f64,
+    pub from_lat: f64,
+    pub from_osm_building: Option<i64>,
+    pub from_osm_border: Option<i64>,
+    pub to_lon: f64,
+    pub to_lat: f64,
+    pub to_osm_building: Option<i64>,
+    pub to_osm_border: Option<i64>,
+}
+
+// An Endpoint, plus the border it's at, if any
+struct ExportEndpt {
+    endpt: Endpoint,
+    osm_border: Option<i64>,
+}
+
+pub fn write_csv(path: &str, trips: &Vec<ExternalTrip>) -> Result<(), failure::Error> {
+    let mut writer = csv::Writer::from_path(path)?;
+    for trip in trips {
+        writer.serialize(trip)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+pub fn read_csv(path: &str) -> Result<Vec<ExternalTrip>, failure::Error> {
+    let mut trips = Vec::new();
+    for rec in csv::Reader::from_path(path)?.deserialize() {
+        trips.push(rec?);
+    }
+    Ok(trips)
+}
+
+// Only the individual trips are exported. The higher-level SpawnOverTime and friends are tied to
+// neighborhoods and borders of this map, and cars can only appear at borders; the warnings say
+// what was skipped.
+pub fn export_scenario(scenario: &Scenario, map: &Map) -> Warn<Vec<ExternalTrip>> {
+    let mut warnings = Vec::new();
+    if !scenario.spawn_over_time.is_empty() || !scenario.border_spawn_over_time.is_empty() {
+        warnings.push(format!(
+            "Skipping {} SpawnOverTime and {} BorderSpawnOverTime",
+            scenario.spawn_over_time.len(),
+            scenario.border_spawn_over_time.len()
+        ));
+    }
+
+    let mut trips = Vec::new();
+    for trip in &scenario.individ_trips {
+        let (depart, mode, from, to) = match trip {
+            SpawnTrip::CarAppearing {
+                depart,
+                start,
+                goal,
+                is_bike,
+            } => {
+                let i = map.get_l(start.lane()).src_i;
+                if !map.get_i(i).is_border() {
+                    warnings.push(format!(
+                        "Skipping trip departing at {}, because it starts in the middle of {}",
+                        depart,
+                        start.lane()
+                    ));
+                    continue;
+                }
+                (
+                    *depart,
+                    if *is_bike { Mode::Bike } else { Mode::Drive },
+                    border_endpt(i, map),
+                    goal_endpt(goal, map),
+                )
+            }
+            SpawnTrip::MaybeUsingParkedCar(depart, b, goal) => (
+                *depart,
+                Mode::Drive,
+                bldg_endpt(*b, map),
+                goal_endpt(goal, map),
+            ),
+            SpawnTrip::UsingBike(depart, start, goal) => (
+                *depart,
+                Mode::Bike,
+                spot_endpt(start, map),
+                goal_endpt(goal, map),
+            ),
+            SpawnTrip::JustWalking(depart, start, goal) => (
+                *depart,
+                Mode::Walk,
+                spot_endpt(start, map),
+                spot_endpt(goal, map),
+            ),
+            SpawnTrip::UsingTransit(depart, start, goal, _, _, _) => (
+                *depart,
+                Mode::Transit,
+                spot_endpt(start, map),
+                spot_endpt(goal, map),
+            ),
+        };
+        trips.push(ExternalTrip {
+            departure: depart.to_string(),
+            mode,
+            from_lon: from.endpt.pos.longitude,
+            from_lat: from.endpt.pos.latitude,
+            from_osm_building: from.endpt.osm_building,
+            from_osm_border: from.osm_border,
+            to_lon: to.endpt.pos.longitude,
+            to_lat: to.endpt.pos.latitude,
+            to_osm_building: to.endpt.osm_building,
+            to_osm_border: to.osm_border,
+        });
+    }
+    Warn::warnings(trips, warnings)
+}
+
+// Snaps each trip to the current map. The warnings describe every trip that couldn't be matched.
+pub fn import_scenario(
+    scenario_name: &str,
+    trips: Vec<ExternalTrip>,
+    map: &Map,
+    timer: &mut Timer,
+) -> Warn<Scenario> {
+    let mut osm_id_to_bldg = HashMap::new();
+    let mut closest_bldg: FindClosest<BuildingID> = FindClosest::new(map.get_bounds());
+    for b in map.all_buildings() {
+        osm_id_to_bldg.insert(b.osm_way_id, b.id);
+        closest_bldg.add(b.id, b.polygon.points());
+    }
+    let borders = Borders::new(map);
+
+    let mut warnings = Vec::new();
+    let mut results = Vec::new();
+    timer.start_iter("snap trips to the map", trips.len());
+    for (idx, trip) in trips.into_iter().enumerate() {
+        timer.next();
+        let depart_at = match Time::parse(&trip.departure) {
+            Ok(t) => t,
+            Err(err) => {
+                warnings.push(format!("Trip {}: bad departure: {}", idx, err));
+                continue;
+            }
+        };
+        let from = Endpoint {
+            pos: LonLat::new(trip.from_lon, trip.from_lat),
+            osm_building: trip.from_osm_building,
+        };
+        let to = Endpoint {
+            pos: LonLat::new(trip.to_lon, trip.to_lat),
+            osm_building: trip.to_osm_building,
+        };
+        let snapped_from = snap(
+            &from,
+            trip.from_osm_border,
+            map,
+            &osm_id_to_bldg,
+            &closest_bldg,
+            borders.incoming(trip.mode),
+        );
+        let snapped_to = snap(
+            &to,
+            trip.to_osm_border,
+            map,
+            &osm_id_to_bldg,
+            &closest_bldg,
+            borders.outgoing(trip.mode),
+        );
+        match (snapped_from, snapped_to) {
+            (Some(TripEndpt::Border(i1, _)), Some(TripEndpt::Border(i2, _))) if i1 == i2 => {
+                warnings.push(format!(
+                    "Trip {}: enters and leaves the map at the same border {}, skipping",
+                    idx, i1
+                ));
+            }
+            (Some(from), Some(to)) => {
+                results.push(Trip {
+                    from,
+                    to,
+                    depart_at,
+                    // Not part of the interchange format
+                    purpose: (Purpose::Home, Purpose::Home),
+                    mode: trip.mode,
+                    trip_time: Duration::ZERO,
+                    trip_dist: Distance::ZERO,
+                });
+            }
+            (None, _) => {
+                warnings.push(format!("Trip {}: couldn't match origin {:?}", idx, from));
+            }
+            (_, None) => {
+                warnings.push(format!("Trip {}: couldn't match destination {:?}", idx, to));
+            }
+        }
+    }
+    timer.note(format!(
+        "Matched {} trips, skipped {}",
+        abstutil::prettyprint_usize(results.len()),
+        abstutil::prettyprint_usize(warnings.len())
+    ));
+
+    Warn::warnings(make_scenario(scenario_name, results, map, timer), warnings)
+}
+
+fn snap(
+    endpt: &Endpoint,
+    osm_border: Option<i64>,
+    map: &Map,
+    osm_id_to_bldg: &HashMap<i64, BuildingID>,
+    closest_bldg: &FindClosest<BuildingID>,
+    borders: &Vec<(IntersectionID, LonLat)>,
+) -> Option<TripEndpt> {
+    // Borders are inside the map boundary, so check for them first.
+    if let Some(id) = osm_border {
+        let pt = Pt2D::forcibly_from_gps(endpt.pos, map.get_gps_bounds());
+        if let Some((i, _)) = borders
+            .iter()
+            .find(|(i, _)| map.get_i(*i).orig_id.osm_node_id == id)
+        {
+            return Some(TripEndpt::Border(*i, pt));
+        }
+        return TripEndpt::new(endpt, map, &HashMap::new(), borders);
+    }
+    if let Some(b) = endpt.osm_building.and_then(|id| osm_id_to_bldg.get(&id)) {
+        return Some(TripEndpt::Building(*b));
+    }
+    if let Some(pt) = Pt2D::from_gps(endpt.pos, map.get_gps_bounds()) {
+        if map.get_boundary_polygon().contains_pt(pt) {
+            return closest_bldg
+                .closest_pt(pt, MAX_SNAP_DIST)
+                .map(|(b, _)| TripEndpt::Building(b));
+        }
+    }
+    TripEndpt::new(endpt, map, osm_id_to_bldg, borders)
+}
+
+fn pt_endpt(pt: Pt2D, map: &Map) -> ExportEndpt {
+    ExportEndpt {
+        endpt: Endpoint {
+            pos: pt.forcibly_to_gps(map.get_gps_bounds()),
+            osm_building: None,
+        },
+        osm_border: None,
+    }
+}
+
+fn bldg_endpt(b: BuildingID, map: &Map) -> ExportEndpt {
+    let bldg = map.get_b(b);
+    ExportEndpt {
+        endpt: Endpoint {
+            pos: bldg.polygon.center().forcibly_to_gps(map.get_gps_bounds()),
+            osm_building: Some(bldg.osm_way_id),
+        },
+        osm_border: None,
+    }
+}
+
+fn border_endpt(i: IntersectionID, map: &Map) -> ExportEndpt {
+    let border = map.get_i(i);
+    ExportEndpt {
+        endpt: Endpoint {
+            pos: border
+                .polygon
+                .center()
+                .forcibly_to_gps(map.get_gps_bounds()),
+            osm_building: None,
+        },
+        osm_border: Some(border.orig_id.osm_node_id),
+    }
+}
+
+fn goal_endpt(goal: &DrivingGoal, map: &Map) -> ExportEndpt {
+    match goal {
+        DrivingGoal::ParkNear(b) => bldg_endpt(*b, map),
+        DrivingGoal::Border(i, _) => border_endpt(*i, map),
+    }
+}
+
+fn spot_endpt(spot: &SidewalkSpot, map: &Map) -> ExportEndpt {
+    match spot.connection {
+        SidewalkPOI::Building(b) => bldg_endpt(b, map),
+        SidewalkPOI::Border(i) => border_endpt(i, map),
+        _ => pt_endpt(spot.sidewalk_pos.pt(map), map),
+    }
+}
@@ -1,9 +1,11 @@
+pub mod external;
+pub mod od;
 pub mod psrc;
 mod trips;
 
 use serde_derive::{Deserialize, Serialize};
 use std::collections::BTreeMap;
-pub use trips::{clip_trips, trips_to_scenario, Trip, TripEndpt};
+pub use trips::{clip_trips, make_scenario, trips_to_scenario, Trip, TripEndpt};
 
 #[derive(Serialize, Deserialize)]
 pub struct PopDat {
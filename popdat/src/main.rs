@@ -1,4 +1,21 @@
+use abstutil::{CmdArgs, Timer};
+use map_model::Map;
+use popdat::od::{od_matrix_to_scenario, ODSettings};
+use rand::SeedableRng;
+use rand_xorshift::XorShiftRng;
+
+// By default, builds popdat from PSRC's Soundcast model. To turn an origin-destination matrix CSV
+// into a Scenario for a map instead:
+//
+// popdat --od_csv=trips.csv --map=montlake --settings=od.json --scenario_name=od
 fn main() {
+    let mut args = CmdArgs::new();
+    if let Some(csv_path) = args.optional("--od_csv") {
+        import_od(args, csv_path);
+        return;
+    }
+    args.done();
+
     let mut timer = abstutil::Timer::new("creating popdat");
     let (trips, parcels) = popdat::psrc::import_trips(
         "../data/input/parcels_urbansim.txt",
@@ -9,3 +26,28 @@ fn main() {
     let popdat = popdat::PopDat { trips, parcels };
     abstutil::write_binary(abstutil::path_popdat(), &popdat);
 }
+
+fn import_od(mut args: CmdArgs, csv_path: String) {
+    let map_name = args.required("--map");
+    let settings_path = args.required("--settings");
+    let scenario_name = args.required("--scenario_name");
+    let rng_seed = args
+        .optional_parse("--rng_seed", |s| s.parse())
+        .unwrap_or(42);
+    args.done();
+
+    let mut timer = Timer::new(format!("import OD matrix {}", csv_path));
+    let map: Map = abstutil::read_binary(abstutil::path_map(&map_name), &mut timer);
+    let settings: ODSettings = abstutil::read_json(settings_path, &mut timer);
+    let mut rng = XorShiftRng::from_seed([rng_seed; 16]);
+    od_matrix_to_scenario(
+        &map,
+        &scenario_name,
+        &csv_path,
+        &settings,
+        &mut rng,
+        &mut timer,
+    )
+    .unwrap()
+    .save();
+}
@@ -0,0 +1,155 @@
+use crate::psrc::{Mode, Purpose};
+use crate::trips::{make_scenario, Trip, TripEndpt};
+use abstutil::Timer;
+use geom::{Distance, Duration, Time};
+use map_model::{FullNeighborhoodInfo, Map};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use rand_xorshift::XorShiftRng;
+use serde_derive::{Deserialize, Serialize};
+use sim::Scenario;
+use std::collections::BTreeMap;
+
+// How to turn the daily totals of an OD matrix into individual trips.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ODSettings {
+    // (start, end, relative weight). Departure times are spread uniformly within each window.
+    pub departure_profile: Vec<(Time, Time, f64)>,
+    // Relative weights, don't need to sum to 1
+    pub mode_split: BTreeMap<Mode, f64>,
+}
+
+// One line of the CSV. Zones are named neighborhoods, drawn for the map with the
+// NeighborhoodBuilder. Fractional trip counts are rounded randomly.
+#[derive(Deserialize)]
+struct ODRow {
+    origin: String,
+    destination: String,
+    trips: f64,
+}
+
+// The CSV needs columns origin, destination, and trips.
+pub fn od_matrix_to_scenario(
+    map: &Map,
+    scenario_name: &str,
+    csv_path: &str,
+    settings: &ODSettings,
+    rng: &mut XorShiftRng,
+    timer: &mut Timer,
+) -> Result<Scenario, failure::Error> {
+    let neighborhoods = FullNeighborhoodInfo::load_all(map);
+    let windows: Vec<(Time, Time)> = settings
+        .departure_profile
+        .iter()
+        .map(|(t1, t2, _)| (*t1, *t2))
+        .collect();
+    let window_weights: Vec<f64> = settings
+        .departure_profile
+        .iter()
+        .map(|(_, _, w)| *w)
+        .collect();
+    let modes: Vec<Mode> = settings.mode_split.keys().cloned().collect();
+    let mode_weights: Vec<f64> = settings.mode_split.values().cloned().collect();
+    if !valid_weights(&window_weights) || !valid_weights(&mode_weights) {
+        return Err(failure::err_msg(
+            "ODSettings needs a departure_profile and mode_split with non-negative weights that \
+             add up to something positive",
+        ));
+    }
+
+    let mut trips = Vec::new();
+    for (idx, rec) in csv::Reader::from_path(csv_path)?.deserialize().enumerate() {
+        let row: ODRow = rec?;
+        if !row.trips.is_finite() || row.trips < 0.0 {
+            // Line 1 is the header
+            return Err(failure::err_msg(format!(
+                "Line {} of {} has {} trips from {} to {}",
+                idx + 2,
+                csv_path,
+                row.trips,
+                row.origin,
+                row.destination
+            )));
+        }
+        let (from, to) = match (
+            neighborhoods.get(&row.origin),
+            neighborhoods.get(&row.destination),
+        ) {
+            (Some(from), Some(to)) if !from.buildings.is_empty() && !to.buildings.is_empty() => {
+                (from, to)
+            }
+            _ => {
+                timer.warn(format!(
+                    "Skipping {} trips from {} to {}; one of the zones isn't a neighborhood with \
+                     buildings",
+                    row.trips, row.origin, row.destination
+                ));
+                continue;
+            }
+        };
+
+        for _ in 0..round_randomly(row.trips, rng) {
+            let (start, end) = windows[weighted_pick(&window_weights, rng)];
+            let depart_at = if end > start {
+                start + Duration::seconds(rng.gen_range(0.0, (end - start).inner_seconds()))
+            } else {
+                start
+            };
+            trips.push(Trip {
+                from: TripEndpt::Building(*from.buildings.choose(rng).unwrap()),
+                to: TripEndpt::Building(*to.buildings.choose(rng).unwrap()),
+                depart_at,
+                // OD matrices don't say why people travel
+                purpose: (Purpose::Home, Purpose::Home),
+                mode: modes[weighted_pick(&mode_weights, rng)],
+                // Unknown until the trip is simulated
+                trip_time: Duration::ZERO,
+                trip_dist: Distance::ZERO,
+            });
+        }
+    }
+    timer.note(format!(
+        "{} trips from the OD matrix",
+        abstutil::prettyprint_usize(trips.len())
+    ));
+    // Both ends in the same building
+    trips.retain(|trip| match (&trip.from, &trip.to) {
+        (TripEndpt::Building(b1), TripEndpt::Building(b2)) => b1 != b2,
+        _ => true,
+    });
+
+    Ok(make_scenario(scenario_name, trips, map, timer))
+}
+
+// Rounds up with probability equal to the fractional part, so the expected value is x. x must be
+// finite and non-negative.
+pub fn round_randomly(x: f64, rng: &mut XorShiftRng) -> usize {
+    let mut count = x.floor() as usize;
+    if rng.gen_bool(x - x.floor()) {
+        count += 1;
+    }
+    count
+}
+
+fn valid_weights(weights: &Vec<f64>) -> bool {
+    weights.iter().all(|w| w.is_finite() && *w >= 0.0) && weights.iter().sum::<f64>() > 0.0
+}
+
+// Returns an index with probability proportional to its weight.
+pub fn weighted_pick(weights: &Vec<f64>, rng: &mut XorShiftRng) -> usize {
+    assert!(
+        valid_weights(weights),
+        "weighted_pick needs non-negative weights with a positive sum, got {:?}",
+        weights
+    );
+    let total: f64 = weights.iter().sum();
+    let mut x = rng.gen_range(0.0, total);
+    for (idx, w) in weights.iter().enumerate() {
+        if x < *w {
+            return idx;
+        }
+        x -= w;
+    }
+    // Floating point error can leave a little bit of x. Don't hand out a zero-weight choice.
+    weights.iter().rposition(|w| *w > 0.0).unwrap()
+}
@@ -18,7 +18,7 @@ pub struct Trip {
     pub trip_dist: Distance,
 }
 
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Endpoint {
     pub pos: LonLat,
     pub osm_building: Option<i64>,
@@ -31,7 +31,7 @@ pub struct Parcel {
     pub offstreet_parking_spaces: usize,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Mode {
     Walk,
     Bike,
@@ -116,7 +116,7 @@ impl Trip {
 }
 
 impl TripEndpt {
-    fn new(
+    pub(crate) fn new(
         endpt: &Endpoint,
         map: &Map,
         osm_id_to_bldg: &HashMap<i64, BuildingID>,
@@ -175,72 +175,16 @@ pub fn clip_trips(map: &Map, timer: &mut Timer) -> (Vec<Trip>, HashMap<BuildingI
     for b in map.all_buildings() {
         osm_id_to_bldg.insert(b.osm_way_id, b.id);
     }
-    let bounds = map.get_gps_bounds();
-    // TODO Figure out why some polygon centers are broken
-    let incoming_borders_walking: Vec<(IntersectionID, LonLat)> = map
-        .all_incoming_borders()
-        .into_iter()
-        .filter(|i| {
-            !i.get_outgoing_lanes(map, PathConstraints::Pedestrian)
-                .is_empty()
-        })
-        .filter_map(|i| i.polygon.center().to_gps(bounds).map(|pt| (i.id, pt)))
-        .collect();
-    let incoming_borders_driving: Vec<(IntersectionID, LonLat)> = map
-        .all_incoming_borders()
-        .into_iter()
-        .filter(|i| !i.get_outgoing_lanes(map, PathConstraints::Car).is_empty())
-        .filter_map(|i| i.polygon.center().to_gps(bounds).map(|pt| (i.id, pt)))
-        .collect();
-    let incoming_borders_biking: Vec<(IntersectionID, LonLat)> = map
-        .all_incoming_borders()
-        .into_iter()
-        .filter(|i| !i.get_outgoing_lanes(map, PathConstraints::Bike).is_empty())
-        .filter_map(|i| i.polygon.center().to_gps(bounds).map(|pt| (i.id, pt)))
-        .collect();
-    let outgoing_borders_walking: Vec<(IntersectionID, LonLat)> = map
-        .all_outgoing_borders()
-        .into_iter()
-        .filter(|i| {
-            !i.get_incoming_lanes(map, PathConstraints::Pedestrian)
-                .is_empty()
-        })
-        .filter_map(|i| i.polygon.center().to_gps(bounds).map(|pt| (i.id, pt)))
-        .collect();
-    let outgoing_borders_driving: Vec<(IntersectionID, LonLat)> = map
-        .all_outgoing_borders()
-        .into_iter()
-        .filter(|i| !i.get_incoming_lanes(map, PathConstraints::Car).is_empty())
-        .filter_map(|i| i.polygon.center().to_gps(bounds).map(|pt| (i.id, pt)))
-        .collect();
-    let outgoing_borders_biking: Vec<(IntersectionID, LonLat)> = map
-        .all_outgoing_borders()
-        .into_iter()
-        .filter(|i| !i.get_incoming_lanes(map, PathConstraints::Bike).is_empty())
-        .filter_map(|i| i.polygon.center().to_gps(bounds).map(|pt| (i.id, pt)))
-        .collect();
+    let borders = Borders::new(map);
 
     let maybe_results: Vec<Option<Trip>> = timer.parallelize("clip trips", popdat.trips, |trip| {
         let from = TripEndpt::new(
             &trip.from,
             map,
             &osm_id_to_bldg,
-            match trip.mode {
-                Mode::Walk | Mode::Transit => &incoming_borders_walking,
-                Mode::Drive => &incoming_borders_driving,
-                Mode::Bike => &incoming_borders_biking,
-            },
-        )?;
-        let to = TripEndpt::new(
-            &trip.to,
-            map,
-            &osm_id_to_bldg,
-            match trip.mode {
-                Mode::Walk | Mode::Transit => &outgoing_borders_walking,
-                Mode::Drive => &outgoing_borders_driving,
-                Mode::Bike => &outgoing_borders_biking,
-            },
+            borders.incoming(trip.mode),
         )?;
+        let to = TripEndpt::new(&trip.to, map, &osm_id_to_bldg, borders.outgoing(trip.mode))?;
 
         let trip = Trip {
             from,
@@ -278,8 +222,73 @@ pub fn clip_trips(map: &Map, timer: &mut Timer) -> (Vec<Trip>, HashMap<BuildingI
     (trips, bldgs)
 }
 
+// Where trips of each mode can enter or leave the map
+pub(crate) struct Borders {
+    incoming_walking: Vec<(IntersectionID, LonLat)>,
+    incoming_driving: Vec<(IntersectionID, LonLat)>,
+    incoming_biking: Vec<(IntersectionID, LonLat)>,
+    outgoing_walking: Vec<(IntersectionID, LonLat)>,
+    outgoing_driving: Vec<(IntersectionID, LonLat)>,
+    outgoing_biking: Vec<(IntersectionID, LonLat)>,
+}
+
+impl Borders {
+    pub(crate) fn new(map: &Map) -> Borders {
+        let bounds = map.get_gps_bounds();
+        // TODO Figure out why some polygon centers are broken
+        let incoming = |constraints: PathConstraints| -> Vec<(IntersectionID, LonLat)> {
+            map.all_incoming_borders()
+                .into_iter()
+                .filter(|i| !i.get_outgoing_lanes(map, constraints).is_empty())
+                .filter_map(|i| i.polygon.center().to_gps(bounds).map(|pt| (i.id, pt)))
+                .collect()
+        };
+        let outgoing = |constraints: PathConstraints| -> Vec<(IntersectionID, LonLat)> {
+            map.all_outgoing_borders()
+                .into_iter()
+                .filter(|i| !i.get_incoming_lanes(map, constraints).is_empty())
+                .filter_map(|i| i.polygon.center().to_gps(bounds).map(|pt| (i.id, pt)))
+                .collect()
+        };
+        Borders {
+            incoming_walking: incoming(PathConstraints::Pedestrian),
+            incoming_driving: incoming(PathConstraints::Car),
+            incoming_biking: incoming(PathConstraints::Bike),
+            outgoing_walking: outgoing(PathConstraints::Pedestrian),
+            outgoing_driving: outgoing(PathConstraints::Car),
+            outgoing_biking: outgoing(PathConstraints::Bike),
+        }
+    }
+
+    pub(crate) fn incoming(&self, mode: Mode) -> &Vec<(IntersectionID, LonLat)> {
+        match mode {
+            Mode::Walk | Mode::Transit => &self.incoming_walking,
+            Mode::Drive => &self.incoming_driving,
+            Mode::Bike => &self.incoming_biking,
+        }
+    }
+
+    pub(crate) fn outgoing(&self, mode: Mode) -> &Vec<(IntersectionID, LonLat)> {
+        match mode {
+            Mode::Walk | Mode::Transit => &self.outgoing_walking,
+            Mode::Drive => &self.outgoing_driving,
+            Mode::Bike => &self.outgoing_biking,
+        }
+    }
+}
+
 pub fn trips_to_scenario(map: &Map, timer: &mut Timer) -> Scenario {
     let (trips, _) = clip_trips(map, timer);
+    make_scenario("weekday", trips, map, timer)
+}
+
+// Also seeds enough parked cars for all of the driving trips.
+pub fn make_scenario(
+    scenario_name: &str,
+    trips: Vec<Trip>,
+    map: &Map,
+    timer: &mut Timer,
+) -> Scenario {
     // TODO Don't clone trips for parallelize
     let individ_trips = timer
         .parallelize("turn PSRC trips into SpawnTrips", trips.clone(), |trip| {
@@ -315,7 +324,7 @@ pub fn trips_to_scenario(map: &Map, timer: &mut Timer) -> Scenario {
     }
 
     Scenario {
-        scenario_name: "weekday".to_string(),
+        scenario_name: scenario_name.to_string(),
         map_name: map.get_name().to_string(),
         seed_buses: true,
         seed_parked_cars: Vec::new(),
@@ -1,5 +1,6 @@
 use abstutil::{CmdArgs, Timer};
-use map_model::Map;
+use map_model::raw::RawMap;
+use map_model::{Map, ValidationReport};
 use popdat::trips_to_scenario;
 
 fn main() {
@@ -7,11 +8,39 @@ fn main() {
     let load = args.required_free();
     let disable_psrc_scenarios = args.enabled("--disable_psrc_scenarios");
     let use_fixes = !args.enabled("--nofixes");
+    let validate = args.enabled("--validate");
     args.done();
 
     let mut timer = Timer::new(format!("precompute {}", load));
 
-    let map = Map::new(load, use_fixes, &mut timer);
+    let map = if validate {
+        // Compare against the same RawMap that Map::new uses
+        let mut raw: RawMap = abstutil::read_binary(load.clone(), &mut timer);
+        if use_fixes {
+            raw.apply_all_fixes(&mut timer);
+        }
+        // If the map can't even be built, that's the most important thing to report.
+        let map = match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            Map::new(load, use_fixes, &mut timer)
+        })) {
+            Ok(map) => map,
+            Err(err) => {
+                let msg = if let Some(s) = err.downcast_ref::<&str>() {
+                    s.to_string()
+                } else if let Some(s) = err.downcast_ref::<String>() {
+                    s.clone()
+                } else {
+                    "unknown panic".to_string()
+                };
+                ValidationReport::failed_to_build(&raw, msg).save();
+                std::panic::resume_unwind(err);
+            }
+        };
+        ValidationReport::new(&map, &raw, &mut timer).save();
+        map
+    } else {
+        Map::new(load, use_fixes, &mut timer)
+    };
     timer.start("save map");
     map.save();
     timer.stop("save map");
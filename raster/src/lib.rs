@@ -0,0 +1,242 @@
+mod palette;
+
+pub use crate::palette::{
+    unzoomed_color, BUILDING, GRASS, MAP_BACKGROUND, PEDESTRIAN_ISLAND, UNZOOMED_ARTERIAL_ROAD,
+    UNZOOMED_BIKE, UNZOOMED_BUS, UNZOOMED_CAR, UNZOOMED_HIGHWAY_ROAD,
+    UNZOOMED_INTERESTING_INTERSECTION, UNZOOMED_PEDESTRIAN, UNZOOMED_RESIDENTIAL_ROAD, WATER,
+};
+use geom::{Bounds, Polygon, Pt2D};
+use std::fmt::Write as FmtWrite;
+use std::fs::File;
+use std::io::{Error, ErrorKind, Write};
+
+// Renders colored polygons to SVG or PNG without a window or GPU, so headless tools can produce
+// images without linking the graphics backend. ezgui converts a GeomBatch into this.
+// TODO Text isn't supported, since it's drawn separately from GeomBatch.
+
+// A plain color, with every component in [0, 1]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rgba {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+impl Rgba {
+    pub const BLACK: Rgba = Rgba::new(0.0, 0.0, 0.0, 1.0);
+    pub const WHITE: Rgba = Rgba::new(1.0, 1.0, 1.0, 1.0);
+
+    pub const fn new(r: f32, g: f32, b: f32, a: f32) -> Rgba {
+        Rgba { r, g, b, a }
+    }
+
+    pub fn rgb(r: u8, g: u8, b: u8) -> Rgba {
+        Rgba::new(
+            f32::from(r) / 255.0,
+            f32::from(g) / 255.0,
+            f32::from(b) / 255.0,
+            1.0,
+        )
+    }
+
+    pub const fn grey(f: f32) -> Rgba {
+        Rgba::new(f, f, f, 1.0)
+    }
+
+    pub fn alpha(self, a: f32) -> Rgba {
+        Rgba::new(self.r, self.g, self.b, a)
+    }
+}
+
+pub fn batch_to_svg(batch: Vec<(Rgba, Polygon)>, bounds: &Bounds, background: Rgba) -> String {
+    let mut svg = String::new();
+    // crispEdges avoids hairline seams between the triangles of one polygon
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}" width="{}" height="{}" shape-rendering="crispEdges">"#,
+        bounds.min_x,
+        bounds.min_y,
+        bounds.width(),
+        bounds.height(),
+        bounds.width(),
+        bounds.height()
+    )
+    .unwrap();
+    let (fill, opacity) = svg_color(background);
+    writeln!(
+        svg,
+        r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}" fill-opacity="{}"/>"#,
+        bounds.min_x,
+        bounds.min_y,
+        bounds.width(),
+        bounds.height(),
+        fill,
+        opacity
+    )
+    .unwrap();
+
+    for (color, poly) in batch {
+        let (fill, opacity) = svg_color(color);
+        let (pts, indices, _) = poly.raw_for_rendering();
+        let mut path = String::new();
+        for tri in indices.chunks_exact(3) {
+            let (p1, p2, p3) = (pts[tri[0]], pts[tri[1]], pts[tri[2]]);
+            write!(
+                path,
+                "M{:.2} {:.2}L{:.2} {:.2}L{:.2} {:.2}Z",
+                p1.x(),
+                p1.y(),
+                p2.x(),
+                p2.y(),
+                p3.x(),
+                p3.y()
+            )
+            .unwrap();
+        }
+        if !path.is_empty() {
+            writeln!(
+                svg,
+                r#"<path d="{}" fill="{}" fill-opacity="{}"/>"#,
+                path, fill, opacity
+            )
+            .unwrap();
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+pub fn write_svg(
+    path: &str,
+    batch: Vec<(Rgba, Polygon)>,
+    bounds: &Bounds,
+    background: Rgba,
+) -> Result<(), Error> {
+    std::fs::create_dir_all(std::path::Path::new(path).parent().unwrap())?;
+    let mut file = File::create(path)?;
+    file.write_all(batch_to_svg(batch, bounds, background).as_bytes())?;
+    Ok(())
+}
+
+// The height is picked to preserve the aspect ratio of the bounds.
+pub fn batch_to_png(
+    batch: Vec<(Rgba, Polygon)>,
+    bounds: &Bounds,
+    background: Rgba,
+    width_px: u32,
+) -> image::RgbaImage {
+    let scale = f64::from(width_px) / bounds.width();
+    let height_px = ((bounds.height() * scale).ceil() as u32).max(1);
+    let mut img = image::RgbaImage::from_pixel(width_px, height_px, png_color(background));
+    draw_batch_on_png(&mut img, batch, bounds);
+    img
+}
+
+// Draws on top of an existing image, which should cover the bounds with the same aspect ratio.
+// Useful for drawing lots of frames over one background.
+pub fn draw_batch_on_png(img: &mut image::RgbaImage, batch: Vec<(Rgba, Polygon)>, bounds: &Bounds) {
+    let scale = f64::from(img.width()) / bounds.width();
+    let to_px = |pt: Pt2D| {
+        (
+            (pt.x() - bounds.min_x) * scale,
+            (pt.y() - bounds.min_y) * scale,
+        )
+    };
+    for (color, poly) in batch {
+        let (pts, indices, _) = poly.raw_for_rendering();
+        for tri in indices.chunks_exact(3) {
+            fill_triangle(
+                img,
+                color,
+                to_px(pts[tri[0]]),
+                to_px(pts[tri[1]]),
+                to_px(pts[tri[2]]),
+            );
+        }
+    }
+}
+
+pub fn write_png(
+    path: &str,
+    batch: Vec<(Rgba, Polygon)>,
+    bounds: &Bounds,
+    background: Rgba,
+    width_px: u32,
+) -> Result<(), Error> {
+    std::fs::create_dir_all(std::path::Path::new(path).parent().unwrap())?;
+    batch_to_png(batch, bounds, background, width_px)
+        .save(path)
+        .map_err(|err| Error::new(ErrorKind::Other, err))
+}
+
+fn png_color(color: Rgba) -> image::Rgba<u8> {
+    image::Rgba([
+        (color.r * 255.0) as u8,
+        (color.g * 255.0) as u8,
+        (color.b * 255.0) as u8,
+        (color.a * 255.0) as u8,
+    ])
+}
+
+fn svg_color(color: Rgba) -> (String, f32) {
+    (
+        format!(
+            "#{:02X}{:02X}{:02X}",
+            (color.r * 255.0) as u8,
+            (color.g * 255.0) as u8,
+            (color.b * 255.0) as u8
+        ),
+        color.a,
+    )
+}
+
+// Fills every pixel whose center is inside the triangle, blending with what's already there.
+fn fill_triangle(
+    img: &mut image::RgbaImage,
+    color: Rgba,
+    p1: (f64, f64),
+    p2: (f64, f64),
+    p3: (f64, f64),
+) {
+    let Rgba { r, g, b, a } = color;
+    if a == 0.0 {
+        return;
+    }
+
+    let edge = |from: (f64, f64), to: (f64, f64), x: f64, y: f64| {
+        (to.0 - from.0) * (y - from.1) - (to.1 - from.1) * (x - from.0)
+    };
+    let area = edge(p1, p2, p3.0, p3.1);
+    if area == 0.0 {
+        return;
+    }
+
+    let min_x = p1.0.min(p2.0).min(p3.0).floor().max(0.0) as u32;
+    let min_y = p1.1.min(p2.1).min(p3.1).floor().max(0.0) as u32;
+    let max_x = (p1.0.max(p2.0).max(p3.0).ceil() as u32).min(img.width());
+    let max_y = (p1.1.max(p2.1).max(p3.1).ceil() as u32).min(img.height());
+
+    for y in min_y..max_y {
+        for x in min_x..max_x {
+            let (cx, cy) = (f64::from(x) + 0.5, f64::from(y) + 0.5);
+            let w1 = edge(p2, p3, cx, cy) / area;
+            let w2 = edge(p3, p1, cx, cy) / area;
+            let w3 = edge(p1, p2, cx, cy) / area;
+            if w1 < 0.0 || w2 < 0.0 || w3 < 0.0 {
+                continue;
+            }
+
+            let px = img.get_pixel_mut(x, y);
+            let blend =
+                |src: f32, dst: u8| (src * 255.0 * a + f32::from(dst) * (1.0 - a)).round() as u8;
+            px.0 = [
+                blend(r, px.0[0]),
+                blend(g, px.0[1]),
+                blend(b, px.0[2]),
+                (255.0 * a + f32::from(px.0[3]) * (1.0 - a)).round() as u8,
+            ];
+        }
+    }
+}
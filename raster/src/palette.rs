@@ -0,0 +1,35 @@
+use crate::Rgba;
+use map_model::{AreaType, UnzoomedShape};
+
+// The default colors of the zoomed-out map and agents. Color schemes in the game start from these,
+// and headless tools use them as they are, so exported images look like the screen.
+// TODO Once f32 can be used in const fn, use Rgba::rgb here.
+
+pub const MAP_BACKGROUND: Rgba = Rgba::grey(0.87);
+pub const GRASS: Rgba = Rgba::new(148.0 / 255.0, 200.0 / 255.0, 74.0 / 255.0, 1.0);
+pub const WATER: Rgba = Rgba::new(164.0 / 255.0, 200.0 / 255.0, 234.0 / 255.0, 1.0);
+pub const PEDESTRIAN_ISLAND: Rgba = Rgba::grey(0.3);
+pub const UNZOOMED_HIGHWAY_ROAD: Rgba = Rgba::new(232.0 / 255.0, 146.0 / 255.0, 162.0 / 255.0, 1.0);
+pub const UNZOOMED_ARTERIAL_ROAD: Rgba = Rgba::new(1.0, 199.0 / 255.0, 62.0 / 255.0, 1.0);
+pub const UNZOOMED_RESIDENTIAL_ROAD: Rgba = Rgba::WHITE;
+pub const UNZOOMED_INTERESTING_INTERSECTION: Rgba = Rgba::BLACK;
+pub const BUILDING: Rgba = Rgba::new(196.0 / 255.0, 193.0 / 255.0, 188.0 / 255.0, 1.0);
+
+pub const UNZOOMED_CAR: Rgba = Rgba::new(1.0, 0.0, 0.0, 0.5);
+pub const UNZOOMED_BIKE: Rgba = Rgba::new(0.0, 1.0, 0.0, 0.5);
+pub const UNZOOMED_BUS: Rgba = Rgba::new(0.0, 0.0, 1.0, 0.5);
+pub const UNZOOMED_PEDESTRIAN: Rgba = Rgba::new(1.0, 0.55, 0.0, 0.5);
+
+pub fn unzoomed_color(shape: UnzoomedShape) -> Rgba {
+    match shape {
+        UnzoomedShape::Background => MAP_BACKGROUND,
+        UnzoomedShape::Area(AreaType::Park) => GRASS,
+        UnzoomedShape::Area(AreaType::Water) => WATER,
+        UnzoomedShape::Area(AreaType::PedestrianIsland) => PEDESTRIAN_ISLAND,
+        UnzoomedShape::HighwayRoad => UNZOOMED_HIGHWAY_ROAD,
+        UnzoomedShape::ArterialRoad => UNZOOMED_ARTERIAL_ROAD,
+        UnzoomedShape::ResidentialRoad => UNZOOMED_RESIDENTIAL_ROAD,
+        UnzoomedShape::InterestingIntersection => UNZOOMED_INTERESTING_INTERSECTION,
+        UnzoomedShape::Building => BUILDING,
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{AgentID, CarID, Event, TripID, TripMode, VehicleType};
+use crate::{AgentID, CarID, ConflictKind, EmissionsStats, Event, TripID, TripMode, VehicleType};
 use abstutil::Counter;
 use derivative::Derivative;
 use geom::{Distance, Duration, DurationHistogram, PercentageHistogram, Time};
@@ -6,7 +6,7 @@ use map_model::{
     BusRouteID, BusStopID, IntersectionID, Map, Path, PathRequest, RoadID, Traversable, TurnGroupID,
 };
 use serde_derive::{Deserialize, Serialize};
-use std::collections::{BTreeMap, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 
 #[derive(Clone, Serialize, Deserialize, Derivative)]
 pub struct Analytics {
@@ -21,6 +21,9 @@ pub struct Analytics {
     // TODO This subsumes finished_trips
     pub trip_log: Vec<(Time, TripID, Option<PathRequest>, String)>,
     pub intersection_delays: BTreeMap<IntersectionID, Vec<(Time, Duration)>>,
+    pub emissions: EmissionsStats,
+    // Time, kind, and how long after the conflicting turn
+    pub near_conflicts: BTreeMap<IntersectionID, Vec<(Time, ConflictKind, Duration)>>,
 
     // After we restore from a savestate, don't record anything. This is only going to make sense
     // if savestates are only used for quickly previewing against prebaked results, where we have
@@ -58,6 +61,8 @@ impl Analytics {
             finished_trips: Vec::new(),
             trip_log: Vec::new(),
             intersection_delays: BTreeMap::new(),
+            emissions: EmissionsStats::new(),
+            near_conflicts: BTreeMap::new(),
             record_anything: true,
         }
     }
@@ -104,6 +109,8 @@ impl Analytics {
             };
         }
 
+        self.emissions.event(&ev, time, map);
+
         // Test expectations
         if !self.test_expectations.is_empty() && &ev == self.test_expectations.front().unwrap() {
             println!("At {}, met expectation {:?}", time, ev);
@@ -135,6 +142,14 @@ impl Analytics {
                 .push((time, delay));
         }
 
+        // Near-conflicts
+        if let Event::NearConflict(id, kind, gap) = ev {
+            self.near_conflicts
+                .entry(id)
+                .or_insert_with(Vec::new)
+                .push((time, kind, gap));
+        }
+
         // TODO Kinda hacky, but these all consume the event, so kinda bundle em.
         match ev {
             Event::TripPhaseStarting(id, maybe_req, metadata) => {
@@ -343,6 +358,33 @@ impl Analytics {
             .collect()
     }
 
+    // How many agents of some modes entered the road during [start, end)?
+    pub fn count_road(
+        &self,
+        road: RoadID,
+        start: Time,
+        end: Time,
+        modes: &BTreeSet<TripMode>,
+    ) -> usize {
+        count_between(&self.thruput_stats.raw_per_road, road, start, end, modes)
+    }
+
+    pub fn count_intersection(
+        &self,
+        intersection: IntersectionID,
+        start: Time,
+        end: Time,
+        modes: &BTreeSet<TripMode>,
+    ) -> usize {
+        count_between(
+            &self.thruput_stats.raw_per_intersection,
+            intersection,
+            start,
+            end,
+            modes,
+        )
+    }
+
     // Slightly misleading -- TripMode::Transit means buses, not pedestrians taking transit
     pub fn throughput_road(
         &self,
@@ -524,6 +566,27 @@ impl Analytics {
         delays
     }
 
+    // Per intersection, how many near-conflicts of each kind have happened
+    pub fn near_conflicts(
+        &self,
+        now: Time,
+    ) -> BTreeMap<IntersectionID, BTreeMap<ConflictKind, usize>> {
+        let mut results = BTreeMap::new();
+        for (i, list) in &self.near_conflicts {
+            let mut counts = BTreeMap::new();
+            for (t, kind, _) in list {
+                if *t > now {
+                    break;
+                }
+                *counts.entry(*kind).or_insert(0) += 1;
+            }
+            if !counts.is_empty() {
+                results.insert(*i, counts);
+            }
+        }
+        results
+    }
+
     pub fn intersection_delays_bucketized(
         &self,
         now: Time,
@@ -615,3 +678,15 @@ impl Window {
         self.times.len()
     }
 }
+
+fn count_between<X: PartialEq>(
+    data: &Vec<(Time, TripMode, X)>,
+    obj: X,
+    start: Time,
+    end: Time,
+    modes: &BTreeSet<TripMode>,
+) -> usize {
+    data.iter()
+        .filter(|(t, m, x)| *x == obj && *t >= start && *t < end && modes.contains(m))
+        .count()
+}
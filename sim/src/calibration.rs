@@ -0,0 +1,196 @@
+use crate::{Analytics, Scenario, TripMode};
+use abstutil::Timer;
+use geom::{Distance, Duration, FindClosest, LonLat, Pt2D, Time};
+use map_model::{IntersectionID, Map, RoadID};
+use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+// The usual target for a calibrated model is GEH under this for 85% of counts.
+const GOOD_GEH: f64 = 5.0;
+// Don't let one noisy count blow up or wipe out demand.
+const MIN_SCALE: f64 = 0.1;
+const MAX_SCALE: f64 = 10.0;
+// A counter farther than this from any road can't be matched.
+const MAX_SNAP_DIST: Distance = Distance::const_meters(30.0);
+
+// A real-world count, like from a tube counter or a turning movement count.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ObservedCount {
+    pub location: CountLocation,
+    pub start: Time,
+    pub end: Time,
+    // Which kinds of agents were counted
+    pub modes: BTreeSet<TripMode>,
+    pub count: usize,
+}
+
+// Counts are collected independently of any one version of the map, so they don't refer to IDs that
+// change when the map is rebuilt.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+pub enum CountLocation {
+    // Where a tube counter was placed, matched to the closest road
+    Road(LonLat),
+    // The OSM node ID of an intersection with a turning movement count
+    Intersection(i64),
+}
+
+// Where a count winds up in the current map
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+pub enum MatchedLocation {
+    Road(RoadID),
+    Intersection(IntersectionID),
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct CountComparison {
+    pub observed: ObservedCount,
+    pub matched: MatchedLocation,
+    pub simulated: usize,
+    pub geh: f64,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct CalibrationReport {
+    pub comparisons: Vec<CountComparison>,
+}
+
+impl CalibrationReport {
+    // Counts that can't be matched to the map are skipped with a warning.
+    pub fn new(
+        counts: Vec<ObservedCount>,
+        analytics: &Analytics,
+        map: &Map,
+        timer: &mut Timer,
+    ) -> CalibrationReport {
+        let mut closest_road: FindClosest<RoadID> = FindClosest::new(map.get_bounds());
+        for r in map.all_roads() {
+            closest_road.add(r.id, r.center_pts.points());
+        }
+
+        let mut comparisons = Vec::new();
+        for observed in counts {
+            let matched = match observed.location {
+                CountLocation::Road(gps) => Pt2D::from_gps(gps, map.get_gps_bounds())
+                    .and_then(|pt| closest_road.closest_pt(pt, MAX_SNAP_DIST))
+                    .map(|(r, _)| MatchedLocation::Road(r)),
+                CountLocation::Intersection(osm_node_id) => map
+                    .all_intersections()
+                    .iter()
+                    .find(|i| i.orig_id.osm_node_id == osm_node_id)
+                    .map(|i| MatchedLocation::Intersection(i.id)),
+            };
+            let matched = if let Some(m) = matched {
+                m
+            } else {
+                timer.warn(format!(
+                    "Skipping count at {:?}; it isn't in this map",
+                    observed.location
+                ));
+                continue;
+            };
+
+            let simulated = match matched {
+                MatchedLocation::Road(r) => {
+                    analytics.count_road(r, observed.start, observed.end, &observed.modes)
+                }
+                MatchedLocation::Intersection(i) => {
+                    analytics.count_intersection(i, observed.start, observed.end, &observed.modes)
+                }
+            };
+            let geh = geh(simulated, observed.count, observed.end - observed.start);
+            comparisons.push(CountComparison {
+                observed,
+                matched,
+                simulated,
+                geh,
+            });
+        }
+        CalibrationReport { comparisons }
+    }
+
+    pub fn describe(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        for c in &self.comparisons {
+            lines.push(format!(
+                "{:?} from {} to {}: observed {}, simulated {}, GEH {:.1}",
+                c.matched, c.observed.start, c.observed.end, c.observed.count, c.simulated, c.geh
+            ));
+        }
+        lines.push(format!(
+            "{:.1}% of {} counts have GEH < {}",
+            100.0 * self.pct_good(),
+            self.comparisons.len(),
+            GOOD_GEH
+        ));
+        lines
+    }
+
+    // What fraction of counts are matched well?
+    pub fn pct_good(&self) -> f64 {
+        if self.comparisons.is_empty() {
+            return 1.0;
+        }
+        let good = self.comparisons.iter().filter(|c| c.geh < GOOD_GEH).count();
+        (good as f64) / (self.comparisons.len() as f64)
+    }
+
+    // Scale the high-level demand in a scenario to bring simulated counts closer to observed.
+    // BorderSpawnOverTime starting at a counted road use the counts there; everything else uses
+    // the ratio over all counts. Individual trips are left alone.
+    pub fn scale_demand(&self, scenario: &mut Scenario) {
+        let global = scale_factor(self.comparisons.iter()).unwrap_or(1.0);
+
+        for s in scenario.spawn_over_time.iter_mut() {
+            s.num_agents = scale(s.num_agents, global);
+        }
+        for s in scenario.border_spawn_over_time.iter_mut() {
+            let local = scale_factor(self.comparisons.iter().filter(|c| {
+                c.matched == MatchedLocation::Road(s.start_from_border.id)
+                    && c.observed.start < s.stop_time
+                    && s.start_time < c.observed.end
+            }));
+            let factor = local.unwrap_or(global);
+            s.num_peds = scale(s.num_peds, factor);
+            s.num_cars = scale(s.num_cars, factor);
+            s.num_bikes = scale(s.num_bikes, factor);
+        }
+    }
+}
+
+// Geoffrey E. Havers' statistic, comparing hourly flows. Handles bins of any length by scaling
+// both counts to an hour first.
+pub fn geh(simulated: usize, observed: usize, bin: Duration) -> f64 {
+    let hours = bin.inner_seconds() / 3600.0;
+    if hours <= 0.0 {
+        return 0.0;
+    }
+    let m = (simulated as f64) / hours;
+    let c = (observed as f64) / hours;
+    if simulated + observed == 0 {
+        return 0.0;
+    }
+    (2.0 * (m - c).powi(2) / (m + c)).sqrt()
+}
+
+// How much to scale demand to match the total observed count, within limits. None if there's
+// nothing to go on.
+pub fn scale_factor<'a, I: Iterator<Item = &'a CountComparison>>(comparisons: I) -> Option<f64> {
+    let mut observed = 0;
+    let mut simulated = 0;
+    for c in comparisons {
+        observed += c.observed.count;
+        simulated += c.simulated;
+    }
+    if observed == 0 || simulated == 0 {
+        return None;
+    }
+    Some(
+        ((observed as f64) / (simulated as f64))
+            .max(MIN_SCALE)
+            .min(MAX_SCALE),
+    )
+}
+
+fn scale(num: usize, factor: f64) -> usize {
+    ((num as f64) * factor).round() as usize
+}
@@ -0,0 +1,274 @@
+use crate::{AgentID, CarID, Event, VehicleType};
+use geom::{Duration, Time};
+use map_model::{IntersectionID, Map, RoadID, Traversable};
+use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::ops;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Emissions {
+    pub co2_grams: f64,
+    pub nox_grams: f64,
+    pub fuel_liters: f64,
+}
+
+impl Emissions {
+    pub const ZERO: Emissions = Emissions {
+        co2_grams: 0.0,
+        nox_grams: 0.0,
+        fuel_liters: 0.0,
+    };
+
+    pub fn describe(&self) -> String {
+        format!(
+            "{:.1} kg CO2, {:.1} g NOx, {:.1} L fuel",
+            self.co2_grams / 1000.0,
+            self.nox_grams,
+            self.fuel_liters
+        )
+    }
+}
+
+impl ops::Add for Emissions {
+    type Output = Emissions;
+
+    fn add(self, other: Emissions) -> Emissions {
+        Emissions {
+            co2_grams: self.co2_grams + other.co2_grams,
+            nox_grams: self.nox_grams + other.nox_grams,
+            fuel_liters: self.fuel_liters + other.fuel_liters,
+        }
+    }
+}
+
+impl ops::AddAssign for Emissions {
+    fn add_assign(&mut self, other: Emissions) {
+        *self = *self + other;
+    }
+}
+
+impl ops::Sub for Emissions {
+    type Output = Emissions;
+
+    fn sub(self, other: Emissions) -> Emissions {
+        Emissions {
+            co2_grams: self.co2_grams - other.co2_grams,
+            nox_grams: self.nox_grams - other.nox_grams,
+            fuel_liters: self.fuel_liters - other.fuel_liters,
+        }
+    }
+}
+
+impl ops::Mul<f64> for Emissions {
+    type Output = Emissions;
+
+    fn mul(self, factor: f64) -> Emissions {
+        Emissions {
+            co2_grams: self.co2_grams * factor,
+            nox_grams: self.nox_grams * factor,
+            fuel_liters: self.fuel_liters * factor,
+        }
+    }
+}
+
+// A deliberately coarse model. Moving at the speed limit costs a fixed amount per km, and any
+// time beyond that spent on a lane or turn counts as idling in traffic.
+// TODO Use a proper speed-dependent curve like COPERT, and distinguish fuel types.
+pub struct EmissionFactors {
+    pub per_km: Emissions,
+    pub per_idle_hour: Emissions,
+}
+
+impl EmissionFactors {
+    pub fn for_vehicle(vt: VehicleType) -> EmissionFactors {
+        match vt {
+            // A typical gasoline passenger car
+            VehicleType::Car => EmissionFactors {
+                per_km: Emissions {
+                    co2_grams: 192.0,
+                    nox_grams: 0.06,
+                    fuel_liters: 0.08,
+                },
+                per_idle_hour: Emissions {
+                    co2_grams: 1400.0,
+                    nox_grams: 0.5,
+                    fuel_liters: 0.6,
+                },
+            },
+            // A typical diesel city bus
+            VehicleType::Bus => EmissionFactors {
+                per_km: Emissions {
+                    co2_grams: 1300.0,
+                    nox_grams: 3.0,
+                    fuel_liters: 0.5,
+                },
+                per_idle_hour: Emissions {
+                    co2_grams: 6700.0,
+                    nox_grams: 20.0,
+                    fuel_liters: 2.5,
+                },
+            },
+            VehicleType::Bike => EmissionFactors {
+                per_km: Emissions::ZERO,
+                per_idle_hour: Emissions::ZERO,
+            },
+        }
+    }
+
+    fn estimate(&self, traversable: Traversable, time_spent: Duration, map: &Map) -> Emissions {
+        let dist = traversable.length(map);
+        let free_flow = dist / traversable.speed_limit(map);
+        let idling = if time_spent > free_flow {
+            time_spent - free_flow
+        } else {
+            Duration::ZERO
+        };
+        self.per_km * (dist.inner_meters() / 1000.0)
+            + self.per_idle_hour * (idling.inner_seconds() / 3600.0)
+    }
+}
+
+// Fed by vehicles entering lanes and turns. The time between entering one traversable and the
+// next gives the average speed over the first.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct EmissionsStats {
+    // Where each vehicle currently is, and since when
+    #[serde(skip_serializing, skip_deserializing)]
+    current: BTreeMap<CarID, (Traversable, Time)>,
+    // When the vehicle left the traversable
+    raw: Vec<(Time, VehicleType, Traversable, Emissions)>,
+}
+
+impl EmissionsStats {
+    pub(crate) fn new() -> EmissionsStats {
+        EmissionsStats {
+            current: BTreeMap::new(),
+            raw: Vec::new(),
+        }
+    }
+
+    pub(crate) fn event(&mut self, ev: &Event, time: Time, map: &Map) {
+        match ev {
+            Event::AgentEntersTraversable(AgentID::Car(car), to) => {
+                self.finish(*car, time, map);
+                self.current.insert(*car, (*to, time));
+            }
+            // TODO Parking and stopping partway along the last lane still count the full length.
+            Event::CarReachedParkingSpot(car, _)
+            | Event::CarOrBikeReachedBorder(car, _)
+            | Event::BikeStoppedAtSidewalk(car, _) => {
+                self.finish(*car, time, map);
+            }
+            _ => {}
+        }
+    }
+
+    fn finish(&mut self, car: CarID, time: Time, map: &Map) {
+        if let Some((on, since)) = self.current.remove(&car) {
+            if car.1 == VehicleType::Bike {
+                return;
+            }
+            let amount = EmissionFactors::for_vehicle(car.1).estimate(on, time - since, map);
+            self.raw.push((time, car.1, on, amount));
+        }
+    }
+
+    pub fn total(&self, now: Time) -> Emissions {
+        let mut sum = Emissions::ZERO;
+        for (t, _, _, amount) in &self.raw {
+            if *t > now {
+                break;
+            }
+            sum += *amount;
+        }
+        sum
+    }
+
+    pub fn per_vehicle_type(&self, now: Time) -> BTreeMap<VehicleType, Emissions> {
+        let mut results = BTreeMap::new();
+        for (t, vt, _, amount) in &self.raw {
+            if *t > now {
+                break;
+            }
+            *results.entry(*vt).or_insert(Emissions::ZERO) += *amount;
+        }
+        results
+    }
+
+    pub fn per_road(&self, now: Time, map: &Map) -> BTreeMap<RoadID, Emissions> {
+        let mut results = BTreeMap::new();
+        for (t, _, on, amount) in &self.raw {
+            if *t > now {
+                break;
+            }
+            if let Traversable::Lane(l) = on {
+                *results
+                    .entry(map.get_l(*l).parent)
+                    .or_insert(Emissions::ZERO) += *amount;
+            }
+        }
+        results
+    }
+
+    pub fn per_intersection(&self, now: Time) -> BTreeMap<IntersectionID, Emissions> {
+        let mut results = BTreeMap::new();
+        for (t, _, on, amount) in &self.raw {
+            if *t > now {
+                break;
+            }
+            if let Traversable::Turn(turn) = on {
+                *results.entry(turn.parent).or_insert(Emissions::ZERO) += *amount;
+            }
+        }
+        results
+    }
+
+    // If there's a baseline, also shows the change relative to it, up to the same time.
+    pub fn describe(&self, now: Time, baseline: Option<&EmissionsStats>, map: &Map) -> Vec<String> {
+        let mut lines = Vec::new();
+        let total = self.total(now);
+        lines.push(format!("Total so far: {}", total.describe()));
+        if let Some(b) = baseline {
+            lines.push(format!(
+                "Change from baseline: {}",
+                (total - b.total(now)).describe()
+            ));
+        }
+
+        let before = baseline.map(|b| b.per_vehicle_type(now));
+        for (vt, amount) in self.per_vehicle_type(now) {
+            if let Some(ref before) = before {
+                let prev = before.get(&vt).cloned().unwrap_or(Emissions::ZERO);
+                lines.push(format!(
+                    "  From {}s: {} (change: {})",
+                    vt,
+                    amount.describe(),
+                    (amount - prev).describe()
+                ));
+            } else {
+                lines.push(format!("  From {}s: {}", vt, amount.describe()));
+            }
+        }
+
+        let mut roads: Vec<(RoadID, Emissions)> = self.per_road(now, map).into_iter().collect();
+        roads.sort_by(|a, b| b.1.co2_grams.partial_cmp(&a.1.co2_grams).unwrap());
+        lines.push(format!("Worst of {} roads:", roads.len()));
+        for (r, amount) in roads.into_iter().take(10) {
+            lines.push(format!(
+                "  {} ({}): {}",
+                map.get_r(r).get_name(),
+                r,
+                amount.describe()
+            ));
+        }
+
+        let mut intersections: Vec<(IntersectionID, Emissions)> =
+            self.per_intersection(now).into_iter().collect();
+        intersections.sort_by(|a, b| b.1.co2_grams.partial_cmp(&a.1.co2_grams).unwrap());
+        lines.push(format!("Worst of {} intersections:", intersections.len()));
+        for (i, amount) in intersections.into_iter().take(10) {
+            lines.push(format!("  {}: {}", i, amount.describe()));
+        }
+        lines
+    }
+}
@@ -2,8 +2,10 @@ use crate::{AgentID, CarID, ParkingSpot, PedestrianID, TripID, TripMode};
 use geom::Duration;
 use map_model::{
     BuildingID, BusRouteID, BusStopID, IntersectionID, LaneID, Path, PathRequest, Traversable,
+    Turn, TurnPriority,
 };
 use serde_derive::{Deserialize, Serialize};
+use std::fmt;
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum Event {
@@ -24,6 +26,8 @@ pub enum Event {
 
     AgentEntersTraversable(AgentID, Traversable),
     IntersectionDelayMeasured(IntersectionID, Duration),
+    // How long after the conflicting turn finished
+    NearConflict(IntersectionID, ConflictKind, Duration),
 
     TripFinished(TripID, TripMode, Duration),
     TripAborted(TripID),
@@ -33,3 +37,50 @@ pub enum Event {
     // to plumb info into Analytics is Event.
     PathAmended(Path),
 }
+
+// Surrogate safety measures: conflicting movements that happen close together in time
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Serialize, Deserialize)]
+pub enum ConflictKind {
+    // A yielding turn started right after a conflicting protected one
+    YieldAfterProtected,
+    // A pedestrian crossing and a turning vehicle
+    PedestrianAndVehicle,
+    VehicleAndVehicle,
+}
+
+impl ConflictKind {
+    pub fn all() -> Vec<ConflictKind> {
+        vec![
+            ConflictKind::YieldAfterProtected,
+            ConflictKind::PedestrianAndVehicle,
+            ConflictKind::VehicleAndVehicle,
+        ]
+    }
+
+    // Our turn is starting soon after their conflicting turn finished. Pedestrians use both
+    // crosswalks and shared sidewalk corners.
+    pub fn classify(
+        ours: &Turn,
+        our_priority: TurnPriority,
+        theirs: &Turn,
+        their_priority: TurnPriority,
+    ) -> ConflictKind {
+        if ours.between_sidewalks() != theirs.between_sidewalks() {
+            ConflictKind::PedestrianAndVehicle
+        } else if our_priority == TurnPriority::Yield && their_priority == TurnPriority::Protected {
+            ConflictKind::YieldAfterProtected
+        } else {
+            ConflictKind::VehicleAndVehicle
+        }
+    }
+}
+
+impl fmt::Display for ConflictKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConflictKind::YieldAfterProtected => write!(f, "yield after protected turn"),
+            ConflictKind::PedestrianAndVehicle => write!(f, "pedestrian and vehicle"),
+            ConflictKind::VehicleAndVehicle => write!(f, "vehicle and vehicle"),
+        }
+    }
+}
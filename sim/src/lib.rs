@@ -1,27 +1,40 @@
 mod analytics;
+mod calibration;
+mod emissions;
 mod events;
 mod make;
 mod mechanics;
+mod metric;
 mod render;
+mod replication;
 mod router;
 mod scheduler;
 mod sim;
 mod transit;
+mod trip_comparison;
 mod trips;
 
 pub use self::analytics::{Analytics, TripPhase};
-pub use self::events::Event;
+pub use self::calibration::{
+    geh, scale_factor, CalibrationReport, CountComparison, CountLocation, MatchedLocation,
+    ObservedCount,
+};
+pub use self::emissions::{EmissionFactors, Emissions, EmissionsStats};
+pub use self::events::{ConflictKind, Event};
 pub use self::make::{
-    ABTest, BorderSpawnOverTime, OriginDestination, Scenario, SeedParkedCars, SimFlags,
-    SpawnOverTime, SpawnTrip, TripSpawner, TripSpec,
+    ABTest, BorderSpawnOverTime, DepartureDistribution, OriginDestination, Scenario,
+    SeedParkedCars, SimFlags, SpawnOverTime, SpawnTrip, TripSpawner, TripSpec,
 };
 pub(crate) use self::mechanics::{
     DrivingSimState, IntersectionSimState, ParkingSimState, WalkingSimState,
 };
+pub use self::metric::{Binning, Metric};
+pub use self::replication::{ConfidenceInterval, ReplicationSummary};
 pub(crate) use self::router::{ActionAtEnd, Router};
 pub(crate) use self::scheduler::{Command, Scheduler};
 pub use self::sim::{Sim, SimOptions};
 pub(crate) use self::transit::TransitSimState;
+pub use self::trip_comparison::{TripComparison, TripDelta, TripDeltaGroup, TripGrouping};
 pub use self::trips::{TripCount, TripResult};
 pub use self::trips::{TripEnd, TripMode, TripStart};
 pub(crate) use self::trips::{TripLeg, TripManager};
@@ -524,7 +537,9 @@ impl TripPositions {
 // We have to do this in the crate where these types are defined. Bit annoying, since it's really
 // kind of an ezgui concept.
 impl Cloneable for ABTest {}
+impl Cloneable for Binning {}
 impl Cloneable for CarID {}
+impl Cloneable for DepartureDistribution {}
 impl Cloneable for Scenario {}
 impl Cloneable for TripID {}
 impl Cloneable for TripMode {}
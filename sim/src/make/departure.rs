@@ -0,0 +1,217 @@
+use abstutil::Timer;
+use geom::{Duration, Time};
+use rand::Rng;
+use rand_xorshift::XorShiftRng;
+use serde_derive::{Deserialize, Serialize};
+
+// Give up on rejection sampling after this many tries and just pick uniformly, with a warning. Only
+// happens when almost none of the distribution lies in the spawning window.
+const MAX_ATTEMPTS: usize = 1000;
+
+// How departures are spread between the start and stop time of a SpawnOverTime or
+// BorderSpawnOverTime. Nothing is ever spawned outside of that window; the distributions are cut
+// off at the edges.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub enum DepartureDistribution {
+    Uniform,
+    Normal {
+        mean: Time,
+        std_dev: Duration,
+    },
+    // Rises linearly from the start time to the peak, then falls to the stop time
+    Triangular {
+        peak: Time,
+    },
+    // Relative weights, linearly interpolated between the points. Zero before the first point and
+    // after the last.
+    Profile {
+        name: String,
+        points: Vec<(Time, f64)>,
+    },
+}
+
+// Scenarios saved before departures could be spread out were uniform
+impl Default for DepartureDistribution {
+    fn default() -> DepartureDistribution {
+        DepartureDistribution::Uniform
+    }
+}
+
+impl DepartureDistribution {
+    pub fn sample(
+        &self,
+        rng: &mut XorShiftRng,
+        start: Time,
+        stop: Time,
+        timer: &mut Timer,
+    ) -> Time {
+        assert!(stop > start);
+        match self {
+            DepartureDistribution::Uniform => uniform(rng, start, stop),
+            DepartureDistribution::Triangular { peak } => {
+                // Inverse transform sampling
+                let a = start.inner_seconds();
+                let b = stop.inner_seconds();
+                let c = peak.inner_seconds().max(a).min(b);
+                let u: f64 = rng.gen_range(0.0, 1.0);
+                let t = if u < (c - a) / (b - a) {
+                    a + (u * (b - a) * (c - a)).sqrt()
+                } else {
+                    b - ((1.0 - u) * (b - a) * (b - c)).sqrt()
+                };
+                Time::START_OF_DAY + Duration::seconds(t)
+            }
+            DepartureDistribution::Normal { mean, std_dev } if *std_dev == Duration::ZERO => {
+                (*mean).max(start).min(stop)
+            }
+            DepartureDistribution::Normal { .. } | DepartureDistribution::Profile { .. } => {
+                if !self.has_weight(start, stop) {
+                    timer.warn(format!(
+                        "The {} departure distribution has no weight between {} and {}, so \
+                         picking a time uniformly",
+                        self.describe(),
+                        start,
+                        stop
+                    ));
+                    return uniform(rng, start, stop);
+                }
+
+                let max = self.max_weight();
+                for _ in 0..MAX_ATTEMPTS {
+                    let t = uniform(rng, start, stop);
+                    if rng.gen_range(0.0, max) < self.weight(t) {
+                        return t;
+                    }
+                }
+                timer.warn(format!(
+                    "Almost none of the {} departure distribution is between {} and {}, so \
+                     picking a time uniformly",
+                    self.describe(),
+                    start,
+                    stop
+                ));
+                uniform(rng, start, stop)
+            }
+        }
+    }
+
+    // Only a profile can be zero over the whole window.
+    pub fn has_weight(&self, start: Time, stop: Time) -> bool {
+        match self {
+            DepartureDistribution::Profile { points, .. } => {
+                // The profile is linear between points, so checking the ends of the window and
+                // every point inside it is enough.
+                self.weight(start) > 0.0
+                    || self.weight(stop) > 0.0
+                    || points
+                        .iter()
+                        .any(|(t, w)| *t >= start && *t < stop && *w > 0.0)
+            }
+            _ => true,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            DepartureDistribution::Uniform => "uniform".to_string(),
+            DepartureDistribution::Normal { mean, std_dev } => {
+                format!("normal around {} (std dev {})", mean, std_dev)
+            }
+            DepartureDistribution::Triangular { peak } => format!("triangular peaking at {}", peak),
+            DepartureDistribution::Profile { name, points } => {
+                format!("profile {} ({} points)", name, points.len())
+            }
+        }
+    }
+
+    // Each non-empty line of the file is "time,weight", with times like 7:30:00. Lines starting
+    // with # are ignored. Times have to be increasing.
+    pub fn load_profile(name: &str) -> Result<DepartureDistribution, abstutil::Error> {
+        let path = abstutil::path_departure_profile(name);
+        let contents = std::fs::read_to_string(&path)
+            .map_err(|err| abstutil::Error::new(format!("Couldn't read {}: {}", path, err)))?;
+        let mut points: Vec<(Time, f64)> = Vec::new();
+        for (idx, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let parse = || -> Result<(Time, f64), abstutil::Error> {
+                let parts: Vec<&str> = line.split(',').collect();
+                if parts.len() != 2 {
+                    return Err(abstutil::Error::new(format!(
+                        "expected time,weight, got {}",
+                        line
+                    )));
+                }
+                let time = Time::parse(parts[0].trim())?;
+                let weight = parts[1].trim().parse::<f64>()?;
+                if weight < 0.0 {
+                    return Err(abstutil::Error::new(format!("negative weight {}", weight)));
+                }
+                Ok((time, weight))
+            };
+            let (time, weight) =
+                parse().map_err(|err| err.context(format!("{} line {}", path, idx + 1)))?;
+            if let Some((last, _)) = points.last() {
+                if time <= *last {
+                    return Err(abstutil::Error::new(format!(
+                        "{} line {}: {} isn't after {}",
+                        path,
+                        idx + 1,
+                        time,
+                        last
+                    )));
+                }
+            }
+            points.push((time, weight));
+        }
+        if points.len() < 2 {
+            return Err(abstutil::Error::new(format!(
+                "{} needs at least 2 points",
+                path
+            )));
+        }
+        Ok(DepartureDistribution::Profile {
+            name: name.to_string(),
+            points,
+        })
+    }
+
+    // Unnormalized density
+    fn weight(&self, t: Time) -> f64 {
+        match self {
+            DepartureDistribution::Uniform | DepartureDistribution::Triangular { .. } => {
+                unreachable!()
+            }
+            DepartureDistribution::Normal { mean, std_dev } => {
+                let z = (t - *mean) / *std_dev;
+                (-0.5 * z * z).exp()
+            }
+            DepartureDistribution::Profile { points, .. } => {
+                for pair in points.windows(2) {
+                    let (t1, w1) = pair[0];
+                    let (t2, w2) = pair[1];
+                    if t >= t1 && t <= t2 {
+                        let pct = (t - t1) / (t2 - t1);
+                        return w1 + pct * (w2 - w1);
+                    }
+                }
+                0.0
+            }
+        }
+    }
+
+    fn max_weight(&self) -> f64 {
+        match self {
+            DepartureDistribution::Profile { points, .. } => {
+                points.iter().map(|(_, w)| *w).fold(0.0, f64::max)
+            }
+            _ => 1.0,
+        }
+    }
+}
+
+fn uniform(rng: &mut XorShiftRng, low: Time, high: Time) -> Time {
+    Time::START_OF_DAY + Duration::seconds(rng.gen_range(low.inner_seconds(), high.inner_seconds()))
+}
@@ -1,9 +1,11 @@
 mod a_b_test;
+mod departure;
 mod load;
 mod scenario;
 mod spawner;
 
 pub use self::a_b_test::ABTest;
+pub use self::departure::DepartureDistribution;
 pub use self::load::SimFlags;
 pub use self::scenario::{
     BorderSpawnOverTime, OriginDestination, Scenario, SeedParkedCars, SpawnOverTime, SpawnTrip,
@@ -1,6 +1,6 @@
 use crate::{
-    CarID, DrivingGoal, ParkingSpot, SidewalkSpot, Sim, TripSpec, VehicleSpec, VehicleType,
-    BIKE_LENGTH, MAX_CAR_LENGTH, MIN_CAR_LENGTH,
+    CarID, DepartureDistribution, DrivingGoal, ParkingSpot, SidewalkSpot, Sim, TripSpec,
+    VehicleSpec, VehicleType, BIKE_LENGTH, MAX_CAR_LENGTH, MIN_CAR_LENGTH,
 };
 use abstutil::{fork_rng, prettyprint_usize, Timer, WeightedUsizeChoice};
 use geom::{Distance, Duration, Speed, Time};
@@ -36,9 +36,10 @@ pub struct Scenario {
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct SpawnOverTime {
     pub num_agents: usize,
-    // TODO use https://docs.rs/rand/0.5.5/rand/distributions/struct.Normal.html
     pub start_time: Time,
     pub stop_time: Time,
+    #[serde(default)]
+    pub departure: DepartureDistribution,
     pub start_from_neighborhood: String,
     pub goal: OriginDestination,
     pub percent_biking: f64,
@@ -51,9 +52,10 @@ pub struct BorderSpawnOverTime {
     pub num_cars: usize,
     pub num_bikes: usize,
     pub percent_use_transit: f64,
-    // TODO use https://docs.rs/rand/0.5.5/rand/distributions/struct.Normal.html
     pub start_time: Time,
     pub stop_time: Time,
+    #[serde(default)]
+    pub departure: DepartureDistribution,
     pub start_from_border: DirectedRoadID,
     pub goal: OriginDestination,
 }
@@ -66,7 +68,17 @@ pub struct SeedParkedCars {
 
 impl Scenario {
     pub fn describe(&self) -> Vec<String> {
-        vec![
+        let mut departures: BTreeMap<String, usize> = BTreeMap::new();
+        for d in self
+            .spawn_over_time
+            .iter()
+            .map(|s| &s.departure)
+            .chain(self.border_spawn_over_time.iter().map(|s| &s.departure))
+        {
+            *departures.entry(d.describe()).or_insert(0) += 1;
+        }
+
+        let mut lines = vec![
             format!("{} for {}", self.scenario_name, self.map_name),
             format!(
                 "{} SeedParkedCars",
@@ -81,7 +93,11 @@ impl Scenario {
                 prettyprint_usize(self.border_spawn_over_time.len())
             ),
             format!("{} SpawnTrip", prettyprint_usize(self.individ_trips.len())),
-        ]
+        ];
+        for (d, cnt) in departures {
+            lines.push(format!("  {} departures: {}", d, cnt));
+        }
+        lines
     }
 
     // TODO may need to fork the RNG a bit more
@@ -181,6 +197,7 @@ impl Scenario {
                 num_agents: 100,
                 start_time: Time::START_OF_DAY,
                 stop_time: Time::START_OF_DAY + Duration::seconds(5.0),
+                departure: DepartureDistribution::Uniform,
                 start_from_neighborhood: "_everywhere_".to_string(),
                 goal: OriginDestination::Neighborhood("_everywhere_".to_string()),
                 percent_biking: 0.5,
@@ -197,6 +214,7 @@ impl Scenario {
                     num_bikes: 10,
                     start_time: Time::START_OF_DAY,
                     stop_time: Time::START_OF_DAY + Duration::seconds(5.0),
+                    departure: DepartureDistribution::Uniform,
                     start_from_border: i.some_outgoing_road(map),
                     goal: OriginDestination::Neighborhood("_everywhere_".to_string()),
                     percent_use_transit: 0.5,
@@ -210,6 +228,7 @@ impl Scenario {
                 num_agents: 10,
                 start_time: Time::START_OF_DAY,
                 stop_time: Time::START_OF_DAY + Duration::seconds(5.0),
+                departure: DepartureDistribution::Uniform,
                 start_from_neighborhood: "_everywhere_".to_string(),
                 goal: OriginDestination::EndOfRoad(i.some_incoming_road(map)),
                 percent_biking: 0.5,
@@ -248,6 +267,7 @@ impl Scenario {
                 num_agents: num_agents,
                 start_time: Time::START_OF_DAY,
                 stop_time: Time::START_OF_DAY + Duration::seconds(5.0),
+                departure: DepartureDistribution::Uniform,
                 start_from_neighborhood: "_everywhere_".to_string(),
                 goal: OriginDestination::Neighborhood("_everywhere_".to_string()),
                 percent_biking: 0.5,
@@ -314,7 +334,9 @@ impl SpawnOverTime {
         map: &Map,
         timer: &mut Timer,
     ) {
-        let spawn_time = rand_time(rng, self.start_time, self.stop_time);
+        let spawn_time = self
+            .departure
+            .sample(rng, self.start_time, self.stop_time, timer);
         // Note that it's fine for agents to start/end at the same building. Later we might
         // want a better assignment of people per household, or workers per office building.
         let from_bldg = *neighborhoods[&self.start_from_neighborhood]
@@ -456,7 +478,9 @@ impl BorderSpawnOverTime {
         };
 
         for _ in 0..self.num_peds {
-            let spawn_time = rand_time(rng, self.start_time, self.stop_time);
+            let spawn_time = self
+                .departure
+                .sample(rng, self.start_time, self.stop_time, timer);
             if let Some(goal) = self.goal.pick_walking_goal(map, &neighborhoods, rng, timer) {
                 if rng.gen_bool(self.percent_use_transit) {
                     // TODO This throws away some work. It also sequentially does expensive
@@ -522,7 +546,9 @@ impl BorderSpawnOverTime {
             return;
         }
         for _ in 0..self.num_cars {
-            let spawn_time = rand_time(rng, self.start_time, self.stop_time);
+            let spawn_time = self
+                .departure
+                .sample(rng, self.start_time, self.stop_time, timer);
             if let Some(goal) =
                 self.goal
                     .pick_driving_goal(PathConstraints::Car, map, &neighborhoods, rng, timer)
@@ -564,7 +590,9 @@ impl BorderSpawnOverTime {
         }
 
         for _ in 0..self.num_bikes {
-            let spawn_time = rand_time(rng, self.start_time, self.stop_time);
+            let spawn_time = self
+                .departure
+                .sample(rng, self.start_time, self.stop_time, timer);
             if let Some(goal) =
                 self.goal
                     .pick_driving_goal(PathConstraints::Bike, map, &neighborhoods, rng, timer)
@@ -810,11 +838,6 @@ fn find_spot_near_building(
     }
 }
 
-fn rand_time(rng: &mut XorShiftRng, low: Time, high: Time) -> Time {
-    assert!(high > low);
-    Time::START_OF_DAY + Duration::seconds(rng.gen_range(low.inner_seconds(), high.inner_seconds()))
-}
-
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub enum SpawnTrip {
     CarAppearing {
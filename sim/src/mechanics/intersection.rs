@@ -1,18 +1,24 @@
 use crate::mechanics::car::Car;
 use crate::mechanics::Queue;
-use crate::{AgentID, Command, Event, Scheduler, Speed};
+use crate::{AgentID, Command, ConflictKind, Event, Scheduler, Speed};
 use abstutil::{deserialize_btreemap, serialize_btreemap};
 use derivative::Derivative;
 use geom::{Duration, Time};
 use map_model::{
-    ControlStopSign, ControlTrafficSignal, IntersectionID, LaneID, Map, TurnID, TurnPriority,
-    TurnType,
+    ControlMerge, ControlRoundabout, ControlStopSign, ControlTrafficSignal, IntersectionID, LaneID,
+    Map, TurnID, TurnPriority, TurnType,
 };
 use serde_derive::{Deserialize, Serialize};
 use std::collections::{BTreeMap, BTreeSet, HashSet};
 
 const WAIT_AT_STOP_SIGN: Duration = Duration::const_seconds(0.5);
 const WAIT_BEFORE_YIELD_AT_TRAFFIC_SIGNAL: Duration = Duration::const_seconds(0.2);
+// The smallest gap after another vehicle that somebody on a freeway on-ramp will merge into, or
+// somebody entering a roundabout will pull out in front of.
+const MERGE_CRITICAL_GAP: Duration = Duration::const_seconds(2.0);
+// Starting a turn this soon after a conflicting turn finished counts as a near-conflict. This is
+// the post-encroachment time used by surrogate safety measures.
+const NEAR_CONFLICT_GAP: Duration = Duration::const_seconds(1.5);
 
 #[derive(Serialize, Deserialize, PartialEq, Clone)]
 pub struct IntersectionSimState {
@@ -33,6 +39,20 @@ struct State {
         deserialize_with = "deserialize_btreemap"
     )]
     waiting: BTreeMap<Request, Time>,
+    // Only for merges and roundabouts: when somebody last entered each outgoing lane.
+    #[serde(
+        serialize_with = "serialize_btreemap",
+        deserialize_with = "deserialize_btreemap"
+    )]
+    last_entry: BTreeMap<LaneID, Time>,
+    // The priority each accepted request had when it started
+    #[serde(
+        serialize_with = "serialize_btreemap",
+        deserialize_with = "deserialize_btreemap"
+    )]
+    accepted_priority: BTreeMap<Request, TurnPriority>,
+    // Turns that finished within the last NEAR_CONFLICT_GAP, with their priority
+    recently_finished: Vec<(TurnID, Time, TurnPriority)>,
 }
 
 impl IntersectionSimState {
@@ -55,6 +75,9 @@ impl IntersectionSimState {
                     id: i.id,
                     accepted: BTreeSet::new(),
                     waiting: BTreeMap::new(),
+                    last_entry: BTreeMap::new(),
+                    accepted_priority: BTreeMap::new(),
+                    recently_finished: Vec::new(),
                 },
             );
             if i.is_traffic_signal() && !use_freeform_policy_everywhere {
@@ -80,7 +103,13 @@ impl IntersectionSimState {
         map: &Map,
     ) {
         let state = self.state.get_mut(&turn.parent).unwrap();
-        assert!(state.accepted.remove(&Request { agent, turn }));
+        let req = Request { agent, turn };
+        assert!(state.accepted.remove(&req));
+        let priority = state.accepted_priority.remove(&req).unwrap();
+        state
+            .recently_finished
+            .retain(|(_, t, _)| now - *t <= NEAR_CONFLICT_GAP);
+        state.recently_finished.push((turn, now, priority));
         if map.get_t(turn).turn_type != TurnType::SharedSidewalkCorner {
             self.wakeup_waiting(now, turn.parent, scheduler, map);
         }
@@ -147,6 +176,22 @@ impl IntersectionSimState {
                     yielding.push(req);
                 }
             }
+        } else if let Some(ref roundabout) = map.maybe_get_roundabout(i) {
+            for (req, _) in all {
+                if roundabout.get_priority(req.turn, map) == TurnPriority::Protected {
+                    protected.push(req);
+                } else {
+                    yielding.push(req);
+                }
+            }
+        } else if let Some(ref merge) = map.maybe_get_merge(i) {
+            for (req, _) in all {
+                if merge.get_priority(req.turn, map) == TurnPriority::Protected {
+                    protected.push(req);
+                } else {
+                    yielding.push(req);
+                }
+            }
         } else {
             assert!(map.get_i(i).is_border());
         };
@@ -209,6 +254,10 @@ impl IntersectionSimState {
             state.traffic_signal_policy(signal, &req, speed, now, map, scheduler)
         } else if let Some(ref sign) = map.maybe_get_stop_sign(state.id) {
             state.stop_sign_policy(sign, &req, now, map, scheduler)
+        } else if let Some(ref roundabout) = map.maybe_get_roundabout(state.id) {
+            state.roundabout_policy(roundabout, &req, now, map, scheduler)
+        } else if let Some(ref merge) = map.maybe_get_merge(state.id) {
+            state.merge_policy(merge, &req, now, map, scheduler)
         } else {
             unreachable!()
         };
@@ -238,6 +287,19 @@ impl IntersectionSimState {
             self.events
                 .push(Event::IntersectionDelayMeasured(turn.parent, delay));
         }
+        if map.maybe_get_merge(state.id).is_some() || map.maybe_get_roundabout(state.id).is_some() {
+            state.last_entry.insert(turn.dst, now);
+        }
+        let priority = if self.use_freeform_policy_everywhere {
+            TurnPriority::Protected
+        } else {
+            state.priority(turn, now, map)
+        };
+        if let Some((kind, gap)) = state.near_conflict(turn, priority, now, map) {
+            self.events
+                .push(Event::NearConflict(turn.parent, kind, gap));
+        }
+        state.accepted_priority.insert(req.clone(), priority);
         state.accepted.insert(req);
         /*if debug {
             println!("{}: {} going!", now, agent)
@@ -251,6 +313,10 @@ impl IntersectionSimState {
             println!("{}", abstutil::to_json(sign));
         } else if let Some(ref signal) = map.maybe_get_traffic_signal(id) {
             println!("{}", abstutil::to_json(signal));
+        } else if let Some(ref roundabout) = map.maybe_get_roundabout(id) {
+            println!("{}", abstutil::to_json(roundabout));
+        } else if let Some(ref merge) = map.maybe_get_merge(id) {
+            println!("{}", abstutil::to_json(merge));
         } else {
             println!("Border");
         }
@@ -277,6 +343,41 @@ impl State {
             .any(|req| map.get_t(req.turn).conflicts_with(turn))
     }
 
+    fn priority(&self, t: TurnID, now: Time, map: &Map) -> TurnPriority {
+        if let Some(ref signal) = map.maybe_get_traffic_signal(self.id) {
+            let (_, phase, _) = signal.current_phase_and_remaining_time(now);
+            phase.get_priority_of_turn(t, signal)
+        } else if let Some(ref sign) = map.maybe_get_stop_sign(self.id) {
+            sign.get_priority(t, map)
+        } else if let Some(ref roundabout) = map.maybe_get_roundabout(self.id) {
+            roundabout.get_priority(t, map)
+        } else if let Some(ref merge) = map.maybe_get_merge(self.id) {
+            merge.get_priority(t, map)
+        } else {
+            TurnPriority::Protected
+        }
+    }
+
+    // Did a conflicting turn finish just before this one starts? Only the most recent one counts.
+    fn near_conflict(
+        &self,
+        t: TurnID,
+        our_priority: TurnPriority,
+        now: Time,
+        map: &Map,
+    ) -> Option<(ConflictKind, Duration)> {
+        let turn = map.get_t(t);
+        let (other, finished, their_priority) =
+            self.recently_finished
+                .iter()
+                .rev()
+                .find(|(other, finished, _)| {
+                    now - *finished <= NEAR_CONFLICT_GAP && map.get_t(*other).conflicts_with(turn)
+                })?;
+        let kind = ConflictKind::classify(turn, our_priority, map.get_t(*other), *their_priority);
+        Some((kind, now - *finished))
+    }
+
     fn freeform_policy(&self, req: &Request, map: &Map) -> bool {
         // Allow concurrent turns that don't conflict
         if self.any_accepted_conflict_with(req.turn, map) {
@@ -331,6 +432,78 @@ impl State {
         true
     }
 
+    fn roundabout_policy(
+        &self,
+        roundabout: &ControlRoundabout,
+        req: &Request,
+        now: Time,
+        map: &Map,
+        scheduler: &mut Scheduler,
+    ) -> bool {
+        if self.any_accepted_conflict_with(req.turn, map) {
+            return false;
+        }
+        if roundabout.get_priority(req.turn, map) == TurnPriority::Protected {
+            return true;
+        }
+
+        // Yield on entry. Don't refuse just because somebody circulating is also waiting -- when
+        // the ring is full, they're waiting on the next segment, and that would gridlock every
+        // entry. Like in stop_sign_policy, wakeup_waiting gives Protected turns a head-start, so
+        // if a circulating vehicle could've gone by now, it already has, and shows up in
+        // last_entry.
+
+        // Gap acceptance, like merge_policy: only pull out onto the ring if the last vehicle on it
+        // is far enough ahead. Turning straight from an entry to an exit doesn't touch the ring.
+        // TODO Make sure we can finish entering before an approaching circulating vehicle arrives.
+        if !roundabout
+            .ring_roads
+            .contains(&map.get_l(req.turn.dst).parent)
+        {
+            return true;
+        }
+        if let Some(t) = self.last_entry.get(&req.turn.dst) {
+            if now < *t + MERGE_CRITICAL_GAP {
+                scheduler.update(*t + MERGE_CRITICAL_GAP, Command::update_agent(req.agent));
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn merge_policy(
+        &self,
+        merge: &ControlMerge,
+        req: &Request,
+        now: Time,
+        map: &Map,
+        scheduler: &mut Scheduler,
+    ) -> bool {
+        if self.any_accepted_conflict_with(req.turn, map) {
+            return false;
+        }
+        if merge.get_priority(req.turn, map) == TurnPriority::Protected {
+            return true;
+        }
+
+        // Don't refuse just because somebody on the mainline is waiting too -- when traffic backs
+        // up, they're stuck behind the next vehicle, and that would gridlock the ramp. Like in
+        // stop_sign_policy, wakeup_waiting gives the mainline a head-start, and anybody who
+        // already went shows up in last_entry.
+
+        // Gap acceptance: only merge if the last vehicle to enter the target lane is far enough
+        // ahead.
+        if let Some(t) = self.last_entry.get(&req.turn.dst) {
+            if now < *t + MERGE_CRITICAL_GAP {
+                scheduler.update(*t + MERGE_CRITICAL_GAP, Command::update_agent(req.agent));
+                return false;
+            }
+        }
+
+        true
+    }
+
     fn traffic_signal_policy(
         &self,
         signal: &ControlTrafficSignal,
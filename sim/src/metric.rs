@@ -0,0 +1,132 @@
+use map_model::{BuildingID, IntersectionID, LaneID, Map, RoadID};
+use std::collections::BTreeMap;
+
+// A number for any roads, lanes, intersections, and buildings. Anything that can produce one of
+// these -- the sim's Analytics or a CSV file from some other tool -- can be shown as a heatmap,
+// without writing any new GUI code.
+pub struct Metric {
+    pub name: String,
+    pub roads: BTreeMap<RoadID, f64>,
+    pub lanes: BTreeMap<LaneID, f64>,
+    pub intersections: BTreeMap<IntersectionID, f64>,
+    pub buildings: BTreeMap<BuildingID, f64>,
+}
+
+impl Metric {
+    pub fn new<S: Into<String>>(name: S) -> Metric {
+        Metric {
+            name: name.into(),
+            roads: BTreeMap::new(),
+            lanes: BTreeMap::new(),
+            intersections: BTreeMap::new(),
+            buildings: BTreeMap::new(),
+        }
+    }
+
+    // Each line is "kind,id,value", where kind is road, lane, intersection, or building, and id is
+    // the number from the info panel. A header line starting with "kind" is skipped.
+    pub fn load_csv(path: &str, map: &Map) -> Result<Metric, String> {
+        let contents =
+            std::fs::read_to_string(path).map_err(|err| format!("Can't read {}: {}", path, err))?;
+        let name = std::path::Path::new(path)
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.to_string());
+        let mut metric = Metric::new(name);
+
+        for (idx, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || (idx == 0 && line.starts_with("kind")) {
+                continue;
+            }
+            let parts: Vec<&str> = line.split(',').map(|x| x.trim()).collect();
+            if parts.len() != 3 {
+                return Err(format!("Line {} isn't kind,id,value: {}", idx + 1, line));
+            }
+            let id = parts[1]
+                .parse::<usize>()
+                .map_err(|_| format!("Line {} has a bad id: {}", idx + 1, parts[1]))?;
+            let value = parts[2]
+                .parse::<f64>()
+                .map_err(|_| format!("Line {} has a bad value: {}", idx + 1, parts[2]))?;
+            if !value.is_finite() {
+                return Err(format!("Line {} has a bad value: {}", idx + 1, parts[2]));
+            }
+            let found = match parts[0] {
+                "road" => {
+                    metric.roads.insert(RoadID(id), value);
+                    map.maybe_get_r(RoadID(id)).is_some()
+                }
+                "lane" => {
+                    metric.lanes.insert(LaneID(id), value);
+                    map.maybe_get_l(LaneID(id)).is_some()
+                }
+                "intersection" => {
+                    metric.intersections.insert(IntersectionID(id), value);
+                    map.maybe_get_i(IntersectionID(id)).is_some()
+                }
+                "building" => {
+                    metric.buildings.insert(BuildingID(id), value);
+                    map.maybe_get_b(BuildingID(id)).is_some()
+                }
+                x => {
+                    return Err(format!("Line {} has an unknown kind: {}", idx + 1, x));
+                }
+            };
+            if !found {
+                return Err(format!(
+                    "Line {}: {} {} isn't in {}",
+                    idx + 1,
+                    parts[0],
+                    id,
+                    map.get_name()
+                ));
+            }
+        }
+        Ok(metric)
+    }
+
+    // Every value, lowest first
+    pub fn sorted_values(&self) -> Vec<f64> {
+        let mut values: Vec<f64> = self
+            .roads
+            .values()
+            .chain(self.lanes.values())
+            .chain(self.intersections.values())
+            .chain(self.buildings.values())
+            .cloned()
+            .collect();
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        values
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Binning {
+    Continuous,
+    // Each bin covers the same range of values
+    EqualIntervals(usize),
+    // Each bin has about the same number of things
+    Quantiles(usize),
+}
+
+impl Binning {
+    // The upper bound of each bin, except for the last, from values sorted lowest first. Empty for
+    // a continuous scale or no values.
+    pub fn thresholds(self, values: &[f64]) -> Vec<f64> {
+        if values.is_empty() {
+            return Vec::new();
+        }
+        let min = values.first().cloned().unwrap_or(0.0);
+        let max = values.last().cloned().unwrap_or(0.0);
+        match self {
+            Binning::Continuous => Vec::new(),
+            Binning::EqualIntervals(n) => (1..n)
+                .map(|i| min + (max - min) * (i as f64) / (n as f64))
+                .collect(),
+            Binning::Quantiles(n) => (1..n)
+                .filter_map(|i| values.get(values.len() * i / n).cloned())
+                .collect(),
+        }
+    }
+}
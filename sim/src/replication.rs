@@ -0,0 +1,285 @@
+use crate::{Analytics, Scenario, Sim, SimOptions, TripMode};
+use abstutil::Timer;
+use geom::{Duration, Time};
+use map_model::{BusRouteID, IntersectionID, Map};
+use rand::SeedableRng;
+use rand_xorshift::XorShiftRng;
+use std::collections::BTreeMap;
+
+// Two-sided 95% critical values of Student's t distribution, indexed by degrees of freedom - 1.
+// Past the end, the normal approximation is close enough.
+const T_95: [f64; 30] = [
+    12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228, 2.201, 2.179, 2.160,
+    2.145, 2.131, 2.120, 2.110, 2.101, 2.093, 2.086, 2.080, 2.074, 2.069, 2.064, 2.060, 2.056,
+    2.052, 2.048, 2.045, 2.042,
+];
+const Z_95: f64 = 1.96;
+
+// The mean of some per-run value and the half-width of its 95% confidence interval.
+#[derive(Clone, Debug)]
+pub struct ConfidenceInterval {
+    pub mean: f64,
+    pub margin: f64,
+    // How many runs produced a value
+    pub runs: usize,
+}
+
+impl ConfidenceInterval {
+    pub fn new(samples: &Vec<f64>) -> ConfidenceInterval {
+        let n = samples.len();
+        if n == 0 {
+            return ConfidenceInterval {
+                mean: 0.0,
+                margin: std::f64::INFINITY,
+                runs: 0,
+            };
+        }
+        let mean = samples.iter().sum::<f64>() / (n as f64);
+        if n == 1 {
+            return ConfidenceInterval {
+                mean,
+                margin: std::f64::INFINITY,
+                runs: 1,
+            };
+        }
+        let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / ((n - 1) as f64);
+        let t = T_95.get(n - 2).cloned().unwrap_or(Z_95);
+        ConfidenceInterval {
+            mean,
+            margin: t * (variance / (n as f64)).sqrt(),
+            runs: n,
+        }
+    }
+
+    pub fn describe_duration(&self) -> String {
+        match self.runs {
+            0 => "no runs".to_string(),
+            1 => format!("{} (1 run)", Duration::seconds(self.mean)),
+            _ => format!(
+                "{} ± {} ({} runs)",
+                Duration::seconds(self.mean),
+                Duration::seconds(self.margin),
+                self.runs
+            ),
+        }
+    }
+
+    pub fn describe_count(&self) -> String {
+        match self.runs {
+            0 => "no runs".to_string(),
+            1 => format!("{:.1} (1 run)", self.mean),
+            _ => format!("{:.1} ± {:.1} ({} runs)", self.mean, self.margin, self.runs),
+        }
+    }
+}
+
+// The same scenario and map edits, run with different RNG seeds, so changes can be told apart
+// from noise.
+#[derive(Clone, Debug)]
+pub struct ReplicationSummary {
+    pub scenario_name: String,
+    pub edits_name: String,
+    pub seeds: Vec<u8>,
+    pub finished_trips: ConfidenceInterval,
+    pub aborted_trips: ConfidenceInterval,
+    // Mean trip duration per run
+    pub trip_durations: ConfidenceInterval,
+    pub trip_durations_per_mode: BTreeMap<TripMode, ConfidenceInterval>,
+    // Mean delay per run. Only intersections with some delay in a run count for that run.
+    pub intersection_delays: BTreeMap<IntersectionID, ConfidenceInterval>,
+    // Mean time between consecutive buses at the same stop, per run
+    pub bus_headways: BTreeMap<BusRouteID, ConfidenceInterval>,
+}
+
+// What's kept from each run. The full Analytics are too big to hold onto for many runs.
+struct RunResult {
+    finished_trips: f64,
+    aborted_trips: f64,
+    trip_durations: Option<f64>,
+    trip_durations_per_mode: BTreeMap<TripMode, f64>,
+    intersection_delays: BTreeMap<IntersectionID, f64>,
+    bus_headways: BTreeMap<BusRouteID, f64>,
+}
+
+impl ReplicationSummary {
+    // Edits should already be applied to the map. With parallel, all of the runs happen at once,
+    // which needs memory for that many simulations.
+    pub fn run(
+        map: &Map,
+        scenario: &Scenario,
+        opts: &SimOptions,
+        seeds: Vec<u8>,
+        parallel: bool,
+        timer: &mut Timer,
+    ) -> ReplicationSummary {
+        let results: Vec<RunResult> = if parallel {
+            timer.parallelize("run replications", seeds.clone(), |seed| {
+                run_once(map, scenario, opts, seed, &mut Timer::throwaway())
+            })
+        } else {
+            timer.start_iter("run replications", seeds.len());
+            seeds
+                .iter()
+                .map(|seed| {
+                    timer.next();
+                    run_once(map, scenario, opts, *seed, timer)
+                })
+                .collect()
+        };
+
+        ReplicationSummary {
+            scenario_name: scenario.scenario_name.clone(),
+            edits_name: map.get_edits().edits_name.clone(),
+            seeds,
+            finished_trips: ConfidenceInterval::new(
+                &results.iter().map(|r| r.finished_trips).collect(),
+            ),
+            aborted_trips: ConfidenceInterval::new(
+                &results.iter().map(|r| r.aborted_trips).collect(),
+            ),
+            trip_durations: ConfidenceInterval::new(
+                &results.iter().filter_map(|r| r.trip_durations).collect(),
+            ),
+            trip_durations_per_mode: aggregate(results.iter().map(|r| &r.trip_durations_per_mode)),
+            intersection_delays: aggregate(results.iter().map(|r| &r.intersection_delays)),
+            bus_headways: aggregate(results.iter().map(|r| &r.bus_headways)),
+        }
+    }
+
+    pub fn describe(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!(
+                "{} with {}, {} runs",
+                self.scenario_name,
+                self.edits_name,
+                self.seeds.len()
+            ),
+            format!("Finished trips: {}", self.finished_trips.describe_count()),
+            format!("Aborted trips: {}", self.aborted_trips.describe_count()),
+        ];
+        if self.trip_durations.runs > 0 {
+            lines.push(format!(
+                "Mean trip duration: {}",
+                self.trip_durations.describe_duration()
+            ));
+        }
+        for (mode, ci) in &self.trip_durations_per_mode {
+            lines.push(format!("  {:?} trips: {}", mode, ci.describe_duration()));
+        }
+
+        // Only the worst intersections
+        let mut delays: Vec<(&IntersectionID, &ConfidenceInterval)> =
+            self.intersection_delays.iter().collect();
+        delays.sort_by(|a, b| b.1.mean.partial_cmp(&a.1.mean).unwrap());
+        lines.push(format!(
+            "Mean delay at the worst of {} intersections:",
+            delays.len()
+        ));
+        for (i, ci) in delays.into_iter().take(10) {
+            lines.push(format!("  {}: {}", i, ci.describe_duration()));
+        }
+
+        for (r, ci) in &self.bus_headways {
+            lines.push(format!("Bus headway on {}: {}", r, ci.describe_duration()));
+        }
+        lines
+    }
+}
+
+fn run_once(
+    map: &Map,
+    scenario: &Scenario,
+    opts: &SimOptions,
+    seed: u8,
+    timer: &mut Timer,
+) -> RunResult {
+    let mut opts = opts.clone();
+    opts.run_name = format!("{}_seed{}", opts.run_name, seed);
+    let mut rng = XorShiftRng::from_seed([seed; 16]);
+    let mut sim = Sim::new(map, opts, timer);
+    scenario.instantiate(&mut sim, map, &mut rng, timer);
+    sim.just_run_until_done(map, None);
+    summarize(sim.get_analytics())
+}
+
+fn summarize(analytics: &Analytics) -> RunResult {
+    let mut aborted_trips = 0;
+    let mut all_durations = Vec::new();
+    let mut durations_per_mode: BTreeMap<TripMode, Vec<f64>> = BTreeMap::new();
+    for (_, _, mode, dt) in &analytics.finished_trips {
+        if let Some(mode) = mode {
+            all_durations.push(dt.inner_seconds());
+            durations_per_mode
+                .entry(*mode)
+                .or_insert_with(Vec::new)
+                .push(dt.inner_seconds());
+        } else {
+            aborted_trips += 1;
+        }
+    }
+
+    let mut intersection_delays = BTreeMap::new();
+    for (i, list) in &analytics.intersection_delays {
+        if let Some(avg) = mean(list.iter().map(|(_, dt)| dt.inner_seconds()).collect()) {
+            intersection_delays.insert(*i, avg);
+        }
+    }
+
+    let mut arrivals: BTreeMap<BusRouteID, BTreeMap<_, Vec<Time>>> = BTreeMap::new();
+    for (t, _, route, stop) in &analytics.bus_arrivals {
+        arrivals
+            .entry(*route)
+            .or_insert_with(BTreeMap::new)
+            .entry(*stop)
+            .or_insert_with(Vec::new)
+            .push(*t);
+    }
+    let mut bus_headways = BTreeMap::new();
+    for (route, per_stop) in arrivals {
+        let mut headways = Vec::new();
+        for (_, mut times) in per_stop {
+            times.sort();
+            for pair in times.windows(2) {
+                headways.push((pair[1] - pair[0]).inner_seconds());
+            }
+        }
+        if let Some(avg) = mean(headways) {
+            bus_headways.insert(route, avg);
+        }
+    }
+
+    RunResult {
+        finished_trips: all_durations.len() as f64,
+        aborted_trips: aborted_trips as f64,
+        trip_durations: mean(all_durations),
+        trip_durations_per_mode: durations_per_mode
+            .into_iter()
+            .filter_map(|(mode, list)| mean(list).map(|avg| (mode, avg)))
+            .collect(),
+        intersection_delays,
+        bus_headways,
+    }
+}
+
+fn mean(list: Vec<f64>) -> Option<f64> {
+    if list.is_empty() {
+        None
+    } else {
+        Some(list.iter().sum::<f64>() / (list.len() as f64))
+    }
+}
+
+fn aggregate<'a, K: Ord + Copy + 'a, I: Iterator<Item = &'a BTreeMap<K, f64>>>(
+    runs: I,
+) -> BTreeMap<K, ConfidenceInterval> {
+    let mut samples: BTreeMap<K, Vec<f64>> = BTreeMap::new();
+    for run in runs {
+        for (key, value) in run {
+            samples.entry(*key).or_insert_with(Vec::new).push(*value);
+        }
+    }
+    samples
+        .into_iter()
+        .map(|(key, list)| (key, ConfidenceInterval::new(&list)))
+        .collect()
+}
@@ -0,0 +1,210 @@
+use crate::{Analytics, Sim, TripEnd, TripID, TripMode, TripStart};
+use geom::{Duration, Time};
+use map_model::{BuildingID, FullNeighborhoodInfo, Map};
+use std::collections::{BTreeMap, HashMap};
+
+// One trip that finished in both the baseline and the current run.
+#[derive(Clone, Debug)]
+pub struct TripDelta {
+    pub id: TripID,
+    pub mode: TripMode,
+    pub departure: Time,
+    pub from_neighborhood: String,
+    pub to_neighborhood: String,
+    pub baseline: Duration,
+    pub now: Duration,
+}
+
+impl TripDelta {
+    // Positive means faster
+    pub fn savings(&self) -> Duration {
+        self.baseline - self.now
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TripGrouping {
+    Mode,
+    OriginNeighborhood,
+    DestinationNeighborhood,
+    DepartureHour,
+}
+
+impl TripGrouping {
+    pub fn all() -> Vec<TripGrouping> {
+        vec![
+            TripGrouping::Mode,
+            TripGrouping::OriginNeighborhood,
+            TripGrouping::DestinationNeighborhood,
+            TripGrouping::DepartureHour,
+        ]
+    }
+
+    pub fn describe(self) -> &'static str {
+        match self {
+            TripGrouping::Mode => "mode",
+            TripGrouping::OriginNeighborhood => "origin neighborhood",
+            TripGrouping::DestinationNeighborhood => "destination neighborhood",
+            TripGrouping::DepartureHour => "departure hour",
+        }
+    }
+
+    fn key(self, delta: &TripDelta) -> String {
+        match self {
+            TripGrouping::Mode => delta.mode.to_string(),
+            TripGrouping::OriginNeighborhood => delta.from_neighborhood.clone(),
+            TripGrouping::DestinationNeighborhood => delta.to_neighborhood.clone(),
+            TripGrouping::DepartureHour => {
+                let hour = (delta.departure.inner_seconds() / 3600.0).floor() as usize;
+                format!("{:02}:00 - {:02}:00", hour, hour + 1)
+            }
+        }
+    }
+}
+
+pub struct TripDeltaGroup {
+    pub key: String,
+    // Sorted from the biggest winner to the biggest loser
+    pub trips: Vec<TripID>,
+    pub num_faster: usize,
+    pub num_slower: usize,
+    pub total_savings: Duration,
+    pub biggest_winner: Option<(TripID, Duration)>,
+    pub biggest_loser: Option<(TripID, Duration)>,
+}
+
+impl TripDeltaGroup {
+    pub fn mean_savings(&self) -> Duration {
+        self.total_savings / (self.trips.len() as f64)
+    }
+}
+
+// Breaks down how trip times changed relative to a baseline, to see who wins and who loses.
+pub struct TripComparison {
+    // Sorted from the biggest winner to the biggest loser
+    pub deltas: Vec<TripDelta>,
+}
+
+impl TripComparison {
+    pub fn new(now: Time, sim: &Sim, baseline: &Analytics, map: &Map) -> TripComparison {
+        let before: BTreeMap<TripID, Duration> = baseline
+            .finished_trips
+            .iter()
+            .filter_map(|(t, id, mode, dt)| {
+                if *t <= now && mode.is_some() {
+                    Some((*id, *dt))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        // Neighborhoods can overlap; just use the first one alphabetically.
+        let mut bldg_to_neighborhood: HashMap<BuildingID, String> = HashMap::new();
+        let mut neighborhoods: Vec<FullNeighborhoodInfo> = FullNeighborhoodInfo::load_all(map)
+            .into_iter()
+            .filter_map(|(name, n)| {
+                if name == "_everywhere_" {
+                    None
+                } else {
+                    Some(n)
+                }
+            })
+            .collect();
+        neighborhoods.sort_by(|a, b| a.name.cmp(&b.name));
+        for n in neighborhoods {
+            for b in &n.buildings {
+                bldg_to_neighborhood
+                    .entry(*b)
+                    .or_insert_with(|| n.name.clone());
+            }
+        }
+        let bldg_name = |b: BuildingID| {
+            bldg_to_neighborhood
+                .get(&b)
+                .cloned()
+                .unwrap_or_else(|| "no neighborhood".to_string())
+        };
+
+        let mut deltas = Vec::new();
+        for (t, id, mode, dt) in &sim.get_analytics().finished_trips {
+            if *t > now {
+                break;
+            }
+            let mode = match mode {
+                Some(m) => *m,
+                None => continue,
+            };
+            let baseline_dt = match before.get(id) {
+                Some(dt) => *dt,
+                None => continue,
+            };
+            let (start, end) = sim.trip_endpoints(*id);
+            deltas.push(TripDelta {
+                id: *id,
+                mode,
+                departure: *t - *dt,
+                from_neighborhood: match start {
+                    TripStart::Bldg(b) => bldg_name(b),
+                    TripStart::Border(_) => "outside the map".to_string(),
+                },
+                to_neighborhood: match end {
+                    TripEnd::Bldg(b) => bldg_name(b),
+                    TripEnd::Border(_) | TripEnd::ServeBusRoute(_) => "outside the map".to_string(),
+                },
+                baseline: baseline_dt,
+                now: *dt,
+            });
+        }
+        deltas.sort_by_key(|d| d.now - d.baseline);
+
+        TripComparison { deltas }
+    }
+
+    pub fn winners(&self, n: usize) -> Vec<&TripDelta> {
+        self.deltas
+            .iter()
+            .take_while(|d| d.savings() > Duration::ZERO)
+            .take(n)
+            .collect()
+    }
+
+    pub fn losers(&self, n: usize) -> Vec<&TripDelta> {
+        self.deltas
+            .iter()
+            .rev()
+            .take_while(|d| d.savings() < Duration::ZERO)
+            .take(n)
+            .collect()
+    }
+
+    pub fn group_by(&self, grouping: TripGrouping) -> Vec<TripDeltaGroup> {
+        let mut groups: BTreeMap<String, TripDeltaGroup> = BTreeMap::new();
+        for delta in &self.deltas {
+            let key = grouping.key(delta);
+            let group = groups.entry(key.clone()).or_insert_with(|| TripDeltaGroup {
+                key,
+                trips: Vec::new(),
+                num_faster: 0,
+                num_slower: 0,
+                total_savings: Duration::ZERO,
+                biggest_winner: None,
+                biggest_loser: None,
+            });
+            let savings = delta.savings();
+            group.trips.push(delta.id);
+            group.total_savings += savings;
+            // The deltas are sorted, so the first winner and last loser seen are the biggest.
+            if savings > Duration::ZERO {
+                group.num_faster += 1;
+                if group.biggest_winner.is_none() {
+                    group.biggest_winner = Some((delta.id, savings));
+                }
+            } else if savings < Duration::ZERO {
+                group.num_slower += 1;
+                group.biggest_loser = Some((delta.id, savings));
+            }
+        }
+        groups.into_iter().map(|(_, g)| g).collect()
+    }
+}
@@ -0,0 +1,59 @@
+use crate::runner::TestRunner;
+use geom::{Duration, LonLat, Time};
+use map_model::RoadID;
+use sim::{geh, scale_factor, CountComparison, CountLocation, MatchedLocation, ObservedCount};
+use std::collections::BTreeSet;
+
+pub fn run(t: &mut TestRunner) {
+    t.run_fast("geh", |_| {
+        assert_eq!(geh(100, 100, Duration::hours(1)), 0.0);
+        assert_eq!(geh(0, 0, Duration::hours(1)), 0.0);
+        // sqrt(2 * 50^2 / 250)
+        assert_close(geh(150, 100, Duration::hours(1)), 20.0_f64.sqrt());
+        // Half an hour is scaled up to the same hourly flows
+        assert_close(geh(75, 50, Duration::minutes(30)), 20.0_f64.sqrt());
+        // The same ratio over more traffic is a worse match
+        assert!(geh(1500, 1000, Duration::hours(1)) > geh(150, 100, Duration::hours(1)));
+        assert_eq!(geh(10, 20, Duration::ZERO), 0.0);
+    });
+
+    t.run_fast("scale_factor", |_| {
+        assert_eq!(scale_factor(Vec::<CountComparison>::new().iter()), None);
+        assert_eq!(scale_factor([comparison(100, 0)].iter()), None);
+        assert_eq!(scale_factor([comparison(0, 100)].iter()), None);
+        assert_close(
+            scale_factor([comparison(150, 100), comparison(50, 0)].iter()).unwrap(),
+            2.0,
+        );
+        assert_close(scale_factor([comparison(50, 100)].iter()).unwrap(), 0.5);
+        // One noisy count can't blow up or wipe out demand
+        assert_close(scale_factor([comparison(1000, 1)].iter()).unwrap(), 10.0);
+        assert_close(scale_factor([comparison(1, 1000)].iter()).unwrap(), 0.1);
+    });
+}
+
+fn comparison(observed: usize, simulated: usize) -> CountComparison {
+    let start = Time::START_OF_DAY;
+    let end = start + Duration::hours(1);
+    CountComparison {
+        observed: ObservedCount {
+            location: CountLocation::Road(LonLat::new(-122.3, 47.6)),
+            start,
+            end,
+            modes: BTreeSet::new(),
+            count: observed,
+        },
+        matched: MatchedLocation::Road(RoadID(0)),
+        simulated,
+        geh: geh(simulated, observed, end - start),
+    }
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 1e-9,
+        "got {}, expected {}",
+        actual,
+        expected
+    );
+}
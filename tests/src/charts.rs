@@ -0,0 +1,69 @@
+use crate::runner::TestRunner;
+use ezgui::{closest_in_time, csv_contents, plot_csv_rows, Color, Series, PLOT_CSV_HEADER};
+use geom::{Duration, Time};
+
+pub fn run(t: &mut TestRunner) {
+    t.run_fast("closest_in_time_within_zoom", |_| {
+        let at = |secs: f64| Time::START_OF_DAY + Duration::seconds(secs);
+        let pts = vec![(at(0.0), 1), (at(10.0), 2), (at(20.0), 3), (at(30.0), 4)];
+
+        // The nearest point overall is zoomed out of view, so take the nearest visible one.
+        assert_eq!(
+            closest_in_time(&pts, at(4.0), at(10.0), at(30.0)),
+            Some((at(10.0), 2))
+        );
+        assert_eq!(
+            closest_in_time(&pts, at(29.0), at(0.0), at(20.0)),
+            Some((at(20.0), 3))
+        );
+        // Both ends of the range are inclusive.
+        assert_eq!(
+            closest_in_time(&pts, at(10.0), at(10.0), at(20.0)),
+            Some((at(10.0), 2))
+        );
+        assert_eq!(
+            closest_in_time(&pts, at(20.0), at(10.0), at(20.0)),
+            Some((at(20.0), 3))
+        );
+        // Halfway between goes to the earlier point.
+        assert_eq!(
+            closest_in_time(&pts, at(15.0), at(0.0), at(30.0)),
+            Some((at(10.0), 2))
+        );
+        // Zoomed in between points
+        assert_eq!(closest_in_time(&pts, at(15.0), at(11.0), at(19.0)), None);
+    });
+
+    t.run_fast("plot_csv_export", |_| {
+        let at = |secs: f64| Time::START_OF_DAY + Duration::seconds(secs);
+        let series = vec![
+            Series {
+                label: "say \"hi\"".to_string(),
+                color: Color::RED,
+                pts: vec![
+                    (at(0.0), Duration::seconds(1.5)),
+                    (at(10.0), Duration::seconds(2.0)),
+                    (at(20.0), Duration::seconds(3.0)),
+                ],
+            },
+            Series {
+                label: "plain, with a comma".to_string(),
+                color: Color::BLUE,
+                pts: vec![(at(10.5), Duration::seconds(0.25))],
+            },
+        ];
+        let rows = plot_csv_rows(series.iter(), at(10.0), at(20.0));
+        assert_eq!(
+            csv_contents(PLOT_CSV_HEADER, rows),
+            "series,time_seconds,value\n\
+             \"say \"\"hi\"\"\",10,2\n\
+             \"say \"\"hi\"\"\",20,3\n\
+             \"plain, with a comma\",10.5,0.25\n"
+        );
+
+        assert_eq!(
+            csv_contents("duration_seconds", Vec::new()),
+            "duration_seconds\n"
+        );
+    });
+}
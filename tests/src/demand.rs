@@ -0,0 +1,202 @@
+use crate::runner::TestRunner;
+use abstutil::Timer;
+use geom::{Distance, Duration, Time};
+use map_model::{IntersectionID, Map, PathConstraints};
+use popdat::external::{export_scenario, import_scenario, read_csv, write_csv};
+use popdat::od::{round_randomly, weighted_pick};
+use popdat::psrc::{Mode, Purpose};
+use popdat::{make_scenario, Trip, TripEndpt};
+use rand::SeedableRng;
+use rand_xorshift::XorShiftRng;
+use sim::{DepartureDistribution, SimFlags};
+
+pub fn run(t: &mut TestRunner) {
+    t.run_fast("weighted_pick", |_| {
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let weights = vec![0.0, 1.0, 3.0, 0.0];
+        let mut counts = vec![0; weights.len()];
+        for _ in 0..4000 {
+            counts[weighted_pick(&weights, &mut rng)] += 1;
+        }
+        assert_eq!(counts[0], 0);
+        assert_eq!(counts[3], 0);
+        let fraction = (counts[2] as f64) / 4000.0;
+        assert!(
+            fraction > 0.7 && fraction < 0.8,
+            "picked 3/4 weight {}",
+            fraction
+        );
+
+        for bad in &[vec![1.0, -0.5], vec![0.0, 0.0], vec![std::f64::NAN, 1.0]] {
+            assert!(std::panic::catch_unwind(|| {
+                weighted_pick(bad, &mut XorShiftRng::from_seed([42; 16]))
+            })
+            .is_err());
+        }
+    });
+
+    t.run_fast("od_fractional_trips", |_| {
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        assert_eq!(round_randomly(0.0, &mut rng), 0);
+        for _ in 0..100 {
+            assert_eq!(round_randomly(3.0, &mut rng), 3);
+        }
+
+        let mut total = 0;
+        for _ in 0..10000 {
+            let count = round_randomly(2.25, &mut rng);
+            assert!(count == 2 || count == 3);
+            total += count;
+        }
+        let mean = (total as f64) / 10000.0;
+        assert!(
+            (mean - 2.25).abs() < 0.05,
+            "mean of 2.25 rounded is {}",
+            mean
+        );
+    });
+    t.run_fast("departure_distributions", |_| {
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let start = Time::START_OF_DAY + Duration::hours(6);
+        let stop = Time::START_OF_DAY + Duration::hours(10);
+        let samples = |dist: &DepartureDistribution, rng: &mut XorShiftRng| -> Vec<Time> {
+            (0..2000)
+                .map(|_| dist.sample(rng, start, stop, &mut Timer::throwaway()))
+                .collect()
+        };
+        let mean_hours = |times: &Vec<Time>| -> f64 {
+            times.iter().map(|t| t.inner_seconds()).sum::<f64>() / (times.len() as f64) / 3600.0
+        };
+
+        // Peaking at the start, the mean is a third of the way through the window.
+        let times = samples(&DepartureDistribution::Triangular { peak: start }, &mut rng);
+        assert!(times.iter().all(|t| *t >= start && *t <= stop));
+        assert!((mean_hours(&times) - (6.0 + 4.0 / 3.0)).abs() < 0.1);
+        // A peak outside the window gets clamped to it
+        let times = samples(
+            &DepartureDistribution::Triangular {
+                peak: Time::START_OF_DAY + Duration::hours(20),
+            },
+            &mut rng,
+        );
+        assert!(times.iter().all(|t| *t >= start && *t <= stop));
+        assert!((mean_hours(&times) - (6.0 + 8.0 / 3.0)).abs() < 0.1);
+
+        // Cut off at the edges of the window
+        let times = samples(
+            &DepartureDistribution::Normal {
+                mean: Time::START_OF_DAY + Duration::hours(7),
+                std_dev: Duration::hours(2),
+            },
+            &mut rng,
+        );
+        assert!(times.iter().all(|t| *t >= start && *t <= stop));
+        let times = samples(
+            &DepartureDistribution::Normal {
+                mean: Time::START_OF_DAY + Duration::hours(12),
+                std_dev: Duration::ZERO,
+            },
+            &mut rng,
+        );
+        assert!(times.iter().all(|t| *t == stop));
+
+        // A bump from 7 to 9, peaking at 8
+        let profile = DepartureDistribution::Profile {
+            name: "bump".to_string(),
+            points: vec![
+                (Time::START_OF_DAY + Duration::hours(7), 0.0),
+                (Time::START_OF_DAY + Duration::hours(8), 1.0),
+                (Time::START_OF_DAY + Duration::hours(9), 0.0),
+            ],
+        };
+        let times = samples(&profile, &mut rng);
+        let hour = |t: &Time| t.inner_seconds() / 3600.0;
+        assert!(times.iter().all(|t| hour(t) > 7.0 && hour(t) < 9.0));
+        // Three quarters of the weight is within half an hour of the peak
+        let near_peak = times.iter().filter(|t| (hour(t) - 8.0).abs() < 0.5).count();
+        let fraction = (near_peak as f64) / (times.len() as f64);
+        assert!(fraction > 0.7 && fraction < 0.8, "near peak {}", fraction);
+
+        // No weight at all in the window, so it's uniform instead
+        let late_start = Time::START_OF_DAY + Duration::hours(10);
+        let late_stop = Time::START_OF_DAY + Duration::hours(11);
+        assert!(!profile.has_weight(late_start, late_stop));
+        assert!(profile.has_weight(Time::START_OF_DAY + Duration::hours(8), late_stop));
+        let mut timer = Timer::throwaway();
+        let t = profile.sample(&mut rng, late_start, late_stop, &mut timer);
+        assert!(t >= late_start && t <= late_stop);
+    });
+
+    t.run_slow("external_trips_round_trip", |_| {
+        let (map, _, _) =
+            SimFlags::for_test("external_trips_round_trip").load(&mut Timer::throwaway());
+        let b1 = map.all_buildings()[0].id;
+        let b2 = map.all_buildings()[1].id;
+        let (car_in, car_out) = borders(&map, PathConstraints::Car);
+        let (walk_in, walk_out) = borders(&map, PathConstraints::Pedestrian);
+        let border = |i: IntersectionID| TripEndpt::Border(i, map.get_i(i).polygon.center());
+
+        let trips: Vec<Trip> = vec![
+            (
+                Mode::Drive,
+                TripEndpt::Building(b1),
+                TripEndpt::Building(b2),
+            ),
+            (Mode::Drive, border(car_in), TripEndpt::Building(b2)),
+            (Mode::Drive, TripEndpt::Building(b1), border(car_out)),
+            (Mode::Drive, border(car_in), border(car_out)),
+            (Mode::Walk, TripEndpt::Building(b1), border(walk_out)),
+            (Mode::Walk, border(walk_in), border(walk_out)),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(idx, (mode, from, to))| Trip {
+            from,
+            to,
+            depart_at: Time::START_OF_DAY + Duration::seconds(10.0 * (idx as f64)),
+            purpose: (Purpose::Home, Purpose::Home),
+            mode,
+            trip_time: Duration::ZERO,
+            trip_dist: Distance::ZERO,
+        })
+        .collect();
+        let num_trips = trips.len();
+        let original = make_scenario("original", trips, &map, &mut Timer::throwaway());
+        assert_eq!(original.individ_trips.len(), num_trips);
+
+        let exported = export_scenario(&original, &map);
+        assert_eq!(exported.get_warnings().len(), 0);
+        let path = std::env::temp_dir().join("external_trips_round_trip.csv");
+        let path = path.to_str().unwrap();
+        write_csv(path, &exported.get(&mut Timer::throwaway())).unwrap();
+        let csv = read_csv(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        let imported = import_scenario("imported", csv, &map, &mut Timer::throwaway());
+        assert_eq!(imported.get_warnings().len(), 0);
+        let imported = imported.get(&mut Timer::throwaway());
+        // Every endpoint should come back as the same building or border
+        assert_eq!(imported.individ_trips.len(), num_trips);
+        assert_eq!(
+            abstutil::to_json(&original.individ_trips),
+            abstutil::to_json(&imported.individ_trips)
+        );
+    });
+}
+
+// Two different borders where trips of some type can enter and leave the map
+fn borders(map: &Map, constraints: PathConstraints) -> (IntersectionID, IntersectionID) {
+    let incoming = map
+        .all_incoming_borders()
+        .into_iter()
+        .find(|i| !i.get_outgoing_lanes(map, constraints).is_empty())
+        .unwrap()
+        .id;
+    let outgoing = map
+        .all_outgoing_borders()
+        .into_iter()
+        .find(|i| i.id != incoming && !i.get_incoming_lanes(map, constraints).is_empty())
+        .unwrap()
+        .id;
+    (incoming, outgoing)
+}
@@ -0,0 +1,81 @@
+use crate::runner::TestRunner;
+use crate::synthetic::SyntheticMap;
+use map_model::{IntersectionID, IntersectionType, LaneID, Map, RoadID};
+use sim::{Binning, Metric};
+
+pub fn run(t: &mut TestRunner) {
+    t.run_fast("load_metric_csv", |_| {
+        let mut raw = SyntheticMap::new("load_metric_csv");
+        let i1 = raw.intersection(10.0, 250.0, IntersectionType::Border);
+        let i2 = raw.intersection(490.0, 250.0, IntersectionType::Border);
+        raw.road(i1, i2, "d/d", Vec::new());
+        let map = raw.build();
+
+        // Only a header on the first line is skipped, and whitespace and blank lines don't matter.
+        let metric = load(
+            "kind,id,value\nroad,0,1.5\n\n intersection , 1 , 3\nlane,0,2\n",
+            &map,
+        )
+        .unwrap();
+        assert_eq!(metric.name, "heatmap_values");
+        assert_eq!(metric.roads.get(&RoadID(0)), Some(&1.5));
+        assert_eq!(metric.intersections.get(&IntersectionID(1)), Some(&3.0));
+        assert_eq!(metric.lanes.get(&LaneID(0)), Some(&2.0));
+        assert!(metric.buildings.is_empty());
+        assert_eq!(metric.sorted_values(), vec![1.5, 2.0, 3.0]);
+
+        let err = |contents: &str| load(contents, &map).err().unwrap();
+        assert_eq!(err("road,0,1\nkind,id,value"), "Line 2 has a bad id: id");
+        assert_eq!(err("road,0"), "Line 1 isn't kind,id,value: road,0");
+        assert_eq!(err("road,-1,1"), "Line 1 has a bad id: -1");
+        assert_eq!(err("road,0,fast"), "Line 1 has a bad value: fast");
+        assert_eq!(err("road,0,NaN"), "Line 1 has a bad value: NaN");
+        assert_eq!(err("bridge,0,1"), "Line 1 has an unknown kind: bridge");
+        assert_eq!(
+            err("road,0,1\nroad,99,1"),
+            "Line 2: road 99 isn't in load_metric_csv"
+        );
+        assert_eq!(
+            err("building,0,1"),
+            "Line 1: building 0 isn't in load_metric_csv"
+        );
+
+        let empty = load("", &map).unwrap();
+        assert!(empty.sorted_values().is_empty());
+        let header_only = load("kind,id,value\n", &map).unwrap();
+        assert!(header_only.sorted_values().is_empty());
+        assert!(Metric::load_csv("/does/not/exist.csv", &map).is_err());
+    });
+
+    t.run_fast("binning_thresholds", |_| {
+        let even: Vec<f64> = (1..=10).map(|x| x as f64).collect();
+        assert!(Binning::Continuous.thresholds(&even).is_empty());
+        assert_eq!(Binning::EqualIntervals(3).thresholds(&even), vec![4.0, 7.0]);
+        assert_eq!(
+            Binning::Quantiles(5).thresholds(&even),
+            vec![3.0, 5.0, 7.0, 9.0]
+        );
+
+        // One outlier stretches equal intervals, but not quantiles.
+        let skewed = vec![1.0, 1.0, 1.0, 1.0, 100.0];
+        assert_eq!(Binning::EqualIntervals(2).thresholds(&skewed), vec![50.5]);
+        assert_eq!(Binning::Quantiles(2).thresholds(&skewed), vec![1.0]);
+
+        for binning in vec![
+            Binning::Continuous,
+            Binning::EqualIntervals(5),
+            Binning::Quantiles(5),
+        ] {
+            assert!(binning.thresholds(&[]).is_empty());
+        }
+    });
+}
+
+// The metric is named after the file.
+fn load(contents: &str, map: &Map) -> Result<Metric, String> {
+    let path = std::env::temp_dir().join("heatmap_values.csv");
+    std::fs::write(&path, contents).unwrap();
+    let result = Metric::load_csv(path.to_str().unwrap(), map);
+    std::fs::remove_file(&path).unwrap();
+    result
+}
@@ -0,0 +1,386 @@
+use crate::runner::TestRunner;
+use crate::synthetic::SyntheticMap;
+use abstutil::Timer;
+use geom::{Duration, Time};
+use map_model::{
+    EditCmd, IntersectionID, IntersectionType, LaneID, LaneType, Map, MapEdits, PathStep, Position,
+    RoadID, TurnPriority, TurnType,
+};
+use rand::SeedableRng;
+use rand_xorshift::XorShiftRng;
+use sim::{ConflictKind, DrivingGoal, Scenario, Sim, SimOptions, TripSpec};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+pub fn run(t: &mut TestRunner) {
+    t.run_fast("roundabout_control", |_| {
+        let map = roundabout_map("roundabout_control");
+        let ring: Vec<IntersectionID> = map
+            .all_intersections()
+            .iter()
+            .filter(|i| i.is_roundabout())
+            .map(|i| i.id)
+            .collect();
+        assert_eq!(ring.len(), 4);
+        let all: BTreeSet<IntersectionID> = ring.iter().cloned().collect();
+
+        for i in &ring {
+            let control = map.get_roundabout(*i);
+            assert_eq!(control.members, all);
+            assert_eq!(control.ring_roads.len(), 4);
+            for t in &map.get_i(*i).turns {
+                let turn = map.get_t(*t);
+                if turn.between_sidewalks() {
+                    continue;
+                }
+                let expected = if map.get_r(map.get_l(t.src).parent).is_roundabout() {
+                    TurnPriority::Protected
+                } else {
+                    TurnPriority::Yield
+                };
+                assert_eq!(control.get_priority(*t, &map), expected);
+            }
+        }
+    });
+
+    t.run_fast("roundabout_close_member", |_| {
+        let mut map = roundabout_map("roundabout_close_member");
+        let ring: Vec<IntersectionID> = map
+            .all_intersections()
+            .iter()
+            .filter(|i| i.is_roundabout())
+            .map(|i| i.id)
+            .collect();
+
+        let mut edits = MapEdits::new(map.get_name().to_string());
+        edits.commands.push(EditCmd::CloseIntersection {
+            id: ring[0],
+            orig_it: IntersectionType::Roundabout,
+        });
+        map.apply_edits(edits, &mut Timer::throwaway());
+        assert!(map.maybe_get_roundabout(ring[0]).is_none());
+        let rest: BTreeSet<IntersectionID> = ring[1..].iter().cloned().collect();
+        for i in &rest {
+            assert_eq!(map.get_roundabout(*i).members, rest);
+        }
+
+        map.apply_edits(
+            MapEdits::new(map.get_name().to_string()),
+            &mut Timer::throwaway(),
+        );
+        let all: BTreeSet<IntersectionID> = ring.iter().cloned().collect();
+        for i in &ring {
+            assert_eq!(map.get_roundabout(*i).members, all);
+        }
+    });
+
+    t.run_slow("roundabout_no_gridlock", |h| {
+        let map = roundabout_map("roundabout_no_gridlock");
+        let mut sim = Sim::new(
+            &map,
+            SimOptions::new("roundabout_no_gridlock"),
+            &mut Timer::throwaway(),
+        );
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+
+        // Everybody goes most of the way around the ring, so it fills up and every entry has to
+        // yield to somebody circulating.
+        let borders: Vec<IntersectionID> = map
+            .all_intersections()
+            .iter()
+            .filter(|i| i.is_border())
+            .map(|i| i.id)
+            .collect();
+        for (idx, from) in borders.iter().enumerate() {
+            let to = borders[(idx + borders.len() - 1) % borders.len()];
+            let start = driving_lane(&map, map.get_i(*from).outgoing_lanes.clone());
+            let goal =
+                DrivingGoal::Border(to, driving_lane(&map, map.get_i(to).incoming_lanes.clone()));
+            for n in 0..30 {
+                let vehicle = Scenario::rand_car(&mut rng);
+                sim.schedule_trip(
+                    Time::START_OF_DAY + Duration::seconds(2.0 * (n as f64)),
+                    TripSpec::CarAppearing {
+                        start_pos: Position::new(start, vehicle.length),
+                        vehicle_spec: vehicle,
+                        goal: goal.clone(),
+                        ped_speed: Scenario::rand_ped_speed(&mut rng),
+                    },
+                    &map,
+                );
+            }
+        }
+        sim.spawn_all_trips(&map, &mut Timer::throwaway(), true);
+        h.setup_done(&mut sim);
+
+        sim.just_run_until_done(&map, Some(Duration::minutes(30)));
+    });
+
+    t.run_slow("roundabout_gap_acceptance", |h| {
+        let map = roundabout_map("roundabout_gap_acceptance");
+        let mut sim = Sim::new(
+            &map,
+            SimOptions::new("roundabout_gap_acceptance"),
+            &mut Timer::throwaway(),
+        );
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+
+        // Steady traffic around the ring, and a queue at one entry that can only get in through
+        // gaps.
+        let borders: Vec<IntersectionID> = map
+            .all_intersections()
+            .iter()
+            .filter(|i| i.is_border())
+            .map(|i| i.id)
+            .collect();
+        for (idx, from) in borders.iter().enumerate() {
+            let to = borders[(idx + borders.len() - 1) % borders.len()];
+            let start = driving_lane(&map, map.get_i(*from).outgoing_lanes.clone());
+            let goal =
+                DrivingGoal::Border(to, driving_lane(&map, map.get_i(to).incoming_lanes.clone()));
+            let spacing = if idx == 0 { 1.0 } else { 4.0 };
+            for n in 0..20 {
+                let vehicle = Scenario::rand_car(&mut rng);
+                sim.schedule_trip(
+                    Time::START_OF_DAY + Duration::seconds(spacing * (n as f64)),
+                    TripSpec::CarAppearing {
+                        start_pos: Position::new(start, vehicle.length),
+                        vehicle_spec: vehicle,
+                        goal: goal.clone(),
+                        ped_speed: Scenario::rand_ped_speed(&mut rng),
+                    },
+                    &map,
+                );
+            }
+        }
+        sim.spawn_all_trips(&map, &mut Timer::throwaway(), true);
+        h.setup_done(&mut sim);
+
+        // Watch who starts turns onto the ring. Sampling every step can be late by up to one step
+        // for both vehicles.
+        let dt = Duration::seconds(0.1);
+        let ring: Vec<IntersectionID> = map
+            .all_intersections()
+            .iter()
+            .filter(|i| i.is_roundabout())
+            .map(|i| i.id)
+            .collect();
+        let mut seen = HashSet::new();
+        let mut last_entry: BTreeMap<LaneID, Time> = BTreeMap::new();
+        let mut waited = 0;
+        while !sim.is_done() {
+            assert!(sim.time() < Time::START_OF_DAY + Duration::minutes(30));
+            sim.step(&map, dt);
+            for i in &ring {
+                for agent in sim.get_accepted_agents(*i) {
+                    if !seen.insert(agent) {
+                        continue;
+                    }
+                    let turn = match sim.get_path(agent).map(|p| p.current_step()) {
+                        Some(PathStep::Turn(t)) => t,
+                        _ => continue,
+                    };
+                    if !map.get_r(map.get_l(turn.dst).parent).is_roundabout() {
+                        continue;
+                    }
+                    let entering = !map.get_r(map.get_l(turn.src).parent).is_roundabout();
+                    if let Some(prev) = last_entry.insert(turn.dst, sim.time()) {
+                        if entering {
+                            let gap = sim.time() - prev;
+                            assert!(
+                                gap >= Duration::seconds(2.0) - dt,
+                                "{} entered the ring {} after somebody else",
+                                agent,
+                                gap
+                            );
+                            // Released right when the gap opened up
+                            if gap < Duration::seconds(2.5) {
+                                waited += 1;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        assert!(waited > 0, "nobody entering had to wait for a gap");
+    });
+
+    t.run_fast("near_conflict_kinds", |_| {
+        let mut raw = SyntheticMap::new("near_conflict_kinds");
+        let center = raw.intersection(250.0, 250.0, IntersectionType::StopSign);
+        for (x, y) in vec![(250.0, 10.0), (490.0, 250.0), (250.0, 490.0), (10.0, 250.0)] {
+            let border = raw.intersection(x, y, IntersectionType::Border);
+            raw.road(border, center, "ds/ds", Vec::new());
+        }
+        let map = raw.build();
+        let i = map
+            .all_intersections()
+            .iter()
+            .find(|i| !i.is_border())
+            .unwrap();
+        let find = |turn_type: TurnType| {
+            i.turns
+                .iter()
+                .map(|t| map.get_t(*t))
+                .find(|t| t.turn_type == turn_type)
+                .unwrap()
+        };
+        let crosswalk = find(TurnType::Crosswalk);
+        let corner = find(TurnType::SharedSidewalkCorner);
+        let straight = find(TurnType::Straight);
+        let left = find(TurnType::Left);
+        let (yld, prot) = (TurnPriority::Yield, TurnPriority::Protected);
+
+        for ped in vec![crosswalk, corner] {
+            assert_eq!(
+                ConflictKind::classify(ped, prot, straight, prot),
+                ConflictKind::PedestrianAndVehicle
+            );
+            assert_eq!(
+                ConflictKind::classify(left, yld, ped, prot),
+                ConflictKind::PedestrianAndVehicle
+            );
+        }
+        assert_eq!(
+            ConflictKind::classify(left, yld, straight, prot),
+            ConflictKind::YieldAfterProtected
+        );
+        assert_eq!(
+            ConflictKind::classify(left, prot, straight, prot),
+            ConflictKind::VehicleAndVehicle
+        );
+        assert_eq!(
+            ConflictKind::classify(straight, prot, left, yld),
+            ConflictKind::VehicleAndVehicle
+        );
+    });
+
+    t.run_fast("merge_control", |_| {
+        let (map, ramp) = merge_map("merge_control");
+        let merge = map
+            .all_intersections()
+            .iter()
+            .find(|i| !i.is_border())
+            .unwrap();
+        assert!(merge.is_merge());
+        let control = map.get_merge(merge.id);
+        let expected: BTreeSet<RoadID> = vec![ramp].into_iter().collect();
+        assert_eq!(control.yielding_roads, expected);
+
+        for t in &merge.turns {
+            let turn = map.get_t(*t);
+            if map.get_l(t.src).parent == ramp {
+                assert_eq!(control.get_priority(*t, &map), TurnPriority::Yield);
+                // The mainline keeps both lanes, so the ramp merges into the right one.
+                assert_eq!(turn.turn_type, TurnType::LaneChangeLeft);
+            } else {
+                assert_eq!(control.get_priority(*t, &map), TurnPriority::Protected);
+                assert_eq!(turn.turn_type, TurnType::Straight);
+            }
+        }
+    });
+
+    t.run_slow("merge_gap_acceptance", |h| {
+        let (map, ramp) = merge_map("merge_gap_acceptance");
+        let mut sim = Sim::new(
+            &map,
+            SimOptions::new("merge_gap_acceptance"),
+            &mut Timer::throwaway(),
+        );
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+
+        // Steady traffic on the mainline, and a queue on the ramp that can only get in through
+        // gaps. Nobody should be stuck forever.
+        let end = map
+            .all_intersections()
+            .iter()
+            .find(|i| i.is_border() && !i.incoming_lanes.is_empty())
+            .unwrap()
+            .id;
+        let goal = DrivingGoal::Border(
+            end,
+            driving_lane(&map, map.get_i(end).incoming_lanes.clone()),
+        );
+        for i in map.all_intersections() {
+            if !i.is_border() || i.outgoing_lanes.is_empty() {
+                continue;
+            }
+            let from_ramp = map.get_l(i.outgoing_lanes[0]).parent == ramp;
+            for l in &i.outgoing_lanes {
+                for n in 0..20 {
+                    let vehicle = Scenario::rand_car(&mut rng);
+                    let spacing = if from_ramp { 1.0 } else { 3.0 };
+                    sim.schedule_trip(
+                        Time::START_OF_DAY + Duration::seconds(spacing * (n as f64)),
+                        TripSpec::CarAppearing {
+                            start_pos: Position::new(*l, vehicle.length),
+                            vehicle_spec: vehicle,
+                            goal: goal.clone(),
+                            ped_speed: Scenario::rand_ped_speed(&mut rng),
+                        },
+                        &map,
+                    );
+                }
+            }
+        }
+        sim.spawn_all_trips(&map, &mut Timer::throwaway(), true);
+        h.setup_done(&mut sim);
+
+        sim.just_run_until_done(&map, Some(Duration::minutes(30)));
+    });
+}
+
+fn driving_lane(map: &Map, lanes: Vec<map_model::LaneID>) -> map_model::LaneID {
+    lanes
+        .into_iter()
+        .find(|l| map.get_l(*l).lane_type == LaneType::Driving)
+        .unwrap()
+}
+
+// A ring of 4 one-way roads, with a two-way road leading out from each corner to the edge of the
+// map.
+fn roundabout_map(name: &str) -> Map {
+    let mut raw = SyntheticMap::new(name);
+    let ring = [
+        raw.intersection(250.0, 150.0, IntersectionType::Roundabout),
+        raw.intersection(350.0, 250.0, IntersectionType::Roundabout),
+        raw.intersection(250.0, 350.0, IntersectionType::Roundabout),
+        raw.intersection(150.0, 250.0, IntersectionType::Roundabout),
+    ];
+    let borders = [
+        raw.intersection(250.0, 10.0, IntersectionType::Border),
+        raw.intersection(490.0, 250.0, IntersectionType::Border),
+        raw.intersection(250.0, 490.0, IntersectionType::Border),
+        raw.intersection(10.0, 250.0, IntersectionType::Border),
+    ];
+    for (idx, border) in borders.iter().enumerate() {
+        raw.road(
+            ring[idx],
+            ring[(idx + 1) % ring.len()],
+            "d/",
+            vec![("junction", "roundabout")],
+        );
+        raw.road(*border, ring[idx], "d/d", Vec::new());
+    }
+    raw.build()
+}
+
+// A two-lane motorway with a one-lane on-ramp joining it partway. Returns the ramp.
+fn merge_map(name: &str) -> (Map, RoadID) {
+    let mut raw = SyntheticMap::new(name);
+    let start = raw.intersection(10.0, 250.0, IntersectionType::Border);
+    let merge = raw.intersection(250.0, 250.0, IntersectionType::StopSign);
+    let end = raw.intersection(490.0, 250.0, IntersectionType::Border);
+    let ramp_start = raw.intersection(100.0, 400.0, IntersectionType::Border);
+    raw.road(start, merge, "dd/", vec![("highway", "motorway")]);
+    raw.road(merge, end, "dd/", vec![("highway", "motorway")]);
+    let ramp = raw.road(ramp_start, merge, "d/", vec![("highway", "motorway_link")]);
+
+    let map = raw.build();
+    let ramp = map
+        .all_roads()
+        .iter()
+        .find(|r| r.orig_id == ramp)
+        .unwrap()
+        .id;
+    (map, ramp)
+}
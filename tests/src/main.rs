@@ -1,9 +1,19 @@
+mod calibration;
+mod charts;
+mod demand;
 mod geom;
+mod heatmap;
+mod intersections;
 mod map_conversion;
 mod parking;
+mod render;
+mod replay;
+mod replication;
 mod runner;
 mod sim_completion;
 mod sim_determinism;
+mod synthetic;
+mod tiles;
 mod transit;
 mod trips;
 
@@ -29,11 +39,20 @@ fn main() {
 
     let mut t = runner::TestRunner::new(flags);
 
+    calibration::run(t.suite("calibration"));
+    charts::run(t.suite("charts"));
+    demand::run(t.suite("demand"));
     geom::run(t.suite("geom"));
+    heatmap::run(t.suite("heatmap"));
+    intersections::run(t.suite("intersections"));
     map_conversion::run(t.suite("map_conversion"));
     parking::run(t.suite("parking"));
+    render::run(t.suite("render"));
+    replay::run(t.suite("replay"));
+    replication::run(t.suite("replication"));
     sim_completion::run(t.suite("sim_completion"));
     sim_determinism::run(t.suite("sim_determinism"));
+    tiles::run(t.suite("tiles"));
     transit::run(t.suite("transit"));
     trips::run(t.suite("trips"));
 
@@ -1,6 +1,142 @@
 use crate::runner::TestRunner;
+use crate::synthetic::SyntheticMap;
+use map_model::{IntersectionType, LaneID, Map, TurnType};
+use std::collections::BTreeSet;
 
 pub fn run(t: &mut TestRunner) {
+    t.run_fast("turn_lanes", |_| {
+        let mut raw = SyntheticMap::new("turn_lanes");
+        let west = raw.intersection(10.0, 250.0, IntersectionType::Border);
+        let middle = raw.intersection(250.0, 250.0, IntersectionType::StopSign);
+        let east = raw.intersection(490.0, 250.0, IntersectionType::Border);
+        let north = raw.intersection(250.0, 10.0, IntersectionType::Border);
+        let south = raw.intersection(250.0, 490.0, IntersectionType::Border);
+        let tagged = raw.road(
+            west,
+            middle,
+            "dddd/",
+            vec![("turn:lanes", "through;right|none||merge_to_left")],
+        );
+        // Doesn't match the number of lanes, so ignored
+        let mismatched = raw.road(middle, east, "dddd/", vec![("turn:lanes", "left|through")]);
+        let unknown = raw.road(middle, north, "d/", vec![("turn:lanes", "sideways")]);
+        raw.road(middle, south, "d/", Vec::new());
+        let map = raw.build();
+
+        let lanes = |id| -> Vec<LaneID> { find_road(&map, id).all_lanes() };
+        let allowed = |l: LaneID| map.get_l(l).allowed_turns.clone();
+        let types = |list: Vec<TurnType>| -> Option<BTreeSet<TurnType>> {
+            Some(list.into_iter().collect())
+        };
+
+        let tagged = lanes(tagged);
+        assert_eq!(
+            allowed(tagged[0]),
+            types(vec![
+                TurnType::Straight,
+                TurnType::LaneChangeLeft,
+                TurnType::LaneChangeRight,
+                TurnType::Right
+            ])
+        );
+        assert_eq!(allowed(tagged[1]), None);
+        // An empty segment means the same as none
+        assert_eq!(allowed(tagged[2]), None);
+        assert_eq!(
+            allowed(tagged[3]),
+            types(vec![
+                TurnType::Straight,
+                TurnType::LaneChangeLeft,
+                TurnType::Left
+            ])
+        );
+        for l in lanes(mismatched).into_iter().chain(lanes(unknown)) {
+            assert_eq!(allowed(l), None);
+        }
+    });
+
+    t.run_fast("bridge_over_street_not_connected", |_| {
+        // A street running east-west and a bridge running north-south, sloppily sharing a node
+        // where they cross. The extra nodes just pad the boundary.
+        let osm = r#"<?xml version="1.0" encoding="UTF-8"?>
+<osm version="0.6">
+  <node id="1" lat="47.600" lon="-122.300"/>
+  <node id="2" lat="47.600" lon="-122.290"/>
+  <node id="3" lat="47.600" lon="-122.280"/>
+  <node id="4" lat="47.610" lon="-122.290"/>
+  <node id="5" lat="47.590" lon="-122.290"/>
+  <node id="6" lat="47.580" lon="-122.310"/>
+  <node id="7" lat="47.620" lon="-122.270"/>
+  <way id="100">
+    <nd ref="1"/><nd ref="2"/><nd ref="3"/>
+    <tag k="highway" v="residential"/>
+  </way>
+  <way id="101">
+    <nd ref="4"/><nd ref="2"/><nd ref="5"/>
+    <tag k="highway" v="residential"/>
+    <tag k="bridge" v="yes"/>
+  </way>
+</osm>"#;
+        let path = std::env::temp_dir().join("bridge_over_street_not_connected.osm");
+        std::fs::write(&path, osm).unwrap();
+        let flags = convert_osm::Flags {
+            osm: path.to_str().unwrap().to_string(),
+            parking_shapes: None,
+            offstreet_parking: None,
+            sidewalks: None,
+            gtfs: None,
+            neighborhoods: None,
+            clip: None,
+            output: String::new(),
+        };
+        let raw = convert_osm::convert(&flags, &mut abstutil::Timer::throwaway());
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(raw.intersections.keys().all(|i| i.osm_node_id != 2));
+        let ways: Vec<(i64, isize)> = raw
+            .roads
+            .iter()
+            .map(|(id, r)| (id.osm_way_id, r.get_zorder()))
+            .collect();
+        assert_eq!(ways, vec![(100, 0), (101, 1)]);
+    });
+
+    t.run_fast("bridge_turns_dont_conflict", |_| {
+        // Both roads really do meet here, but the bridge stays on its own layer straight through.
+        let mut raw = SyntheticMap::new("bridge_turns_dont_conflict");
+        let middle = raw.intersection(250.0, 250.0, IntersectionType::StopSign);
+        let west = raw.intersection(10.0, 250.0, IntersectionType::Border);
+        let east = raw.intersection(490.0, 250.0, IntersectionType::Border);
+        let north = raw.intersection(250.0, 10.0, IntersectionType::Border);
+        let south = raw.intersection(250.0, 490.0, IntersectionType::Border);
+        let west = raw.road(west, middle, "d/d", Vec::new());
+        let east = raw.road(middle, east, "d/d", Vec::new());
+        let north = raw.road(north, middle, "d/d", vec![("bridge", "yes")]);
+        let south = raw.road(middle, south, "d/d", vec![("bridge", "yes")]);
+        let map = raw.build();
+
+        let turn = |from, to| {
+            let (from, to) = (find_road(&map, from), find_road(&map, to));
+            map.get_turns_from_lane(from.children_forwards[0].0)
+                .into_iter()
+                .find(|t| map.get_l(t.id.dst).parent == to.id)
+                .unwrap()
+        };
+        let street = turn(west, east);
+        let bridge = turn(north, south);
+        let ramp = turn(north, east);
+        assert_eq!(street.zorder, (0, 0));
+        assert_eq!(bridge.zorder, (1, 1));
+        assert_eq!(ramp.zorder, (1, 0));
+
+        // The straight movements cross in 2D, but not in reality.
+        assert!(street.geom.intersection(&bridge.geom).is_some());
+        assert!(!street.conflicts_with(bridge));
+        assert!(!bridge.conflicts_with(street));
+        // Leaving the bridge does touch the street's level.
+        assert!(ramp.conflicts_with(street));
+    });
+
     t.run_slow("convert_osm_twice", |_| {
         let flags = convert_osm::Flags {
             osm: "../data/input/osm/montlake.osm".to_string(),
@@ -60,3 +196,7 @@ pub fn run(t: &mut TestRunner) {
         );
     });
 }
+
+fn find_road(map: &Map, id: map_model::raw::OriginalRoad) -> &map_model::Road {
+    map.all_roads().iter().find(|r| r.orig_id == id).unwrap()
+}
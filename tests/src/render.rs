@@ -0,0 +1,87 @@
+use crate::runner::TestRunner;
+use geom::{Bounds, Polygon, Pt2D, Triangle};
+use raster::Rgba;
+
+const RED: Rgba = Rgba::new(1.0, 0.0, 0.0, 1.0);
+
+pub fn run(t: &mut TestRunner) {
+    t.run_fast("fill_triangle", |_| {
+        // One pixel per unit, so pixel (x, y) covers [x, x+1) x [y, y+1)
+        let bounds = square(10.0);
+        for tri in &[
+            triangle((0.0, 0.0), (10.0, 0.0), (0.0, 10.0)),
+            // The other winding should fill the same pixels
+            triangle((0.0, 0.0), (0.0, 10.0), (10.0, 0.0)),
+        ] {
+            let img = raster::batch_to_png(vec![(RED, tri.clone())], &bounds, Rgba::WHITE, 10);
+            assert_eq!((img.width(), img.height()), (10, 10));
+            // Filled when the pixel's center is inside: x + 0.5 + y + 0.5 <= 10
+            for (x, y) in &[(0, 0), (8, 0), (0, 8), (4, 4), (3, 5)] {
+                assert_eq!(img.get_pixel(*x, *y).0, [255, 0, 0, 255], "({}, {})", x, y);
+            }
+            for (x, y) in &[(5, 5), (9, 9), (9, 1), (4, 6)] {
+                assert_eq!(
+                    img.get_pixel(*x, *y).0,
+                    [255, 255, 255, 255],
+                    "({}, {})",
+                    x,
+                    y
+                );
+            }
+        }
+
+        // Translucent colors blend with what's underneath
+        let img = raster::batch_to_png(
+            vec![(
+                RED.alpha(0.5),
+                triangle((0.0, 0.0), (10.0, 0.0), (0.0, 10.0)),
+            )],
+            &bounds,
+            Rgba::WHITE,
+            10,
+        );
+        assert_eq!(img.get_pixel(0, 0).0, [255, 128, 128, 255]);
+
+        // Scaling up the output scales the triangle too
+        let img = raster::batch_to_png(
+            vec![(RED, triangle((0.0, 0.0), (10.0, 0.0), (0.0, 10.0)))],
+            &bounds,
+            Rgba::WHITE,
+            20,
+        );
+        assert_eq!((img.width(), img.height()), (20, 20));
+        assert_eq!(img.get_pixel(9, 9).0, [255, 0, 0, 255]);
+        assert_eq!(img.get_pixel(10, 10).0, [255, 255, 255, 255]);
+    });
+
+    t.run_fast("batch_to_svg", |_| {
+        let svg = raster::batch_to_svg(
+            vec![(RED, triangle((0.0, 0.0), (10.0, 0.0), (0.0, 10.0)))],
+            &square(10.0),
+            Rgba::WHITE,
+        );
+        assert_eq!(
+            svg,
+            [
+                r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 10 10" width="10" height="10" shape-rendering="crispEdges">"#,
+                r##"<rect x="0" y="0" width="10" height="10" fill="#FFFFFF" fill-opacity="1"/>"##,
+                r##"<path d="M0.00 0.00L10.00 0.00L0.00 10.00Z" fill="#FF0000" fill-opacity="1"/>"##,
+                "</svg>",
+                "",
+            ]
+            .join("\n")
+        );
+    });
+}
+
+fn square(size: f64) -> Bounds {
+    Bounds::from(&vec![Pt2D::new(0.0, 0.0), Pt2D::new(size, size)])
+}
+
+fn triangle(pt1: (f64, f64), pt2: (f64, f64), pt3: (f64, f64)) -> Polygon {
+    Polygon::from_triangle(&Triangle {
+        pt1: Pt2D::new(pt1.0, pt1.1),
+        pt2: Pt2D::new(pt2.0, pt2.1),
+        pt3: Pt2D::new(pt3.0, pt3.1),
+    })
+}
@@ -0,0 +1,55 @@
+use crate::runner::TestRunner;
+use ezgui::{Event, Key, Recording, ScreenPt};
+use std::time::Duration;
+
+pub fn run(t: &mut TestRunner) {
+    t.run_fast("recording_round_trip", |_| {
+        let frames = vec![
+            (Duration::from_millis(0), vec![Event::NoOp]),
+            (
+                Duration::from_millis(16),
+                vec![
+                    Event::MouseMovedTo(ScreenPt::new(10.5, 20.25)),
+                    Event::MouseMovedTo(ScreenPt::new(11.0, 21.0)),
+                    Event::LeftMouseButtonDown,
+                ],
+            ),
+            (
+                Duration::from_millis(33),
+                vec![
+                    Event::LeftMouseButtonUp,
+                    Event::KeyPress(Key::Escape),
+                    Event::KeyRelease(Key::Escape),
+                    Event::MouseWheelScroll(0.0, -1.5),
+                ],
+            ),
+            (Duration::from_millis(50), vec![Event::Update]),
+        ];
+        let path = std::env::temp_dir()
+            .join("recording_round_trip.json")
+            .to_str()
+            .unwrap()
+            .to_string();
+        abstutil::write_json(
+            path.clone(),
+            &Recording {
+                window_width: 1024.0,
+                window_height: 768.0,
+                hidpi_factor: 2.0,
+                frames: frames.clone(),
+            },
+        );
+
+        let loaded = Recording::load(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            (
+                loaded.window_width,
+                loaded.window_height,
+                loaded.hidpi_factor
+            ),
+            (1024.0, 768.0, 2.0)
+        );
+        assert_eq!(loaded.frames, frames);
+    });
+}
@@ -0,0 +1,28 @@
+use crate::runner::TestRunner;
+use sim::ConfidenceInterval;
+
+pub fn run(t: &mut TestRunner) {
+    t.run_fast("confidence_interval_no_runs", |_| {
+        let ci = ConfidenceInterval::new(&Vec::new());
+        assert_eq!(ci.runs, 0);
+        assert!(!ci.mean.is_nan());
+        assert_eq!(ci.describe_count(), "no runs");
+        assert_eq!(ci.describe_duration(), "no runs");
+    });
+
+    t.run_fast("confidence_interval_one_run", |_| {
+        let ci = ConfidenceInterval::new(&vec![3.0]);
+        assert_eq!(ci.runs, 1);
+        assert_eq!(ci.mean, 3.0);
+        assert_eq!(ci.describe_count(), "3.0 (1 run)");
+        assert!(ci.describe_duration().ends_with(" (1 run)"));
+    });
+
+    t.run_fast("confidence_interval_many_runs", |_| {
+        // With 1 degree of freedom, the t value is 12.706 and the standard error is 1.
+        let ci = ConfidenceInterval::new(&vec![1.0, 3.0]);
+        assert_eq!(ci.runs, 2);
+        assert_eq!(ci.describe_count(), "2.0 ± 12.7 (2 runs)");
+        assert!(ci.describe_duration().ends_with(" (2 runs)"));
+    });
+}
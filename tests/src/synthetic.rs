@@ -0,0 +1,81 @@
+use abstutil::Timer;
+use geom::{Polygon, Pt2D};
+use map_model::raw::{OriginalIntersection, OriginalRoad, RawIntersection, RawMap, RawRoad};
+use map_model::{IntersectionType, Map};
+
+// Just enough of a RawMap to exercise intersection policies without any input data.
+pub struct SyntheticMap {
+    raw: RawMap,
+    next_id: i64,
+}
+
+impl SyntheticMap {
+    pub fn new(name: &str) -> SyntheticMap {
+        let mut raw = RawMap::blank(name.to_string());
+        raw.boundary_polygon = Polygon::rectangle(500.0, 500.0);
+        SyntheticMap { raw, next_id: -1 }
+    }
+
+    fn id(&mut self) -> i64 {
+        self.next_id -= 1;
+        self.next_id
+    }
+
+    pub fn intersection(&mut self, x: f64, y: f64, it: IntersectionType) -> OriginalIntersection {
+        let id = OriginalIntersection {
+            osm_node_id: self.id(),
+        };
+        self.raw.intersections.insert(
+            id,
+            RawIntersection {
+                point: Pt2D::new(x, y),
+                intersection_type: it,
+            },
+        );
+        id
+    }
+
+    // Lanes are given like "ds/ds", forwards and then backwards.
+    pub fn road(
+        &mut self,
+        i1: OriginalIntersection,
+        i2: OriginalIntersection,
+        lanes: &str,
+        tags: Vec<(&str, &str)>,
+    ) -> OriginalRoad {
+        let id = OriginalRoad {
+            osm_way_id: self.id(),
+            i1,
+            i2,
+        };
+        let mut osm_tags = std::collections::BTreeMap::new();
+        osm_tags.insert(
+            map_model::osm::SYNTHETIC_LANES.to_string(),
+            lanes.to_string(),
+        );
+        osm_tags.insert(map_model::osm::ENDPT_FWD.to_string(), "true".to_string());
+        osm_tags.insert(map_model::osm::ENDPT_BACK.to_string(), "true".to_string());
+        for (k, v) in tags {
+            osm_tags.insert(k.to_string(), v.to_string());
+        }
+        self.raw.roads.insert(
+            id,
+            RawRoad {
+                center_points: vec![
+                    self.raw.intersections[&i1].point,
+                    self.raw.intersections[&i2].point,
+                ],
+                osm_tags,
+                turn_restrictions: Vec::new(),
+            },
+        );
+        id
+    }
+
+    // Map::new treats any path it doesn't recognize as a synthetic map in JSON.
+    pub fn build(self) -> Map {
+        let path = format!("{}.json", self.raw.name);
+        abstutil::write_json(path.clone(), &self.raw);
+        Map::new(path, false, &mut Timer::throwaway())
+    }
+}
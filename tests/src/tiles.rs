@@ -0,0 +1,123 @@
+use crate::runner::TestRunner;
+use export_tiles::{command, encode_geometry, project, zigzag, Geometry, Protobuf, EXTENT};
+use geom::LonLat;
+
+pub fn run(t: &mut TestRunner) {
+    t.run_fast("protobuf_wire_format", |_| {
+        // The examples from https://developers.google.com/protocol-buffers/docs/encoding
+        let mut pb = Protobuf::new();
+        pb.uint(1, 150);
+        assert_eq!(pb.0, vec![0x08, 0x96, 0x01]);
+
+        let mut pb = Protobuf::new();
+        pb.string(2, "testing");
+        assert_eq!(
+            pb.0,
+            vec![0x12, 0x07, 0x74, 0x65, 0x73, 0x74, 0x69, 0x6e, 0x67]
+        );
+
+        let mut pb = Protobuf::new();
+        pb.packed(4, &vec![3, 270, 86942]);
+        assert_eq!(pb.0, vec![0x22, 0x06, 0x03, 0x8E, 0x02, 0x9E, 0xA7, 0x05]);
+
+        let mut inner = Protobuf::new();
+        inner.uint(1, 150);
+        let mut pb = Protobuf::new();
+        pb.message(3, &inner);
+        assert_eq!(pb.0, vec![0x1A, 0x03, 0x08, 0x96, 0x01]);
+
+        // Little-endian IEEE 754
+        let mut pb = Protobuf::new();
+        pb.double(3, 1.0);
+        assert_eq!(pb.0, vec![0x19, 0, 0, 0, 0, 0, 0, 0xF0, 0x3F]);
+    });
+
+    t.run_fast("zigzag_and_commands", |_| {
+        for (n, expected) in &[
+            (0, 0),
+            (-1, 1),
+            (1, 2),
+            (-2, 3),
+            (2_147_483_647, 4_294_967_294),
+            (-2_147_483_648, 4_294_967_295),
+        ] {
+            assert_eq!(zigzag(*n), *expected, "zigzag({})", n);
+        }
+
+        // From the vector tile spec
+        assert_eq!(command(1, 1), 9);
+        assert_eq!(command(2, 3), 26);
+        assert_eq!(command(7, 1), 15);
+    });
+
+    t.run_fast("web_mercator", |_| {
+        let close = |(x1, y1): (f64, f64), (x2, y2): (f64, f64)| {
+            (x1 - x2).abs() < 1e-9 && (y1 - y2).abs() < 1e-9
+        };
+        assert!(close(project(LonLat::new(0.0, 0.0), 0), (0.5, 0.5)));
+        assert!(close(project(LonLat::new(0.0, 0.0), 1), (1.0, 1.0)));
+        assert!(close(project(LonLat::new(-180.0, 0.0), 3), (0.0, 4.0)));
+        // The top edge of the world
+        assert!(close(
+            project(LonLat::new(180.0, 85.051_128_779_806_59), 2),
+            (4.0, 0.0)
+        ));
+        // Seattle, in tile 41/89 at zoom 8
+        let (x, y) = project(LonLat::new(-122.3, 47.65), 8);
+        assert_eq!((x.floor(), y.floor()), (41.0, 89.0));
+    });
+
+    t.run_fast("encode_geometry", |_| {
+        // Around the middle of the only tile at zoom 0
+        let pt = |x: i32, y: i32| tile_pt(2000 + x, 2000 + y);
+
+        assert_eq!(
+            encode_geometry(&Geometry::Point(pt(0, 0)), 0, 0, 0),
+            vec![9, 4000, 4000]
+        );
+        assert_eq!(
+            encode_geometry(
+                &Geometry::Line(vec![pt(0, 0), pt(10, 0), pt(10, -5)]),
+                0,
+                0,
+                0
+            ),
+            vec![9, 4000, 4000, 18, 20, 0, 0, 9]
+        );
+        // Repeated points collapse, leaving nothing to draw
+        assert!(encode_geometry(&Geometry::Line(vec![pt(0, 0), pt(0, 0)]), 0, 0, 0).is_empty());
+
+        // Clockwise with y pointing down, and closed, which gets dropped
+        let clockwise = vec![pt(0, 0), pt(10, 0), pt(10, 10), pt(0, 10), pt(0, 0)];
+        assert_eq!(
+            encode_geometry(&Geometry::Polygon(clockwise), 0, 0, 0),
+            vec![9, 4000, 4000, 26, 20, 0, 0, 20, 19, 0, 15]
+        );
+        // The other way around gets reversed to be clockwise
+        let counter = vec![pt(0, 0), pt(0, 10), pt(10, 10), pt(10, 0)];
+        assert_eq!(
+            encode_geometry(&Geometry::Polygon(counter), 0, 0, 0),
+            vec![9, 4020, 4000, 26, 0, 20, 19, 0, 0, 19, 15]
+        );
+        // No area
+        let flat = vec![pt(0, 0), pt(5, 0), pt(10, 0)];
+        assert!(encode_geometry(&Geometry::Polygon(flat), 0, 0, 0).is_empty());
+
+        // Coordinates are relative to the tile
+        assert_eq!(
+            encode_geometry(&Geometry::Point(LonLat::new(0.0, 0.0)), 1, 1, 1),
+            vec![9, 0, 0]
+        );
+    });
+}
+
+// The inverse of project at zoom 0, from tile units
+fn tile_pt(x: i32, y: i32) -> LonLat {
+    let x = f64::from(x) / f64::from(EXTENT);
+    let y = f64::from(y) / f64::from(EXTENT);
+    let lat = (std::f64::consts::PI * (1.0 - 2.0 * y))
+        .sinh()
+        .atan()
+        .to_degrees();
+    LonLat::new(x * 360.0 - 180.0, lat)
+}